@@ -1,4 +1,5 @@
 pub mod bearer;
+pub mod scopes;
 pub mod token_validator;
 pub mod types;
 
@@ -6,7 +7,7 @@ pub mod types;
 pub mod token_store;
 
 pub use bearer::extract_bearer_token;
-pub use token_validator::{TokenValidator, UserInfo};
+pub use token_validator::{Identity, TokenValidator, UserClaims, UserInfo};
 pub use types::{AuthError, TokenSet};
 
 #[cfg(feature = "stdio-mcp")]
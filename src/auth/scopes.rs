@@ -0,0 +1,165 @@
+//! OAuth scopes: mapping a client's requested scope to Miro scopes and
+//! deciding which MCP operations a set of granted scopes allows
+//!
+//! Clients ask for Miro scope names directly. Only scopes the server has a use
+//! for are forwarded to Miro, so a client can't obtain more access through the
+//! proxy than its tools need.
+
+use thiserror::Error;
+use tracing::warn;
+
+/// Read boards and their items
+pub const BOARDS_READ: &str = "boards:read";
+
+/// Create, update and delete boards and their items
+pub const BOARDS_WRITE: &str = "boards:write";
+
+/// Read the user's profile
+pub const IDENTITY_READ: &str = "identity:read";
+
+/// Read the user's team and organization
+pub const TEAM_READ: &str = "team:read";
+
+/// Scopes a client may request, in the order they are reported
+pub const SUPPORTED_SCOPES: [&str; 4] = [BOARDS_READ, BOARDS_WRITE, IDENTITY_READ, TEAM_READ];
+
+/// Scopes granted when the authorization request has no scope parameter
+pub const DEFAULT_SCOPES: [&str; 2] = [BOARDS_READ, BOARDS_WRITE];
+
+/// [`DEFAULT_SCOPES`] as owned strings
+pub fn default_scopes() -> Vec<String> {
+    DEFAULT_SCOPES.iter().map(|s| s.to_string()).collect()
+}
+
+/// Errors from scope mapping
+#[derive(Error, Debug, PartialEq)]
+pub enum ScopeError {
+    #[error("None of the requested scopes are supported: {0}")]
+    Unsupported(String),
+}
+
+/// Map a requested scope parameter to the Miro scopes to ask for
+///
+/// A missing or empty scope gets [`DEFAULT_SCOPES`]. Unsupported scopes are
+/// dropped (RFC 6749 §3.3 lets the server grant less than requested), and
+/// `boards:write` brings `boards:read` along since every write tool reads the
+/// board first. Fails if nothing supported is left.
+pub fn map_requested_scope(requested: Option<&str>) -> Result<Vec<String>, ScopeError> {
+    let requested: Vec<&str> = requested.unwrap_or_default().split_whitespace().collect();
+    if requested.is_empty() {
+        return Ok(default_scopes());
+    }
+
+    for scope in &requested {
+        if !SUPPORTED_SCOPES.contains(scope) {
+            warn!(scope = %scope, "Dropping unsupported scope from request");
+        }
+    }
+
+    let granted: Vec<String> = SUPPORTED_SCOPES
+        .iter()
+        .filter(|supported| {
+            requested.contains(supported)
+                || (**supported == BOARDS_READ && requested.contains(&BOARDS_WRITE))
+        })
+        .map(|s| s.to_string())
+        .collect();
+
+    if granted.is_empty() {
+        return Err(ScopeError::Unsupported(requested.join(" ")));
+    }
+    Ok(granted)
+}
+
+/// Scopes both in `granted` and in Miro's answer, keeping `granted`'s order
+///
+/// Miro may grant less than we asked for (e.g. the user's plan lacks a scope);
+/// when it doesn't report scopes, the request is assumed granted in full.
+pub fn intersect(granted: &[String], miro_scope: Option<&str>) -> Vec<String> {
+    match miro_scope {
+        Some(miro_scope) => {
+            let miro: Vec<&str> = miro_scope.split_whitespace().collect();
+            granted
+                .iter()
+                .filter(|scope| miro.contains(&scope.as_str()))
+                .cloned()
+                .collect()
+        }
+        None => granted.to_vec(),
+    }
+}
+
+/// What a scope lets the client do, as shown on the consent page
+pub fn describe(scope: &str) -> &'static str {
+    match scope {
+        BOARDS_READ => "View your boards and their content",
+        BOARDS_WRITE => "Create, change and delete boards and their content",
+        IDENTITY_READ => "View your name and email address",
+        TEAM_READ => "View your team and organization",
+        _ => "Unrecognized permission",
+    }
+}
+
+/// Scope needed to call a tool, based on its read-only hint
+pub fn required_for_tool(read_only: bool) -> &'static str {
+    if read_only {
+        BOARDS_READ
+    } else {
+        BOARDS_WRITE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_scope_gets_defaults() {
+        assert_eq!(
+            map_requested_scope(None).unwrap(),
+            vec!["boards:read", "boards:write"]
+        );
+        assert_eq!(
+            map_requested_scope(Some("  ")).unwrap(),
+            vec!["boards:read", "boards:write"]
+        );
+    }
+
+    #[test]
+    fn test_read_only_request() {
+        assert_eq!(
+            map_requested_scope(Some("boards:read")).unwrap(),
+            vec!["boards:read"]
+        );
+    }
+
+    #[test]
+    fn test_write_implies_read() {
+        assert_eq!(
+            map_requested_scope(Some("team:read boards:write")).unwrap(),
+            vec!["boards:read", "boards:write", "team:read"]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_scopes_are_dropped() {
+        assert_eq!(
+            map_requested_scope(Some("boards:read auditlogs:read")).unwrap(),
+            vec!["boards:read"]
+        );
+        assert_eq!(
+            map_requested_scope(Some("auditlogs:read")),
+            Err(ScopeError::Unsupported("auditlogs:read".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_intersect_with_miro_scope() {
+        let granted = vec!["boards:read".to_string(), "team:read".to_string()];
+        assert_eq!(
+            intersect(&granted, Some("boards:write boards:read")),
+            vec!["boards:read"]
+        );
+        assert_eq!(intersect(&granted, None), granted);
+    }
+}
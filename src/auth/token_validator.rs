@@ -1,6 +1,8 @@
+use crate::auth::scopes;
 use crate::auth::types::AuthError;
 use lru::LruCache;
 use reqwest::Client;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::num::NonZeroUsize;
 use std::sync::Mutex;
@@ -16,6 +18,9 @@ pub struct UserInfo {
     pub team_id: String,
     /// Scopes granted to the token
     pub scopes: Vec<String>,
+    /// Names and organization Miro reported with the token
+    #[serde(default)]
+    pub identity: Identity,
     /// Timestamp when this cache entry was created
     #[serde(skip)]
     cached_at: u64,
@@ -33,10 +38,17 @@ impl UserInfo {
             user_id,
             team_id,
             scopes,
+            identity: Identity::default(),
             cached_at: now,
         }
     }
 
+    /// Attach the names and organization Miro reported
+    pub fn with_identity(mut self, identity: Identity) -> Self {
+        self.identity = identity;
+        self
+    }
+
     /// Check if this cache entry is expired (5 minute TTL)
     pub fn is_expired(&self) -> bool {
         let now = SystemTime::now()
@@ -47,6 +59,73 @@ impl UserInfo {
         const TTL_SECONDS: u64 = 5 * 60; // 5 minutes
         now - self.cached_at > TTL_SECONDS
     }
+
+    /// Check whether the token was granted a scope
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|granted| granted == scope)
+    }
+
+    /// Claims the token's scopes allow disclosing: name and email need
+    /// `identity:read`, team and organization details `team:read`
+    pub fn claims(&self) -> UserClaims {
+        let identity = self.has_scope(scopes::IDENTITY_READ);
+        let team = self.has_scope(scopes::TEAM_READ);
+        let allowed = |allowed: bool, value: &Option<String>| value.clone().filter(|_| allowed);
+
+        UserClaims {
+            sub: self.user_id.clone(),
+            name: allowed(identity, &self.identity.user_name),
+            email: allowed(identity, &self.identity.email),
+            team_id: self.team_id.clone(),
+            team_name: allowed(team, &self.identity.team_name),
+            organization_id: allowed(team, &self.identity.organization_id),
+            organization_name: allowed(team, &self.identity.organization_name),
+            scope: self.scopes.join(" "),
+        }
+    }
+}
+
+/// Names and organization behind a token, when Miro reports them
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Identity {
+    /// User's display name
+    pub user_name: Option<String>,
+    /// User's email address (only known for proxy-issued tokens)
+    pub email: Option<String>,
+    /// Team name
+    pub team_name: Option<String>,
+    /// Miro organization ID
+    pub organization_id: Option<String>,
+    /// Miro organization name
+    pub organization_name: Option<String>,
+}
+
+/// OpenID Connect-style claims about the user behind a token
+///
+/// Returned by `/oauth/userinfo` and the `whoami` tool.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct UserClaims {
+    /// Miro user ID
+    pub sub: String,
+    /// User's display name (identity:read)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// User's email address (identity:read)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// Miro team ID
+    pub team_id: String,
+    /// Team name (team:read)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team_name: Option<String>,
+    /// Miro organization ID (team:read)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<String>,
+    /// Organization name (team:read)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization_name: Option<String>,
+    /// Space-separated scopes granted to the token
+    pub scope: String,
 }
 
 /// Response from Miro's token introspection endpoint
@@ -58,6 +137,21 @@ struct MiroTokenResponse {
     team: String,
     #[serde(rename = "scopes")]
     scopes: String, // Space-separated string
+    /// Named user, team and organization, when included
+    #[serde(default, rename = "user")]
+    user_details: Option<MiroEntity>,
+    #[serde(default, rename = "team")]
+    team_details: Option<MiroEntity>,
+    #[serde(default)]
+    organization: Option<MiroEntity>,
+}
+
+/// A user, team or organization in Miro's token response
+#[derive(Debug, Deserialize)]
+struct MiroEntity {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
 }
 
 /// Token validator with LRU caching
@@ -200,11 +294,29 @@ impl TokenValidator {
             .map(|s| s.to_string())
             .collect();
 
-        Ok(UserInfo::new(
-            miro_response.user,
-            miro_response.team,
-            scopes,
-        ))
+        let identity = Identity {
+            user_name: miro_response.user_details.and_then(|user| user.name),
+            email: None,
+            team_name: miro_response.team_details.and_then(|team| team.name),
+            organization_name: miro_response
+                .organization
+                .as_ref()
+                .and_then(|org| org.name.clone()),
+            organization_id: miro_response.organization.map(|org| org.id),
+        };
+
+        Ok(UserInfo::new(miro_response.user, miro_response.team, scopes).with_identity(identity))
+    }
+
+    /// Drop a token from the cache so its next use is revalidated with Miro
+    ///
+    /// Called after revocation; otherwise a revoked token would keep working
+    /// until its cache entry expires.
+    pub fn evict(&self, token: &str) {
+        let mut cache = self.cache.lock().unwrap();
+        if cache.pop(token).is_some() {
+            debug!("Evicted token from validation cache");
+        }
     }
 
     /// Get cache statistics (for testing and monitoring)
@@ -261,6 +373,52 @@ mod tests {
         assert!(user_info.is_expired());
     }
 
+    #[test]
+    fn test_evict_removes_cached_token() {
+        let validator = TokenValidator::new();
+        validator.cache.lock().unwrap().put(
+            "token".to_string(),
+            UserInfo::new("user123".to_string(), "team456".to_string(), vec![]),
+        );
+
+        validator.evict("token");
+        assert_eq!(validator.cache_stats().0, 0);
+    }
+
+    #[test]
+    fn test_claims_depend_on_scopes() {
+        let identity = Identity {
+            user_name: Some("Ada".to_string()),
+            email: Some("ada@example.com".to_string()),
+            team_name: Some("Design".to_string()),
+            organization_id: Some("org789".to_string()),
+            organization_name: Some("Example Inc".to_string()),
+        };
+        let user_info = UserInfo::new(
+            "user123".to_string(),
+            "team456".to_string(),
+            vec!["boards:read".to_string()],
+        )
+        .with_identity(identity.clone());
+
+        let claims = user_info.claims();
+        assert_eq!(claims.sub, "user123");
+        assert_eq!(claims.team_id, "team456");
+        assert_eq!(claims.name, None);
+        assert_eq!(claims.organization_id, None);
+
+        let user_info = UserInfo::new(
+            "user123".to_string(),
+            "team456".to_string(),
+            vec!["identity:read".to_string(), "team:read".to_string()],
+        )
+        .with_identity(identity);
+        let claims = user_info.claims();
+        assert_eq!(claims.email.as_deref(), Some("ada@example.com"));
+        assert_eq!(claims.organization_name.as_deref(), Some("Example Inc"));
+        assert_eq!(claims.scope, "identity:read team:read");
+    }
+
     #[test]
     fn test_token_validator_creation() {
         let validator = TokenValidator::new();
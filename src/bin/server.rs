@@ -69,7 +69,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cookie_manager = Arc::new(CookieManager::new(&config.encryption_key));
 
     #[cfg(feature = "oauth-proxy")]
-    let code_storage = CodeStorage::new();
+    let code_storage = CodeStorage::from_config(&config)?;
 
     // Start background cleanup task for expired authorization codes
     #[cfg(feature = "oauth-proxy")]
@@ -47,6 +47,163 @@ struct ConfigFile {
     /// Base URL for OAuth endpoints (e.g., https://your-server.com)
     #[serde(skip_serializing_if = "Option::is_none")]
     base_url: Option<String>,
+
+    /// Seconds between polls of subscribed board resources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    resource_poll_interval_secs: Option<u64>,
+
+    /// Maximum resource subscriptions per MCP session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_resource_subscriptions: Option<usize>,
+
+    /// Imports creating more objects than this require user confirmation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    confirm_import_threshold: Option<usize>,
+
+    /// OAuth proxy storage backend: "memory", "file" or "sqlite"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    oauth_storage: Option<String>,
+
+    /// Path of the OAuth proxy storage file (file and sqlite backends)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    oauth_storage_path: Option<String>,
+
+    /// Issue proxy-owned access tokens instead of returning Miro's tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    issue_proxy_tokens: Option<bool>,
+
+    /// Host patterns DCR redirect URIs must match (e.g. "claude.ai", "*.example.com")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dcr_allowed_redirect_hosts: Option<Vec<String>>,
+
+    /// Initial access token required to register clients
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dcr_initial_access_token: Option<String>,
+
+    /// Maximum number of registered clients
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dcr_max_clients: Option<usize>,
+
+    /// Seconds until client secrets issued by DCR expire
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dcr_client_secret_ttl_secs: Option<u64>,
+
+    /// Show a consent page before redirecting to Miro
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    require_consent: Option<bool>,
+
+    /// Client IDs that skip the consent page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    trusted_clients: Option<Vec<String>>,
+
+    /// Include user names and emails in logs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    log_pii: Option<bool>,
+
+    /// Directory snapshot tools read and write files in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    snapshot_dir: Option<String>,
+}
+
+/// Default seconds between polls of subscribed board resources
+pub const DEFAULT_RESOURCE_POLL_INTERVAL_SECS: u64 = 30;
+
+/// Default maximum resource subscriptions per MCP session
+pub const DEFAULT_MAX_RESOURCE_SUBSCRIPTIONS: usize = 10;
+
+/// Default number of objects an import may create without user confirmation
+pub const DEFAULT_CONFIRM_IMPORT_THRESHOLD: usize = 100;
+
+/// Where the OAuth proxy keeps client registrations and pending authorization codes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum StorageBackend {
+    /// In-process only; lost on restart
+    #[default]
+    Memory,
+    /// Single file encrypted with the configured encryption key
+    EncryptedFile(PathBuf),
+    /// SQLite database file
+    Sqlite(PathBuf),
+}
+
+impl StorageBackend {
+    /// Parse the backend name and path from configuration
+    pub fn parse(kind: Option<&str>, path: Option<&str>) -> Result<Self, ConfigError> {
+        let path = || {
+            path.filter(|path| !path.trim().is_empty())
+                .map(PathBuf::from)
+                .ok_or_else(|| {
+                    ConfigError::ParseError(format!(
+                        "oauth_storage \"{}\" needs a storage path",
+                        kind.unwrap_or_default()
+                    ))
+                })
+        };
+        match kind.map(|kind| kind.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("") | Some("memory") => Ok(StorageBackend::Memory),
+            Some("file") => Ok(StorageBackend::EncryptedFile(path()?)),
+            Some("sqlite") => Ok(StorageBackend::Sqlite(path()?)),
+            Some(other) => Err(ConfigError::ParseError(format!(
+                "Unknown oauth_storage \"{}\" (expected memory, file or sqlite)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Limits on Dynamic Client Registration, so a public deployment can't be
+/// flooded with junk registrations
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegistrationPolicy {
+    /// Host patterns redirect URIs must match; empty allows any host.
+    /// `*.example.com` matches subdomains of example.com.
+    pub allowed_redirect_hosts: Vec<String>,
+    /// Bearer token required to register clients (RFC 7591 initial access token)
+    pub initial_access_token: Option<String>,
+    /// Maximum number of registered clients
+    pub max_clients: Option<usize>,
+    /// Seconds until issued client secrets expire
+    pub client_secret_ttl_secs: Option<u64>,
+}
+
+impl RegistrationPolicy {
+    /// Whether a redirect URI host matches one of the allowed host patterns
+    pub fn allows_redirect_host(&self, host: &str) -> bool {
+        if self.allowed_redirect_hosts.is_empty() {
+            return true;
+        }
+        let host = host.to_ascii_lowercase();
+        self.allowed_redirect_hosts.iter().any(|pattern| {
+            let pattern = pattern.trim().to_ascii_lowercase();
+            match pattern.strip_prefix("*.") {
+                Some(domain) => host
+                    .strip_suffix(domain)
+                    .is_some_and(|subdomain| subdomain.ends_with('.') && subdomain.len() > 1),
+                None => host == pattern,
+            }
+        })
+    }
+}
+
+/// When /oauth/authorize asks the user to approve a client before sending
+/// them to Miro
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsentPolicy {
+    /// Show the consent page; off redirects to Miro straight away
+    pub required: bool,
+    /// Client IDs that never see the consent page
+    pub trusted_clients: Vec<String>,
+}
+
+impl ConsentPolicy {
+    /// Whether a client must be approved by the user before the Miro redirect
+    pub fn requires_consent(&self, client_id: &str) -> bool {
+        self.required
+            && !self
+                .trusted_clients
+                .iter()
+                .any(|trusted| trusted == client_id)
+    }
 }
 
 /// Configuration for Miro MCP Server
@@ -73,6 +230,34 @@ pub struct Config {
     /// Base URL for OAuth proxy endpoints (e.g., https://your-server.com)
     /// Used to construct authorization_endpoint and token_endpoint in metadata
     pub base_url: Option<String>,
+
+    /// Seconds between polls of subscribed board resources (modified_at diffing)
+    pub resource_poll_interval_secs: u64,
+
+    /// Maximum resource subscriptions per MCP session
+    pub max_resource_subscriptions: usize,
+
+    /// Imports creating more objects than this ask the user to confirm first
+    pub confirm_import_threshold: usize,
+
+    /// Storage for DCR registrations and pending authorization codes
+    pub oauth_storage: StorageBackend,
+
+    /// Issue proxy-owned tokens and keep Miro's tokens server-side
+    pub issue_proxy_tokens: bool,
+
+    /// Limits on Dynamic Client Registration
+    pub registration_policy: RegistrationPolicy,
+
+    /// Consent page shown before the Miro redirect
+    pub consent_policy: ConsentPolicy,
+
+    /// Log user names and emails; redacted unless enabled. Team and
+    /// organization are always logged for auditing.
+    pub log_pii: bool,
+
+    /// Directory snapshot files are confined to (export, import and diff tools)
+    pub snapshot_dir: PathBuf,
 }
 
 impl Config {
@@ -103,6 +288,11 @@ impl Config {
             None => [0u8; 32], // Dummy key for ADR-005 (not used)
         };
 
+        let oauth_storage = StorageBackend::parse(
+            config_file.oauth_storage.as_deref(),
+            config_file.oauth_storage_path.as_deref(),
+        )?;
+
         Ok(Config {
             client_id: config_file.client_id,
             client_secret: config_file.client_secret.unwrap_or_default(),
@@ -110,6 +300,34 @@ impl Config {
             encryption_key,
             port: config_file.port,
             base_url: config_file.base_url,
+            resource_poll_interval_secs: config_file
+                .resource_poll_interval_secs
+                .unwrap_or(DEFAULT_RESOURCE_POLL_INTERVAL_SECS),
+            max_resource_subscriptions: config_file
+                .max_resource_subscriptions
+                .unwrap_or(DEFAULT_MAX_RESOURCE_SUBSCRIPTIONS),
+            confirm_import_threshold: config_file
+                .confirm_import_threshold
+                .unwrap_or(DEFAULT_CONFIRM_IMPORT_THRESHOLD),
+            oauth_storage,
+            issue_proxy_tokens: config_file.issue_proxy_tokens.unwrap_or(false),
+            registration_policy: RegistrationPolicy {
+                allowed_redirect_hosts: config_file.dcr_allowed_redirect_hosts.unwrap_or_default(),
+                initial_access_token: config_file
+                    .dcr_initial_access_token
+                    .filter(|token| !token.is_empty()),
+                max_clients: config_file.dcr_max_clients,
+                client_secret_ttl_secs: config_file.dcr_client_secret_ttl_secs,
+            },
+            consent_policy: ConsentPolicy {
+                required: config_file.require_consent.unwrap_or(false),
+                trusted_clients: config_file.trusted_clients.unwrap_or_default(),
+            },
+            log_pii: config_file.log_pii.unwrap_or(false),
+            snapshot_dir: config_file
+                .snapshot_dir
+                .map(PathBuf::from)
+                .unwrap_or_else(Self::default_snapshot_dir),
         })
     }
 
@@ -125,6 +343,13 @@ impl Config {
         Ok(config_dir.join("config.json"))
     }
 
+    /// Default snapshot directory: ~/.config/mcp/miro-rust/snapshots
+    pub fn default_snapshot_dir() -> PathBuf {
+        dirs::home_dir()
+            .map(|home| home.join(".config/mcp/miro-rust/snapshots"))
+            .unwrap_or_else(|| PathBuf::from("snapshots"))
+    }
+
     /// Ensure configuration directory exists (creates if needed)
     pub fn ensure_config_dir() -> Result<PathBuf, ConfigError> {
         let config_dir = dirs::home_dir()
@@ -141,6 +366,16 @@ impl Config {
     /// Load configuration from environment variables
     /// Reads: MIRO_CLIENT_ID, MIRO_REDIRECT_URI, MCP_SERVER_PORT, BASE_URL
     /// Optional (for ADR-004 OAuth Proxy): MIRO_CLIENT_SECRET, MIRO_ENCRYPTION_KEY
+    /// Optional (resource subscriptions): MCP_RESOURCE_POLL_INTERVAL_SECS,
+    /// MCP_MAX_RESOURCE_SUBSCRIPTIONS
+    /// Optional (tool confirmation): MCP_CONFIRM_IMPORT_THRESHOLD
+    /// Optional (OAuth proxy storage): OAUTH_STORAGE, OAUTH_STORAGE_PATH
+    /// Optional (OAuth proxy tokens): OAUTH_ISSUE_PROXY_TOKENS
+    /// Optional (client registration): DCR_ALLOWED_REDIRECT_HOSTS (comma-separated),
+    /// DCR_INITIAL_ACCESS_TOKEN, DCR_MAX_CLIENTS, DCR_CLIENT_SECRET_TTL_SECS
+    /// Optional (consent page): OAUTH_REQUIRE_CONSENT, OAUTH_TRUSTED_CLIENTS (comma-separated)
+    /// Optional (logging): LOG_PII
+    /// Optional (snapshot tools): MCP_SNAPSHOT_DIR
     pub fn from_env_vars() -> Result<Self, ConfigError> {
         let client_id = std::env::var("MIRO_CLIENT_ID").map_err(|_| ConfigError::FileNotFound {
             path: "environment".to_string(),
@@ -172,6 +407,78 @@ impl Config {
 
         let base_url = std::env::var("BASE_URL").ok();
 
+        let resource_poll_interval_secs = std::env::var("MCP_RESOURCE_POLL_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(DEFAULT_RESOURCE_POLL_INTERVAL_SECS);
+
+        let max_resource_subscriptions = std::env::var("MCP_MAX_RESOURCE_SUBSCRIPTIONS")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(DEFAULT_MAX_RESOURCE_SUBSCRIPTIONS);
+
+        let confirm_import_threshold = std::env::var("MCP_CONFIRM_IMPORT_THRESHOLD")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(DEFAULT_CONFIRM_IMPORT_THRESHOLD);
+
+        let oauth_storage = StorageBackend::parse(
+            std::env::var("OAUTH_STORAGE").ok().as_deref(),
+            std::env::var("OAUTH_STORAGE_PATH").ok().as_deref(),
+        )?;
+
+        let issue_proxy_tokens = std::env::var("OAUTH_ISSUE_PROXY_TOKENS")
+            .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false);
+
+        let registration_policy = RegistrationPolicy {
+            allowed_redirect_hosts: std::env::var("DCR_ALLOWED_REDIRECT_HOSTS")
+                .map(|hosts| {
+                    hosts
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|host| !host.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            initial_access_token: std::env::var("DCR_INITIAL_ACCESS_TOKEN")
+                .ok()
+                .filter(|token| !token.is_empty()),
+            max_clients: std::env::var("DCR_MAX_CLIENTS")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok()),
+            client_secret_ttl_secs: std::env::var("DCR_CLIENT_SECRET_TTL_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok()),
+        };
+
+        let consent_policy = ConsentPolicy {
+            required: std::env::var("OAUTH_REQUIRE_CONSENT")
+                .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+                .unwrap_or(false),
+            trusted_clients: std::env::var("OAUTH_TRUSTED_CLIENTS")
+                .map(|clients| {
+                    clients
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|client| !client.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+
+        let log_pii = std::env::var("LOG_PII")
+            .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false);
+
+        let snapshot_dir = std::env::var("MCP_SNAPSHOT_DIR")
+            .ok()
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(Self::default_snapshot_dir);
+
         Ok(Config {
             client_id,
             client_secret,
@@ -179,6 +486,15 @@ impl Config {
             encryption_key,
             port,
             base_url,
+            resource_poll_interval_secs,
+            max_resource_subscriptions,
+            confirm_import_threshold,
+            oauth_storage,
+            issue_proxy_tokens,
+            registration_policy,
+            consent_policy,
+            log_pii,
+            snapshot_dir,
         })
     }
 
@@ -262,6 +578,52 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_redirect_host_patterns() {
+        let policy = RegistrationPolicy {
+            allowed_redirect_hosts: vec!["claude.ai".to_string(), "*.example.com".to_string()],
+            ..Default::default()
+        };
+        assert!(policy.allows_redirect_host("claude.ai"));
+        assert!(policy.allows_redirect_host("Claude.AI"));
+        assert!(policy.allows_redirect_host("app.example.com"));
+        assert!(!policy.allows_redirect_host("example.com"));
+        assert!(!policy.allows_redirect_host("evilexample.com"));
+        assert!(!policy.allows_redirect_host("claude.ai.evil.example"));
+
+        // No patterns: any host
+        assert!(RegistrationPolicy::default().allows_redirect_host("anything.example"));
+    }
+
+    #[test]
+    fn test_trusted_clients_skip_consent() {
+        let policy = ConsentPolicy {
+            required: true,
+            trusted_clients: vec!["claude".to_string()],
+        };
+        assert!(policy.requires_consent("someone-else"));
+        assert!(!policy.requires_consent("claude"));
+        assert!(!ConsentPolicy::default().requires_consent("someone-else"));
+    }
+
+    #[test]
+    fn test_parse_storage_backend() {
+        assert_eq!(
+            StorageBackend::parse(None, None).unwrap(),
+            StorageBackend::Memory
+        );
+        assert_eq!(
+            StorageBackend::parse(Some("SQLite"), Some("/data/oauth.db")).unwrap(),
+            StorageBackend::Sqlite(PathBuf::from("/data/oauth.db"))
+        );
+        assert_eq!(
+            StorageBackend::parse(Some("file"), Some("/data/oauth.enc")).unwrap(),
+            StorageBackend::EncryptedFile(PathBuf::from("/data/oauth.enc"))
+        );
+        assert!(StorageBackend::parse(Some("file"), None).is_err());
+        assert!(StorageBackend::parse(Some("redis"), Some("x")).is_err());
+    }
+
     #[test]
     #[serial_test::serial]
     fn test_from_env_vars_success() {
@@ -321,6 +683,24 @@ mod tests {
 
         let config = result.unwrap();
         assert_eq!(config.port, 3000); // Default port
+        assert_eq!(
+            config.resource_poll_interval_secs,
+            DEFAULT_RESOURCE_POLL_INTERVAL_SECS
+        );
+        assert_eq!(
+            config.max_resource_subscriptions,
+            DEFAULT_MAX_RESOURCE_SUBSCRIPTIONS
+        );
+        assert_eq!(
+            config.confirm_import_threshold,
+            DEFAULT_CONFIRM_IMPORT_THRESHOLD
+        );
+        assert_eq!(config.oauth_storage, StorageBackend::Memory);
+        assert!(!config.issue_proxy_tokens);
+        assert_eq!(config.registration_policy, RegistrationPolicy::default());
+        assert_eq!(config.consent_policy, ConsentPolicy::default());
+        assert!(!config.log_pii);
+        assert_eq!(config.snapshot_dir, Config::default_snapshot_dir());
 
         // Cleanup
         std::env::remove_var("MIRO_CLIENT_ID");
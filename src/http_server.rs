@@ -1,28 +1,47 @@
-use crate::auth::token_validator::UserInfo;
+use crate::auth::token_validator::{UserClaims, UserInfo};
 use crate::auth::{extract_bearer_token, TokenValidator};
 use crate::config::Config;
-use crate::mcp::{handle_initialize, handle_tools_call, handle_tools_list};
+use crate::mcp::protocol::ProtocolVersion;
+use crate::mcp::subscriptions::SubscriptionConfig;
+use crate::mcp::{
+    handle_initialize, handle_ping, handle_prompts_get, handle_prompts_list,
+    handle_resource_templates_list, handle_resources_list, handle_resources_read,
+    handle_resources_subscribe, handle_resources_unsubscribe, handle_tools_call, handle_tools_list,
+    insufficient_scope, method_scope, negotiate_protocol_version, progress_notification,
+    progress_token,
+};
 use crate::mcp::{
     oauth_authorization_server_metadata, oauth_metadata, JsonRpcError, JsonRpcRequest,
-    JsonRpcResponse,
+    JsonRpcResponse, McpSession, SessionStore, PROTOCOL_VERSION_HEADER, SESSION_HEADER,
 };
+use crate::miro::progress::{ProgressSink, ToolProgress};
 use axum::{
+    body::Bytes,
     extract::State,
-    http::{HeaderValue, Method, Request, StatusCode},
+    http::{HeaderMap, HeaderName, HeaderValue, Method, Request, StatusCode},
     middleware::{self, Next},
-    response::{IntoResponse, Response},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
     routing::{get, post},
     Json, Router,
 };
+use futures_util::stream;
+use serde_json::Value;
 use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
 use tower_http::cors::CorsLayer;
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 #[cfg(feature = "oauth-proxy")]
 use crate::oauth::{
-    authorize_handler, callback_handler, code_storage::CodeStorage, cookie_manager::CookieManager,
-    dcr::ClientRegistry, proxy_provider::MiroOAuthProvider, register_handler, token_handler,
+    authorize_handler, callback_handler, code_storage::CodeStorage, consent_handler,
+    cookie_manager::CookieManager, dcr::ClientRegistry, delete_client_handler, introspect_handler,
+    proxy_provider::MiroOAuthProvider, proxy_tokens::ProxyTokenStore, read_client_handler,
+    register_handler, revoke_handler, token_handler, update_client_handler,
 };
 
 /// Health check endpoint
@@ -30,59 +49,497 @@ async fn health_check() -> impl IntoResponse {
     (StatusCode::OK, "OK")
 }
 
-/// MCP Protocol endpoint for JSON-RPC 2.0 requests
+/// MCP Protocol endpoint for JSON-RPC 2.0 requests (streamable HTTP POST)
 ///
 /// Handles MCP methods:
-/// - initialize: Handshake and capability negotiation
+/// - initialize: Handshake and capability negotiation (issues `Mcp-Session-Id`)
+/// - ping: Liveness check
 /// - tools/list: List available tools
 /// - tools/call: Execute a tool
+/// - resources/*, prompts/*: Board resources and workflow prompts
+///
+/// The body may be a single message or, before protocol version 2025-06-18, a
+/// JSON-RPC batch array. Notifications produce no response; a body made only of
+/// notifications gets 202 Accepted.
+/// Malformed JSON is answered with a -32700 parse error.
+///
+/// Requests after initialize may carry `Mcp-Session-Id`; unknown sessions get
+/// 404 so the client re-initializes. `tools/call` answers with a
+/// `text/event-stream` body when the client accepts it, other methods with JSON.
+/// Tool calls with `_meta.progressToken` get `notifications/progress` on that
+/// stream (or the session's GET stream), and `notifications/cancelled` stops a
+/// running call made in the same session.
 ///
 /// Requires Bearer token authentication (provided by middleware).
 /// Token and user info are extracted from request extensions.
 async fn mcp_endpoint(
+    State(state): State<AppStateADR002>,
     axum::Extension(token): axum::Extension<Arc<String>>,
     axum::Extension(user_info): axum::Extension<Arc<UserInfo>>,
-    Json(req): Json<JsonRpcRequest>,
-) -> impl IntoResponse {
-    // Validate JSON-RPC request format
-    if let Err(e) = req.validate() {
-        error!("Invalid JSON-RPC request: {}", e);
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let (messages, is_batch) = match serde_json::from_slice::<Value>(&body) {
+        Ok(Value::Array(messages)) => (messages, true),
+        Ok(message) => (vec![message], false),
+        Err(e) => {
+            warn!(error = %e, "Failed to parse JSON-RPC body");
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(JsonRpcResponse::error(
+                    JsonRpcError::parse_error(format!("Parse error: {}", e)),
+                    None,
+                )),
+            )
+                .into_response();
+        }
+    };
+    if messages.is_empty() {
         return (
             StatusCode::BAD_REQUEST,
             Json(JsonRpcResponse::error(
-                JsonRpcError::invalid_request(e),
-                req.id.clone(),
+                JsonRpcError::invalid_request("Batch must contain at least one request"),
+                None,
             )),
+        )
+            .into_response();
+    }
+
+    let requests: Vec<Result<JsonRpcRequest, Box<JsonRpcResponse>>> =
+        messages.into_iter().map(parse_request).collect();
+
+    // A lone invalid message keeps the 400 status; batches report errors per entry
+    if let [Err(response)] = requests.as_slice() {
+        if !is_batch {
+            return (StatusCode::BAD_REQUEST, Json(response.as_ref().clone())).into_response();
+        }
+    }
+
+    let initialize_request = match requests.as_slice() {
+        [Ok(req)] if !is_batch && req.method == "initialize" => Some(req),
+        _ => None,
+    };
+    let initialize = initialize_request.is_some();
+    let session = if let Some(req) = initialize_request {
+        // A rejected version is reported by handle_initialize, without a session
+        negotiate_protocol_version(req)
+            .ok()
+            .map(|version| state.sessions.create(&user_info.user_id, version))
+    } else {
+        match session_id(&headers) {
+            Some(id) => match state.sessions.get(id, &user_info.user_id) {
+                Some(session) => Some(session),
+                None => {
+                    warn!(session_id = %id, "Unknown MCP session");
+                    return (
+                        StatusCode::NOT_FOUND,
+                        Json(JsonRpcResponse::error(
+                            JsonRpcError::invalid_request("Unknown or expired MCP session"),
+                            None,
+                        )),
+                    )
+                        .into_response();
+                }
+            },
+            None => None,
+        }
+    };
+    if let Some(session) = &session {
+        session.set_token(token.clone());
+    }
+
+    let protocol_version = match protocol_version_header(&headers) {
+        Some(Ok(version)) => version,
+        Some(Err(e)) => {
+            warn!(error = %e.message, "Unsupported MCP-Protocol-Version header");
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(JsonRpcResponse::error(e, None)),
+            )
+                .into_response();
+        }
+        None => session
+            .as_ref()
+            .map(|session| session.protocol_version())
+            .unwrap_or(ProtocolVersion::DEFAULT),
+    };
+    if is_batch && !protocol_version.supports_batching() {
+        warn!(protocol_version = %protocol_version, "JSON-RPC batch rejected");
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(JsonRpcResponse::error(
+                JsonRpcError::invalid_request("batching is not supported in this protocol version"),
+                None,
+            )),
+        )
+            .into_response();
+    }
+
+    let stream_response = accepts_event_stream(&headers)
+        && requests.iter().any(|request| {
+            matches!(request, Ok(req) if req.method == "tools/call" && !req.is_notification())
+        });
+
+    if stream_response {
+        // Progress notifications go out on the response stream ahead of the result
+        let (tx, rx) = mpsc::unbounded_channel::<Value>();
+        tokio::spawn(async move {
+            let responses = process_requests(
+                requests,
+                is_batch,
+                &user_info,
+                &token,
+                session.as_ref(),
+                protocol_version,
+                Some(&tx),
+            )
+            .await;
+            let _ = tx.send(response_body(&responses, is_batch));
+        });
+        let events = stream::unfold(rx, |mut rx| async move {
+            let message = rx.recv().await?;
+            Some((Event::default().event("message").json_data(&message), rx))
+        });
+        return Sse::new(events).into_response();
+    }
+
+    let responses = process_requests(
+        requests,
+        is_batch,
+        &user_info,
+        &token,
+        session.as_ref(),
+        protocol_version,
+        None,
+    )
+    .await;
+    if responses.is_empty() {
+        return StatusCode::ACCEPTED.into_response();
+    }
+    let mut http_response =
+        (StatusCode::OK, Json(response_body(&responses, is_batch))).into_response();
+
+    if let Some(session) = session.filter(|_| initialize) {
+        if let Ok(value) = HeaderValue::from_str(session.id()) {
+            http_response
+                .headers_mut()
+                .insert(HeaderName::from_static(SESSION_HEADER), value);
+        }
+    }
+
+    http_response
+}
+
+/// Handle every message of a POST body in order, collecting the responses
+///
+/// `stream` is the POST's event stream, when the response is streamed; tool
+/// progress goes there, otherwise to the session's `GET /mcp` stream.
+async fn process_requests(
+    requests: Vec<Result<JsonRpcRequest, Box<JsonRpcResponse>>>,
+    is_batch: bool,
+    user_info: &Arc<UserInfo>,
+    token: &Arc<String>,
+    session: Option<&Arc<McpSession>>,
+    version: ProtocolVersion,
+    stream: Option<&mpsc::UnboundedSender<Value>>,
+) -> Vec<JsonRpcResponse> {
+    let mut responses = Vec::with_capacity(requests.len());
+    for request in requests {
+        let req = match request {
+            Ok(req) => req,
+            Err(response) => {
+                responses.push(*response);
+                continue;
+            }
+        };
+
+        info!(
+            method = %req.method,
+            user_id = %user_info.user_id,
+            "Processing MCP request"
         );
+
+        if req.is_notification() {
+            handle_notification(&req, session);
+        } else if is_batch && req.method == "initialize" {
+            responses.push(JsonRpcResponse::error(
+                JsonRpcError::invalid_request("initialize must not be part of a batch"),
+                req.id.clone(),
+            ));
+        } else if req.method == "tools/call" {
+            let progress = tool_progress(&req, session, stream);
+            responses.push(dispatch(&req, user_info, token, session, version, &progress).await);
+            if let (Some(session), Some(id)) = (session, req.id.as_ref()) {
+                session.finish_request(id);
+            }
+        } else {
+            responses.push(
+                dispatch(
+                    &req,
+                    user_info,
+                    token,
+                    session,
+                    version,
+                    &ToolProgress::none(),
+                )
+                .await,
+            );
+        }
     }
+    responses
+}
 
-    info!(
-        method = %req.method,
-        user_id = %user_info.user_id,
-        "Processing MCP request"
-    );
+/// Progress and cancellation for a tools/call request
+///
+/// The call can be cancelled through its session; progress is only reported
+/// when the request carries `_meta.progressToken` and there's a stream to
+/// report on.
+fn tool_progress(
+    req: &JsonRpcRequest,
+    session: Option<&Arc<McpSession>>,
+    stream: Option<&mpsc::UnboundedSender<Value>>,
+) -> ToolProgress {
+    let mut progress = ToolProgress::none();
+    if let (Some(session), Some(id)) = (session, req.id.as_ref()) {
+        progress = progress.with_cancel_flag(session.track_request(id));
+    }
+    let Some(progress_token) = progress_token(req) else {
+        return progress;
+    };
+
+    let sink: Option<ProgressSink> = match (stream, session) {
+        (Some(stream), _) => {
+            let stream = stream.clone();
+            Some(Arc::new(move |update| {
+                let notification = progress_notification(&progress_token, update);
+                if let Ok(message) = serde_json::to_value(notification) {
+                    let _ = stream.send(message);
+                }
+            }))
+        }
+        (None, Some(session)) => {
+            let session = session.clone();
+            Some(Arc::new(move |update| {
+                session.notify(progress_notification(&progress_token, update));
+            }))
+        }
+        (None, None) => None,
+    };
+    progress.with_sink(sink)
+}
+
+/// JSON body for the collected responses: an array for batches
+fn response_body(responses: &[JsonRpcResponse], is_batch: bool) -> Value {
+    if is_batch {
+        serde_json::to_value(responses)
+    } else {
+        serde_json::to_value(&responses[0])
+    }
+    .unwrap_or(Value::Null)
+}
+
+/// Deserialize and validate one message of a JSON-RPC body
+fn parse_request(message: Value) -> Result<JsonRpcRequest, Box<JsonRpcResponse>> {
+    let id = message.get("id").cloned().filter(|id| !id.is_null());
+    let req = serde_json::from_value::<JsonRpcRequest>(message).map_err(|e| {
+        error!("Invalid JSON-RPC request: {}", e);
+        Box::new(JsonRpcResponse::error(
+            JsonRpcError::invalid_request(format!("Invalid request: {}", e)),
+            id.clone(),
+        ))
+    })?;
+
+    if let Err(e) = req.validate() {
+        error!("Invalid JSON-RPC request: {}", e);
+        return Err(Box::new(JsonRpcResponse::error(
+            JsonRpcError::invalid_request(e),
+            id,
+        )));
+    }
+    Ok(req)
+}
+
+/// Handle a client notification; notifications never get a response
+fn handle_notification(req: &JsonRpcRequest, session: Option<&Arc<McpSession>>) {
+    match req.method.as_str() {
+        "notifications/initialized" => info!("Client finished initialization"),
+        "notifications/cancelled" => {
+            let params = req.params.as_ref();
+            let Some(request_id) = params.and_then(|p| p.get("requestId")) else {
+                warn!("notifications/cancelled without requestId");
+                return;
+            };
+            let reason = params
+                .and_then(|p| p.get("reason"))
+                .and_then(|r| r.as_str())
+                .unwrap_or("none given");
+            // Cancellations are scoped to the session the request was made in
+            let cancelled = session.is_some_and(|session| session.cancel_request(request_id));
+            info!(
+                request_id = %request_id,
+                reason = %reason,
+                cancelled,
+                "Client cancelled request"
+            );
+        }
+        method => debug!(method = %method, "Ignoring MCP notification"),
+    }
+}
+
+/// Route a JSON-RPC request to its method handler
+async fn dispatch(
+    req: &JsonRpcRequest,
+    user_info: &Arc<UserInfo>,
+    token: &Arc<String>,
+    session: Option<&Arc<McpSession>>,
+    version: ProtocolVersion,
+    progress: &ToolProgress,
+) -> JsonRpcResponse {
+    if let Some(scope) = method_scope(&req.method) {
+        if let Some(response) = insufficient_scope(req, user_info, scope) {
+            return response;
+        }
+    }
 
-    // Route to appropriate handler
-    let response = match req.method.as_str() {
+    match req.method.as_str() {
         "initialize" => {
             info!("Handling initialize request");
-            handle_initialize(&req, &user_info)
+            handle_initialize(req, user_info)
         }
+        "ping" => handle_ping(req),
         "tools/list" => {
             info!("Handling tools/list request");
-            handle_tools_list(&req, &user_info)
+            handle_tools_list(req, user_info, version)
         }
         "tools/call" => {
             info!("Handling tools/call request");
-            handle_tools_call(&req, &user_info, &token).await
+            handle_tools_call(req, user_info, token, version, progress).await
+        }
+        "resources/list" => {
+            info!("Handling resources/list request");
+            handle_resources_list(req, user_info, token).await
+        }
+        "resources/templates/list" => {
+            info!("Handling resources/templates/list request");
+            handle_resource_templates_list(req)
+        }
+        "resources/read" => {
+            info!("Handling resources/read request");
+            handle_resources_read(req, user_info, token).await
+        }
+        "resources/subscribe" => {
+            info!("Handling resources/subscribe request");
+            handle_resources_subscribe(req, session, token)
+        }
+        "resources/unsubscribe" => {
+            info!("Handling resources/unsubscribe request");
+            handle_resources_unsubscribe(req, session)
+        }
+        "prompts/list" => {
+            info!("Handling prompts/list request");
+            handle_prompts_list(req, version)
+        }
+        "prompts/get" => {
+            info!("Handling prompts/get request");
+            handle_prompts_get(req, user_info, token).await
         }
         method => {
             warn!(method = %method, "Unknown MCP method");
             JsonRpcResponse::error(JsonRpcError::method_not_found(method), req.id.clone())
         }
+    }
+}
+
+/// Server→client event stream for a session (streamable HTTP GET)
+///
+/// Carries notifications such as `notifications/resources/updated`. The stream
+/// ends when the session is terminated or expires.
+async fn mcp_event_stream(
+    State(state): State<AppStateADR002>,
+    axum::Extension(token): axum::Extension<Arc<String>>,
+    axum::Extension(user_info): axum::Extension<Arc<UserInfo>>,
+    headers: HeaderMap,
+) -> Response {
+    if !accepts_event_stream(&headers) {
+        return (
+            StatusCode::NOT_ACCEPTABLE,
+            "GET /mcp requires Accept: text/event-stream",
+        )
+            .into_response();
+    }
+    let Some(id) = session_id(&headers) else {
+        return (StatusCode::BAD_REQUEST, "Mcp-Session-Id header required").into_response();
+    };
+    let Some(session) = state.sessions.get(id, &user_info.user_id) else {
+        return (StatusCode::NOT_FOUND, "Unknown or expired MCP session").into_response();
     };
+    session.set_token(token);
+
+    info!(session_id = %session.id(), "MCP event stream opened");
+
+    // Hold only the receiver so the stream closes once the session is dropped
+    let events = stream::unfold(session.events(), |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(notification) => {
+                    let event = Event::default().event("message").json_data(&notification);
+                    return Some((event, receiver));
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(skipped = skipped, "MCP event stream lagged, events dropped");
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
 
-    (StatusCode::OK, Json(response))
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// Terminate a session (streamable HTTP DELETE)
+async fn mcp_delete_session(
+    State(state): State<AppStateADR002>,
+    axum::Extension(user_info): axum::Extension<Arc<UserInfo>>,
+    headers: HeaderMap,
+) -> StatusCode {
+    match session_id(&headers) {
+        Some(id) if state.sessions.remove(id, &user_info.user_id) => StatusCode::NO_CONTENT,
+        Some(_) => StatusCode::NOT_FOUND,
+        None => StatusCode::BAD_REQUEST,
+    }
+}
+
+fn session_id(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(SESSION_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| !id.is_empty())
+}
+
+/// Protocol version from the `MCP-Protocol-Version` header, if sent
+fn protocol_version_header(headers: &HeaderMap) -> Option<Result<ProtocolVersion, JsonRpcError>> {
+    let value = headers.get(PROTOCOL_VERSION_HEADER)?;
+    let version = value
+        .to_str()
+        .ok()
+        .and_then(ProtocolVersion::parse)
+        .ok_or_else(|| {
+            JsonRpcError::invalid_request(format!(
+                "Unsupported MCP-Protocol-Version: {}",
+                String::from_utf8_lossy(value.as_bytes())
+            ))
+        });
+    Some(version)
+}
+
+fn accepts_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(axum::http::header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .any(|accept| accept.contains("text/event-stream"))
 }
 
 //
@@ -130,6 +587,7 @@ async fn correlation_id_middleware(mut request: Request<axum::body::Body>, next:
 pub struct AppStateADR002 {
     pub token_validator: Arc<TokenValidator>,
     pub config: Arc<Config>,
+    pub sessions: SessionStore,
     #[cfg(feature = "oauth-proxy")]
     pub oauth_provider: Arc<MiroOAuthProvider>,
     #[cfg(feature = "oauth-proxy")]
@@ -138,6 +596,9 @@ pub struct AppStateADR002 {
     pub client_registry: ClientRegistry,
     #[cfg(feature = "oauth-proxy")]
     pub code_storage: CodeStorage,
+    /// Set when the proxy issues its own tokens (`issue_proxy_tokens`)
+    #[cfg(feature = "oauth-proxy")]
+    pub proxy_tokens: Option<ProxyTokenStore>,
 }
 
 /// Bearer token validation middleware for ADR-002
@@ -155,7 +616,8 @@ async fn bearer_auth_middleware_adr002(
         .unwrap_or_else(|| "unknown".to_string());
 
     // Extract Bearer token from Authorization header
-    let token = match extract_bearer_token(request.headers()) {
+    #[cfg_attr(not(feature = "oauth-proxy"), allow(unused_mut))]
+    let mut token = match extract_bearer_token(request.headers()) {
         Ok(token) => token,
         Err(e) => {
             warn!(
@@ -176,8 +638,44 @@ async fn bearer_auth_middleware_adr002(
         }
     };
 
+    // Proxy-issued tokens stand in for a Miro token kept server-side
+    #[cfg(feature = "oauth-proxy")]
+    let mut proxy_grant = None;
+    #[cfg(feature = "oauth-proxy")]
+    if let Some(proxy_tokens) = &state.proxy_tokens {
+        match proxy_tokens.resolve(&token, &state.oauth_provider).await {
+            Ok(access) => {
+                debug!(
+                    request_id = %request_id,
+                    client_id = %access.client_id,
+                    scope = %access.scope,
+                    "Proxy token resolved"
+                );
+                token = access.miro_access_token;
+                proxy_grant = Some((access.scope, access.user));
+            }
+            Err(e) => {
+                warn!(
+                    request_id = %request_id,
+                    error = %e,
+                    auth_stage = "proxy_token_resolution",
+                    "Proxy token resolution failed"
+                );
+                return Ok((
+                    StatusCode::UNAUTHORIZED,
+                    [(
+                        axum::http::header::WWW_AUTHENTICATE,
+                        "Bearer realm=\"miro-mcp-server\", error=\"invalid_token\"",
+                    )],
+                )
+                    .into_response());
+            }
+        }
+    }
+
     // Validate token with Miro API (with caching)
-    let user_info = match state.token_validator.validate_token(&token).await {
+    #[cfg_attr(not(feature = "oauth-proxy"), allow(unused_mut))]
+    let mut user_info = match state.token_validator.validate_token(&token).await {
         Ok(user_info) => user_info,
         Err(e) => {
             warn!(
@@ -198,10 +696,28 @@ async fn bearer_auth_middleware_adr002(
         }
     };
 
+    #[cfg(feature = "oauth-proxy")]
+    if let Some((scope, user)) = proxy_grant {
+        // A proxy token carries only its own grant, even if the Miro token has more
+        user_info.scopes = crate::auth::scopes::intersect(&user_info.scopes, Some(&scope));
+
+        // Miro's token response may have named the user the grant was issued for
+        let identity = &mut user_info.identity;
+        identity.user_name = identity.user_name.take().or(user.name);
+        identity.email = identity.email.take().or(user.email);
+    }
+
+    // Audit trail: team and organization always, names and emails only with log_pii
+    let identity = &user_info.identity;
     info!(
         request_id = %request_id,
         user_id = %user_info.user_id,
+        user_name = %redact_pii(identity.user_name.as_deref(), state.config.log_pii),
+        email = %redact_pii(identity.email.as_deref(), state.config.log_pii),
         team_id = %user_info.team_id,
+        team_name = %identity.team_name.as_deref().unwrap_or("-"),
+        organization_id = %identity.organization_id.as_deref().unwrap_or("-"),
+        organization_name = %identity.organization_name.as_deref().unwrap_or("-"),
         scopes = ?user_info.scopes,
         "Request authenticated successfully"
     );
@@ -213,13 +729,32 @@ async fn bearer_auth_middleware_adr002(
     Ok(next.run(request).await)
 }
 
+/// Personal data for a log field: the value with `log_pii`, otherwise a placeholder
+fn redact_pii(value: Option<&str>, log_pii: bool) -> &str {
+    match value {
+        Some(_) if !log_pii => "[redacted]",
+        Some(value) => value,
+        None => "-",
+    }
+}
+
+/// Handle GET /oauth/userinfo - Claims about the bearer token's user
+///
+/// OpenID Connect-style userinfo (OIDC Core §5.3); which claims appear depends
+/// on the token's scopes.
+async fn userinfo_endpoint(
+    axum::Extension(user_info): axum::Extension<Arc<UserInfo>>,
+) -> Json<UserClaims> {
+    Json(user_info.claims())
+}
+
 /// Create HTTP server for ADR-002 Resource Server with ADR-004 Proxy OAuth
 /// Includes:
 /// - Correlation ID middleware (OBS1)
 /// - OAuth metadata endpoint (AUTH14 - updated for proxy pattern)
-/// - OAuth proxy endpoints (AUTH11 - authorize, callback, token)
+/// - OAuth proxy endpoints (AUTH11 - authorize, consent, callback, token, revoke, introspect)
 /// - Bearer token authentication (AUTH7+AUTH8+AUTH9)
-/// - MCP tools (list_boards, get_board)
+/// - MCP tools (list_boards, get_board, whoami) and OpenID Connect-style userinfo
 pub fn create_app_adr002(
     token_validator: Arc<TokenValidator>,
     config: Arc<Config>,
@@ -227,13 +762,23 @@ pub fn create_app_adr002(
     #[cfg(feature = "oauth-proxy")] cookie_manager: Arc<CookieManager>,
     #[cfg(feature = "oauth-proxy")] code_storage: CodeStorage,
 ) -> Router {
+    let sessions = SessionStore::new(SubscriptionConfig::from(config.as_ref()));
+    // Apps built outside a runtime (e.g. in unit tests) don't sweep sessions
+    if tokio::runtime::Handle::try_current().is_ok() {
+        sessions.start_sweep_task();
+    }
+
     #[cfg(feature = "oauth-proxy")]
     let state = AppStateADR002 {
+        proxy_tokens: config
+            .issue_proxy_tokens
+            .then(|| ProxyTokenStore::new(code_storage.backend(), cookie_manager.clone())),
         token_validator,
         config,
+        sessions,
         oauth_provider,
         cookie_manager,
-        client_registry: ClientRegistry::new(),
+        client_registry: ClientRegistry::with_storage(code_storage.backend()),
         code_storage,
     };
 
@@ -241,6 +786,7 @@ pub fn create_app_adr002(
     let state = AppStateADR002 {
         token_validator,
         config,
+        sessions,
     };
 
     // Public routes (no authentication required)
@@ -248,19 +794,28 @@ pub fn create_app_adr002(
     let oauth_routes = Router::new()
         // Standard paths with /oauth prefix
         .route("/oauth/authorize", get(authorize_handler))
+        .route("/oauth/consent", post(consent_handler))
         .route("/oauth/callback", get(callback_handler))
         .route("/oauth/token", post(token_handler))
+        .route("/oauth/revoke", post(revoke_handler))
+        .route("/oauth/introspect", post(introspect_handler))
         // Alias paths without /oauth prefix (for Claude.ai compatibility)
         .route("/authorize", get(authorize_handler))
         .route("/callback", get(callback_handler))
         .route("/token", post(token_handler))
         .with_state(state.clone());
 
-    // DCR endpoint needs separate router with ClientRegistry state
+    // Dynamic Client Registration (RFC 7591) and management (RFC 7592)
     #[cfg(feature = "oauth-proxy")]
     let dcr_routes = Router::new()
         .route("/register", post(register_handler))
-        .with_state(state.client_registry.clone());
+        .route(
+            "/register/:client_id",
+            get(read_client_handler)
+                .put(update_client_handler)
+                .delete(delete_client_handler),
+        )
+        .with_state(state.clone());
 
     let public_routes = Router::new()
         .route("/health", get(health_check))
@@ -275,7 +830,12 @@ pub fn create_app_adr002(
 
     // Protected routes (Bearer token required)
     let protected_routes = Router::new()
-        .route("/mcp", axum::routing::post(mcp_endpoint))
+        .route(
+            "/mcp",
+            post(mcp_endpoint)
+                .get(mcp_event_stream)
+                .delete(mcp_delete_session),
+        )
         .route(
             "/mcp/list_boards",
             axum::routing::post(crate::mcp::tools::list_boards),
@@ -284,22 +844,27 @@ pub fn create_app_adr002(
             "/mcp/get_board/:board_id",
             axum::routing::post(crate::mcp::tools::get_board),
         )
+        .route("/oauth/userinfo", get(userinfo_endpoint))
         .layer(middleware::from_fn_with_state(
             state.clone(),
             bearer_auth_middleware_adr002,
-        ));
+        ))
+        .with_state(state.clone());
 
     // CORS layer for Claude.ai compatibility
     // Allow Claude.ai domain to access OAuth metadata and endpoints
     let cors = CorsLayer::new()
         .allow_origin("https://claude.ai".parse::<HeaderValue>().unwrap())
-        .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+        .allow_methods([Method::GET, Method::POST, Method::DELETE, Method::OPTIONS])
         .allow_headers([
             axum::http::header::AUTHORIZATION,
             axum::http::header::CONTENT_TYPE,
             axum::http::header::ACCEPT,
             axum::http::header::COOKIE,
+            HeaderName::from_static(SESSION_HEADER),
+            HeaderName::from_static(PROTOCOL_VERSION_HEADER),
         ])
+        .expose_headers([HeaderName::from_static(SESSION_HEADER)])
         .allow_credentials(true);
 
     // Merge routes and apply middlewares to ALL requests
@@ -321,7 +886,13 @@ pub async fn run_server_adr002(
     #[cfg(feature = "oauth-proxy")] code_storage: CodeStorage,
 ) -> Result<(), Box<dyn std::error::Error>> {
     #[cfg(feature = "oauth-proxy")]
-    let app = create_app_adr002(token_validator, config, oauth_provider, cookie_manager, code_storage);
+    let app = create_app_adr002(
+        token_validator,
+        config,
+        oauth_provider,
+        cookie_manager,
+        code_storage,
+    );
 
     #[cfg(not(feature = "oauth-proxy"))]
     let app = create_app_adr002(token_validator, config);
@@ -366,6 +937,15 @@ mod tests {
             encryption_key: [0u8; 32],
             port: 3010,
             base_url: Some("http://localhost:3010".to_string()),
+            resource_poll_interval_secs: 30,
+            max_resource_subscriptions: 10,
+            confirm_import_threshold: 100,
+            oauth_storage: Default::default(),
+            issue_proxy_tokens: false,
+            registration_policy: Default::default(),
+            consent_policy: Default::default(),
+            log_pii: false,
+            snapshot_dir: "snapshots".into(),
         });
         let oauth_provider = Arc::new(MiroOAuthProvider::new(
             config.client_id.clone(),
@@ -374,7 +954,13 @@ mod tests {
         ));
         let cookie_manager = Arc::new(CookieManager::new(&config.encryption_key));
         let code_storage = CodeStorage::new();
-        let app = create_app_adr002(token_validator, config, oauth_provider, cookie_manager, code_storage);
+        let app = create_app_adr002(
+            token_validator,
+            config,
+            oauth_provider,
+            cookie_manager,
+            code_storage,
+        );
         assert!(std::mem::size_of_val(&app) > 0);
     }
 
@@ -58,7 +58,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 http_config.redirect_uri.clone(),
             ));
             let cookie_manager = Arc::new(CookieManager::new(&http_config.encryption_key));
-            let code_storage = CodeStorage::new();
+            let code_storage = match CodeStorage::from_config(&http_config) {
+                Ok(code_storage) => code_storage,
+                Err(e) => {
+                    eprintln!("OAuth storage error: {}", e);
+                    return;
+                }
+            };
 
             // Start background cleanup task for expired authorization codes
             start_cleanup_task(code_storage.clone());
@@ -64,6 +64,15 @@ mod tests {
             encryption_key: [0u8; 32],
             port: 3000,
             base_url: None,
+            resource_poll_interval_secs: 30,
+            max_resource_subscriptions: 10,
+            confirm_import_threshold: 100,
+            oauth_storage: Default::default(),
+            issue_proxy_tokens: false,
+            registration_policy: Default::default(),
+            consent_policy: Default::default(),
+            log_pii: false,
+            snapshot_dir: "snapshots".into(),
         }
     }
 
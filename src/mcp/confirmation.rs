@@ -0,0 +1,129 @@
+//! Confirmation for destructive and large-scale tools
+//!
+//! Deleting items and large imports change shared team boards in ways that are
+//! hard to undo. Before acting, such tools describe the change as a
+//! [`PendingChange`] and ask the user to confirm it through MCP elicitation.
+//! Clients that don't support elicitation must instead pass `"confirm": true`,
+//! which the tool only accepts after telling the agent what it would do.
+
+use serde_json::{json, Map, Value};
+
+/// Tool argument that confirms a change when elicitation isn't available
+pub const CONFIRM_ARGUMENT: &str = "confirm";
+
+/// A change a tool is about to make, described for the person approving it
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingChange {
+    pub tool: String,
+    pub summary: String,
+}
+
+impl PendingChange {
+    /// Deleting one item; `description` names the item when it could be looked up
+    pub fn delete_item(board_id: &str, item_id: &str, description: Option<&str>) -> Self {
+        let item = match description {
+            Some(description) => format!("{} ({})", description, item_id),
+            None => format!("item {}", item_id),
+        };
+        Self {
+            tool: "delete_item".to_string(),
+            summary: format!(
+                "Delete {} from board {}. This cannot be undone.",
+                item, board_id
+            ),
+        }
+    }
+
+    /// Creating `count` objects on a board in one import
+    pub fn large_import(tool: &str, target: &str, count: usize) -> Self {
+        Self {
+            tool: tool.to_string(),
+            summary: format!("Create {} objects on {}.", count, target),
+        }
+    }
+
+    /// Message shown in the elicitation prompt
+    pub fn elicitation_message(&self) -> String {
+        format!(
+            "{} wants to make this change:\n\n{}\n\nDo you want to proceed?",
+            self.tool, self.summary
+        )
+    }
+
+    /// Tool error for clients that can't elicit and didn't pass `confirm: true`
+    pub fn confirmation_required_message(&self) -> String {
+        format!(
+            "Confirmation required: {} Ask the user to approve this change, then call {} \
+             again with \"{}\": true.",
+            self.summary, self.tool, CONFIRM_ARGUMENT
+        )
+    }
+}
+
+/// Whether an import of `count` objects needs confirmation
+pub fn requires_import_confirmation(count: usize, threshold: usize) -> bool {
+    count > threshold
+}
+
+/// Schema of the elicitation form: a single required checkbox
+pub fn confirmation_schema() -> Map<String, Value> {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            CONFIRM_ARGUMENT: {
+                "type": "boolean",
+                "title": "Confirm",
+                "description": "Check to apply the change",
+                "default": false
+            }
+        },
+        "required": [CONFIRM_ARGUMENT]
+    });
+    match schema {
+        Value::Object(schema) => schema,
+        _ => Map::new(),
+    }
+}
+
+/// Whether an accepted elicitation actually ticked the confirm box
+pub fn is_confirmed(content: Option<&Value>) -> bool {
+    content
+        .and_then(|content| content.get(CONFIRM_ARGUMENT))
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_change_messages() {
+        let change = PendingChange::delete_item("uXjVO", "3458", Some("sticky note \"Ship it\""));
+        assert_eq!(
+            change.summary,
+            "Delete sticky note \"Ship it\" (3458) from board uXjVO. This cannot be undone."
+        );
+        assert!(change.elicitation_message().contains(&change.summary));
+
+        let change = PendingChange::large_import("import_csv", "board uXjVO", 250);
+        let message = change.confirmation_required_message();
+        assert!(message.contains("Create 250 objects on board uXjVO."));
+        assert!(message.contains("call import_csv again with \"confirm\": true"));
+    }
+
+    #[test]
+    fn test_confirmation_checks() {
+        assert!(!requires_import_confirmation(100, 100));
+        assert!(requires_import_confirmation(101, 100));
+
+        assert!(is_confirmed(Some(&json!({"confirm": true}))));
+        assert!(!is_confirmed(Some(&json!({"confirm": false}))));
+        assert!(!is_confirmed(Some(&json!({}))));
+        assert!(!is_confirmed(None));
+
+        let schema = confirmation_schema();
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["properties"]["confirm"]["type"], "boolean");
+    }
+}
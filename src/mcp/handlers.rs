@@ -2,12 +2,33 @@
 //!
 //! Implements handlers for MCP methods:
 //! - initialize: Handshake and capability negotiation
+//! - ping: Liveness check
 //! - tools/list: List available tools
 //! - tools/call: Execute a tool
+//! - resources/list, resources/templates/list, resources/read: Board resources
+//! - resources/subscribe, resources/unsubscribe: Board change notifications (session required)
+//! - prompts/list, prompts/get: Workflow prompt templates
+//!
+//! Tools and board data are gated on the token's scopes: read-only tools,
+//! resources and prompts need `boards:read`, every other tool `boards:write`.
+//! `whoami` is always available; its answer is filtered by scope instead.
 
 use super::protocol::*;
-use crate::auth::token_validator::UserInfo;
-use crate::mcp::tools::{BoardInfo, GetBoardResponse, ListBoardsResponse};
+use crate::auth::scopes;
+use crate::auth::token_validator::{UserClaims, UserInfo};
+use crate::mcp::prompts;
+use crate::mcp::resources::{self, ResourceUri};
+use crate::mcp::session::McpSession;
+use crate::mcp::subscriptions::{FetchItems, NotifyUpdated, SubscriptionError};
+use crate::mcp::tools::{
+    fetch_all_boards_from_miro, fetch_board_from_miro, fetch_boards_from_miro,
+    fetch_connectors_from_miro, fetch_items_from_miro, BoardInfo, GetBoardResponse,
+    ListBoardsResponse,
+};
+use crate::miro::progress::{ProgressUpdate, ToolProgress};
+use crate::miro::types::Board;
+use schemars::JsonSchema;
+use serde::Serialize;
 use serde_json::{json, Value};
 use std::sync::Arc;
 use tracing::{error, info, warn};
@@ -18,16 +39,29 @@ use tracing::{error, info, warn};
 pub fn handle_initialize(req: &JsonRpcRequest, _user_info: &Arc<UserInfo>) -> JsonRpcResponse {
     info!("Handling initialize request");
 
+    let protocol_version = match negotiate_protocol_version(req) {
+        Ok(version) => version,
+        Err(e) => {
+            warn!(error = %e.message, "Rejected initialize");
+            return JsonRpcResponse::error(e, req.id.clone());
+        }
+    };
+
     let server_capabilities = ServerCapabilities {
         tools: Some(ToolsCapability {
             list_changed: Some(false),
         }),
-        resources: None,
-        prompts: None,
+        resources: Some(ResourcesCapability {
+            subscribe: Some(true),
+            list_changed: Some(false),
+        }),
+        prompts: Some(PromptsCapability {
+            list_changed: Some(false),
+        }),
     };
 
     let result = InitializeResult {
-        protocol_version: Some("2024-11-05".to_string()),
+        protocol_version: Some(protocol_version.to_string()),
         capabilities: server_capabilities,
         server_info: ServerInfo {
             name: "Miro MCP Server".to_string(),
@@ -41,24 +75,120 @@ pub fn handle_initialize(req: &JsonRpcRequest, _user_info: &Arc<UserInfo>) -> Js
     )
 }
 
+/// Negotiate the protocol version requested in initialize params
+pub fn negotiate_protocol_version(req: &JsonRpcRequest) -> Result<ProtocolVersion, JsonRpcError> {
+    let params = match req.params.clone() {
+        Some(params) => serde_json::from_value::<InitializeRequest>(params).map_err(|e| {
+            JsonRpcError::invalid_params(format!("Invalid initialize params: {}", e))
+        })?,
+        None => return Ok(ProtocolVersion::DEFAULT),
+    };
+
+    let version = ProtocolVersion::negotiate(params.protocol_version.as_deref())?;
+    info!(
+        requested = ?params.protocol_version,
+        negotiated = %version,
+        client = ?params.client_info.as_ref().map(|client| client.name.as_str()),
+        "Negotiated MCP protocol version"
+    );
+    Ok(version)
+}
+
+/// Scope a method needs beyond the per-tool checks, if any
+pub fn method_scope(method: &str) -> Option<&'static str> {
+    match method {
+        "resources/list" | "resources/read" | "resources/subscribe" | "prompts/get" => {
+            Some(scopes::BOARDS_READ)
+        }
+        _ => None,
+    }
+}
+
+/// Error response when the token lacks `scope`, or `None` if it has it
+pub fn insufficient_scope(
+    req: &JsonRpcRequest,
+    user_info: &UserInfo,
+    scope: &str,
+) -> Option<JsonRpcResponse> {
+    if user_info.has_scope(scope) {
+        return None;
+    }
+    warn!(
+        method = %req.method,
+        user_id = %user_info.user_id,
+        required = %scope,
+        granted = ?user_info.scopes,
+        "Insufficient scope"
+    );
+    Some(JsonRpcResponse::error(
+        JsonRpcError::server_error(-32004, format!("Insufficient scope: {} required", scope)),
+        req.id.clone(),
+    ))
+}
+
+/// Scope needed to call a tool, if any
+fn tool_scope(tool: &Tool) -> Option<&'static str> {
+    if tool.name == "whoami" {
+        return None;
+    }
+    let read_only = tool
+        .annotations
+        .as_ref()
+        .and_then(|annotations| annotations.read_only_hint)
+        .unwrap_or(false);
+    Some(scopes::required_for_tool(read_only))
+}
+
 /// Handle the tools/list method
 ///
-/// Returns list of available tools (list_boards, get_board)
-pub fn handle_tools_list(req: &JsonRpcRequest, _user_info: &Arc<UserInfo>) -> JsonRpcResponse {
+/// Returns the tools (list_boards, get_board, whoami) the token's scopes allow calling.
+/// Output schemas are only advertised to clients that negotiated structured
+/// tool output.
+pub fn handle_tools_list(
+    req: &JsonRpcRequest,
+    user_info: &Arc<UserInfo>,
+    version: ProtocolVersion,
+) -> JsonRpcResponse {
     info!("Handling tools/list request");
+    let structured = version.supports_structured_output();
+
+    let mut tools = tool_definitions(structured);
+    tools.retain(|tool| tool_scope(tool).is_none_or(|scope| user_info.has_scope(scope)));
+    for tool in &mut tools {
+        if !version.supports_titles() {
+            tool.title = None;
+        }
+        if !version.supports_tool_annotations() {
+            tool.annotations = None;
+        }
+    }
+
+    let result = ToolsListResult { tools };
+
+    JsonRpcResponse::success(
+        serde_json::to_value(result).unwrap_or_else(|_| json!({})),
+        req.id.clone(),
+    )
+}
 
-    let tools = vec![
+/// Every tool served over HTTP, with annotations for all protocol versions
+fn tool_definitions(structured: bool) -> Vec<Tool> {
+    vec![
         Tool {
             name: "list_boards".to_string(),
+            title: Some("List boards".to_string()),
             description: "List all Miro boards accessible to the authenticated user".to_string(),
             input_schema: Some(json!({
                 "type": "object",
                 "properties": {},
                 "required": []
             })),
+            output_schema: structured.then(output_schema::<ListBoardsResponse>),
+            annotations: Some(ToolAnnotations::read_only("List boards")),
         },
         Tool {
             name: "get_board".to_string(),
+            title: Some("Get board".to_string()),
             description: "Get details of a specific Miro board by ID".to_string(),
             input_schema: Some(json!({
                 "type": "object",
@@ -70,26 +200,36 @@ pub fn handle_tools_list(req: &JsonRpcRequest, _user_info: &Arc<UserInfo>) -> Js
                 },
                 "required": ["board_id"]
             })),
+            output_schema: structured.then(output_schema::<GetBoardResponse>),
+            annotations: Some(ToolAnnotations::read_only("Get board")),
         },
-    ];
-
-    let result = ToolsListResult { tools };
-
-    JsonRpcResponse::success(
-        serde_json::to_value(result).unwrap_or_else(|_| json!({})),
-        req.id.clone(),
-    )
+        Tool {
+            name: "whoami".to_string(),
+            title: Some("Who am I".to_string()),
+            description: "Show the Miro user, team and organization this connection acts for"
+                .to_string(),
+            input_schema: Some(json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            })),
+            output_schema: structured.then(output_schema::<UserClaims>),
+            annotations: Some(ToolAnnotations::read_only("Who am I")),
+        },
+    ]
 }
 
 /// Handle the tools/call method
 ///
-/// Executes a tool (list_boards or get_board)
+/// Executes a tool (list_boards, get_board or whoami) if the token's scopes allow it
 ///
 /// # Arguments
 ///
 /// * `req` - JSON-RPC request containing tool name and arguments
 /// * `user_info` - User info with Bearer token for API calls
 /// * `token` - Bearer token for Miro API authentication
+/// * `version` - Negotiated protocol version
+/// * `progress` - Progress reporting and cancellation for this call
 ///
 /// # Returns
 ///
@@ -98,6 +238,8 @@ pub async fn handle_tools_call(
     req: &JsonRpcRequest,
     user_info: &Arc<UserInfo>,
     token: &Arc<String>,
+    version: ProtocolVersion,
+    progress: &ToolProgress,
 ) -> JsonRpcResponse {
     // Parse tool call parameters
     let params = match req.params.as_ref() {
@@ -129,9 +271,22 @@ pub async fn handle_tools_call(
         "Executing tool"
     );
 
+    let tool = tool_definitions(false)
+        .into_iter()
+        .find(|tool| &tool.name == tool_name);
+    if let Some(response) = tool
+        .and_then(|tool| tool_scope(&tool))
+        .and_then(|scope| insufficient_scope(req, user_info, scope))
+    {
+        return response;
+    }
+
     match tool_name.as_str() {
-        "list_boards" => handle_list_boards_call(req, user_info, token).await,
-        "get_board" => handle_get_board_call(req, user_info, token, &tool_call_params).await,
+        "list_boards" => handle_list_boards_call(req, user_info, token, version, progress).await,
+        "get_board" => {
+            handle_get_board_call(req, user_info, token, &tool_call_params, version).await
+        }
+        "whoami" => handle_whoami_call(req, user_info, version),
         _ => {
             warn!(tool_name = %tool_name, "Unknown tool requested");
             JsonRpcResponse::error(
@@ -142,111 +297,152 @@ pub async fn handle_tools_call(
     }
 }
 
+/// Progress token a tools/call request asked for in `params._meta.progressToken`
+pub fn progress_token(req: &JsonRpcRequest) -> Option<Value> {
+    req.params
+        .as_ref()?
+        .get("_meta")?
+        .get("progressToken")
+        .filter(|token| token.is_string() || token.is_number())
+        .cloned()
+}
+
+/// `notifications/progress` message for a progress token
+pub fn progress_notification(
+    progress_token: &Value,
+    update: ProgressUpdate,
+) -> JsonRpcNotification {
+    let mut params = json!({
+        "progressToken": progress_token,
+        "progress": update.progress,
+    });
+    if let Some(total) = update.total {
+        params["total"] = json!(total);
+    }
+    if let Some(message) = update.message {
+        params["message"] = json!(message);
+    }
+    JsonRpcNotification::new("notifications/progress", Some(params))
+}
+
+/// Build a successful tool result from a typed value
+///
+/// Clients on 2025-06-18+ get a short summary plus the value as
+/// `structuredContent`; older clients get the value serialized as text, which
+/// was the only way to return it before output schemas.
+fn structured_tool_result<T: Serialize>(
+    summary: String,
+    value: &T,
+    links: Vec<ToolContent>,
+    version: ProtocolVersion,
+) -> ToolCallResult {
+    let structured = serde_json::to_value(value).unwrap_or_else(|_| json!({}));
+    let (text, structured_content) = if version.supports_structured_output() {
+        (summary, Some(structured))
+    } else {
+        (structured.to_string(), None)
+    };
+
+    let mut content = vec![ToolContent::from(TextContent {
+        content_type: "text".to_string(),
+        text,
+    })];
+    content.extend(links);
+    ToolCallResult::Success {
+        content,
+        structured_content,
+        is_error: Some(false),
+    }
+}
+
+/// JSON Schema of a tool's typed result, advertised as `outputSchema`
+fn output_schema<T: JsonSchema>() -> Value {
+    serde_json::to_value(schemars::schema_for!(T)).unwrap_or_else(|_| json!({"type": "object"}))
+}
+
+/// Resource links to each board's resource, for clients that understand them
+fn board_links(boards: &[Board], version: ProtocolVersion) -> Vec<ToolContent> {
+    if !version.supports_resource_links() {
+        return Vec::new();
+    }
+    resources::board_resources(boards)
+        .into_iter()
+        .skip(1) // the board index
+        .map(|resource| ToolContent::ResourceLink(resource.into()))
+        .collect()
+}
+
+/// Handle whoami tool call
+///
+/// Answers from the validated token; name, email, team and organization
+/// details only appear with the scopes that cover them.
+fn handle_whoami_call(
+    req: &JsonRpcRequest,
+    user_info: &Arc<UserInfo>,
+    version: ProtocolVersion,
+) -> JsonRpcResponse {
+    let claims = user_info.claims();
+    let user = match &claims.name {
+        Some(name) => format!("{} ({})", name, claims.sub),
+        None => format!("User {}", claims.sub),
+    };
+    let team = claims.team_name.as_deref().unwrap_or(&claims.team_id);
+    let summary = format!("{} in team {}", user, team);
+    let result = structured_tool_result(summary, &claims, vec![], version);
+
+    JsonRpcResponse::success(
+        serde_json::to_value(result).unwrap_or_else(|_| json!({})),
+        req.id.clone(),
+    )
+}
+
 /// Handle list_boards tool call
+///
+/// Walks every page of boards, reporting progress per page. A cancelled call
+/// returns the boards fetched so far, flagged as `cancelled`.
 async fn handle_list_boards_call(
     req: &JsonRpcRequest,
     user_info: &Arc<UserInfo>,
     token: &Arc<String>,
+    version: ProtocolVersion,
+    progress: &ToolProgress,
 ) -> JsonRpcResponse {
-    use reqwest::Client;
+    let http_client = reqwest::Client::new();
 
-    let http_client = Client::new();
-    const MIRO_API_URL: &str = "https://api.miro.com/v2/boards";
-
-    match http_client
-        .get(MIRO_API_URL)
-        .bearer_auth(token.as_str())
-        .send()
-        .await
-    {
-        Ok(response) => match response.status() {
-            reqwest::StatusCode::OK => match response.json::<serde_json::Value>().await {
-                Ok(boards_response) => {
-                    match boards_response.get("data").and_then(|v| v.as_array()) {
-                        Some(boards) => {
-                            let board_infos: Vec<BoardInfo> = boards
-                                .iter()
-                                .filter_map(|board_json| {
-                                    serde_json::from_value::<crate::miro::types::Board>(
-                                        board_json.clone(),
-                                    )
-                                    .ok()
-                                    .map(BoardInfo::from)
-                                })
-                                .collect();
-
-                            let count = board_infos.len();
-                            let list_boards_result = ListBoardsResponse {
-                                boards: board_infos,
-                                count,
-                            };
-
-                            info!(
-                                user_id = %user_info.user_id,
-                                count = count,
-                                "Successfully listed boards via MCP"
-                            );
-
-                            let result = ToolCallResult::Success {
-                                content: vec![TextContent {
-                                    content_type: "text".to_string(),
-                                    text: serde_json::to_string(&list_boards_result)
-                                        .unwrap_or_else(|_| "{}".to_string()),
-                                }],
-                                is_error: Some(false),
-                            };
-
-                            JsonRpcResponse::success(
-                                serde_json::to_value(result).unwrap_or_else(|_| json!({})),
-                                req.id.clone(),
-                            )
-                        }
-                        None => {
-                            error!("Miro API response missing data array");
-                            JsonRpcResponse::error(
-                                JsonRpcError::internal_error("Invalid Miro API response format"),
-                                req.id.clone(),
-                            )
-                        }
-                    }
-                }
-                Err(e) => {
-                    error!(error = %e, "Failed to parse Miro API response");
-                    JsonRpcResponse::error(
-                        JsonRpcError::internal_error(format!(
-                            "Failed to parse API response: {}",
-                            e
-                        )),
-                        req.id.clone(),
-                    )
-                }
-            },
-            reqwest::StatusCode::UNAUTHORIZED => {
-                warn!("Bearer token invalid or expired");
-                JsonRpcResponse::error(
-                    JsonRpcError::server_error(-32001, "Bearer token invalid or expired (401)"),
-                    req.id.clone(),
-                )
-            }
-            status => {
-                error!(status = ?status, "Miro API returned error");
-                JsonRpcResponse::error(
-                    JsonRpcError::server_error(
-                        -32001,
-                        format!("Miro API error: {}", status.as_u16()),
-                    ),
-                    req.id.clone(),
-                )
-            }
-        },
+    let pages = match fetch_all_boards_from_miro(&http_client, token.as_str(), progress).await {
+        Ok(pages) => pages,
         Err(e) => {
-            error!(error = %e, "HTTP request to Miro API failed");
-            JsonRpcResponse::error(
-                JsonRpcError::internal_error(format!("HTTP request failed: {}", e)),
-                req.id.clone(),
-            )
+            error!(error = %e, "Failed to list boards from Miro API");
+            return JsonRpcResponse::error(JsonRpcError::server_error(-32001, e), req.id.clone());
         }
+    };
+
+    let links = board_links(&pages.entries, version);
+    let board_infos: Vec<BoardInfo> = pages.entries.into_iter().map(BoardInfo::from).collect();
+    let count = board_infos.len();
+    let list_boards_result = ListBoardsResponse {
+        boards: board_infos,
+        count,
+        cancelled: !pages.complete,
+    };
+
+    info!(
+        user_id = %user_info.user_id,
+        count = count,
+        cancelled = !pages.complete,
+        "Successfully listed boards via MCP"
+    );
+
+    let mut summary = format!("Found {} board(s)", count);
+    if list_boards_result.cancelled {
+        summary.push_str(" before the listing was cancelled");
     }
+    let result = structured_tool_result(summary, &list_boards_result, links, version);
+
+    JsonRpcResponse::success(
+        serde_json::to_value(result).unwrap_or_else(|_| json!({})),
+        req.id.clone(),
+    )
 }
 
 /// Handle get_board tool call
@@ -255,6 +451,7 @@ async fn handle_get_board_call(
     user_info: &Arc<UserInfo>,
     token: &Arc<String>,
     tool_params: &ToolCallParams,
+    version: ProtocolVersion,
 ) -> JsonRpcResponse {
     use reqwest::Client;
 
@@ -291,7 +488,7 @@ async fn handle_get_board_call(
         .await
     {
         Ok(response) => match response.status() {
-            reqwest::StatusCode::OK => match response.json::<crate::miro::types::Board>().await {
+            reqwest::StatusCode::OK => match response.json::<Board>().await {
                 Ok(board) => {
                     info!(
                         user_id = %user_info.user_id,
@@ -300,18 +497,12 @@ async fn handle_get_board_call(
                         "Successfully retrieved board via MCP"
                     );
 
+                    let links = board_links(std::slice::from_ref(&board), version);
+                    let summary = format!("Board '{}' (ID: {})", board.name, board.id);
                     let get_board_result = GetBoardResponse {
                         board: BoardInfo::from(board),
                     };
-
-                    let result = ToolCallResult::Success {
-                        content: vec![TextContent {
-                            content_type: "text".to_string(),
-                            text: serde_json::to_string(&get_board_result)
-                                .unwrap_or_else(|_| "{}".to_string()),
-                        }],
-                        is_error: Some(false),
-                    };
+                    let result = structured_tool_result(summary, &get_board_result, links, version);
 
                     JsonRpcResponse::success(
                         serde_json::to_value(result).unwrap_or_else(|_| json!({})),
@@ -361,6 +552,331 @@ async fn handle_get_board_call(
     }
 }
 
+/// Handle the ping method
+///
+/// Replies with an empty result so clients can check the connection is alive.
+pub fn handle_ping(req: &JsonRpcRequest) -> JsonRpcResponse {
+    JsonRpcResponse::success(json!({}), req.id.clone())
+}
+
+/// Handle the resources/list method
+///
+/// Returns the board index resource plus one resource per accessible board
+pub async fn handle_resources_list(
+    req: &JsonRpcRequest,
+    user_info: &Arc<UserInfo>,
+    token: &Arc<String>,
+) -> JsonRpcResponse {
+    let http_client = reqwest::Client::new();
+
+    match fetch_boards_from_miro(&http_client, token.as_str()).await {
+        Ok(boards) => {
+            info!(
+                user_id = %user_info.user_id,
+                count = boards.len(),
+                "Listed board resources via MCP"
+            );
+            let result = ResourcesListResult {
+                resources: resources::board_resources(&boards),
+            };
+            JsonRpcResponse::success(
+                serde_json::to_value(result).unwrap_or_else(|_| json!({})),
+                req.id.clone(),
+            )
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to list board resources");
+            JsonRpcResponse::error(JsonRpcError::internal_error(e), req.id.clone())
+        }
+    }
+}
+
+/// Handle the resources/templates/list method
+pub fn handle_resource_templates_list(req: &JsonRpcRequest) -> JsonRpcResponse {
+    let result = ResourceTemplatesListResult {
+        resource_templates: resources::resource_templates(),
+    };
+
+    JsonRpcResponse::success(
+        serde_json::to_value(result).unwrap_or_else(|_| json!({})),
+        req.id.clone(),
+    )
+}
+
+/// Handle the resources/read method
+///
+/// Fetches the board data behind a `miro://` URI and renders it as markdown or JSON
+pub async fn handle_resources_read(
+    req: &JsonRpcRequest,
+    user_info: &Arc<UserInfo>,
+    token: &Arc<String>,
+) -> JsonRpcResponse {
+    let params = match req
+        .params
+        .clone()
+        .map(serde_json::from_value::<ResourceReadParams>)
+    {
+        Some(Ok(params)) => params,
+        Some(Err(e)) => {
+            return JsonRpcResponse::error(
+                JsonRpcError::invalid_params(format!("Invalid resources/read params: {}", e)),
+                req.id.clone(),
+            )
+        }
+        None => {
+            return JsonRpcResponse::error(
+                JsonRpcError::invalid_params("params field is required for resources/read"),
+                req.id.clone(),
+            )
+        }
+    };
+
+    if let Err(e) = ResourceUri::parse(&params.uri) {
+        warn!(uri = %params.uri, "Unknown resource requested");
+        return JsonRpcResponse::error(JsonRpcError::server_error(-32002, e), req.id.clone());
+    }
+
+    info!(
+        uri = %params.uri,
+        user_id = %user_info.user_id,
+        "Reading resource"
+    );
+
+    let http_client = reqwest::Client::new();
+    let contents = read_resource_contents(&http_client, token, &params.uri).await;
+
+    match contents {
+        Ok(contents) => JsonRpcResponse::success(
+            serde_json::to_value(ResourceReadResult {
+                contents: vec![contents],
+            })
+            .unwrap_or_else(|_| json!({})),
+            req.id.clone(),
+        ),
+        Err(e) => {
+            error!(uri = %params.uri, error = %e, "Failed to read resource");
+            JsonRpcResponse::error(JsonRpcError::internal_error(e), req.id.clone())
+        }
+    }
+}
+
+/// Fetch and render a board resource by URI
+async fn read_resource_contents(
+    http_client: &reqwest::Client,
+    token: &str,
+    uri: &str,
+) -> Result<ResourceContents, String> {
+    match ResourceUri::parse(uri)? {
+        ResourceUri::Boards => fetch_boards_from_miro(http_client, token)
+            .await
+            .map(|boards| resources::render_boards(uri, &boards)),
+        ResourceUri::Board { board_id, json } => {
+            let board = fetch_board_from_miro(http_client, token, &board_id).await?;
+            let items = fetch_items_from_miro(http_client, token, &board_id, None).await?;
+            let connectors = fetch_connectors_from_miro(http_client, token, &board_id).await?;
+            Ok(resources::render_board(
+                uri, board, &items, connectors, json,
+            ))
+        }
+        ResourceUri::BoardItems {
+            board_id,
+            item_type,
+        } => fetch_items_from_miro(http_client, token, &board_id, item_type.as_deref())
+            .await
+            .map(|items| resources::render_items(uri, &items)),
+    }
+}
+
+fn subscribe_params(req: &JsonRpcRequest) -> Result<ResourceSubscribeParams, JsonRpcError> {
+    match req
+        .params
+        .clone()
+        .map(serde_json::from_value::<ResourceSubscribeParams>)
+    {
+        Some(Ok(params)) => Ok(params),
+        Some(Err(e)) => Err(JsonRpcError::invalid_params(format!(
+            "Invalid {} params: {}",
+            req.method, e
+        ))),
+        None => Err(JsonRpcError::invalid_params(format!(
+            "params field is required for {}",
+            req.method
+        ))),
+    }
+}
+
+/// Handle the resources/subscribe method
+///
+/// Starts a polling watcher owned by the session; change notifications are
+/// delivered on the session's `GET /mcp` stream. Each poll uses the token of
+/// the session's latest request, falling back to the one subscribed with.
+pub fn handle_resources_subscribe(
+    req: &JsonRpcRequest,
+    session: Option<&Arc<McpSession>>,
+    token: &Arc<String>,
+) -> JsonRpcResponse {
+    let params = match subscribe_params(req) {
+        Ok(params) => params,
+        Err(e) => return JsonRpcResponse::error(e, req.id.clone()),
+    };
+    let Some(session) = session else {
+        return JsonRpcResponse::error(
+            JsonRpcError::invalid_request(
+                "resources/subscribe requires an MCP session (Mcp-Session-Id header)",
+            ),
+            req.id.clone(),
+        );
+    };
+
+    let http_client = reqwest::Client::new();
+    let token = token.clone();
+    let token_session = Arc::downgrade(session);
+    let fetch: FetchItems = Arc::new(move |board_id, item_type| {
+        let http_client = http_client.clone();
+        let token = token_session
+            .upgrade()
+            .and_then(|session| session.token())
+            .unwrap_or_else(|| token.clone());
+        Box::pin(async move {
+            fetch_items_from_miro(&http_client, &token, &board_id, item_type.as_deref()).await
+        })
+    });
+    let weak_session = Arc::downgrade(session);
+    let notify: NotifyUpdated = Arc::new(move |uri| {
+        let session = weak_session.upgrade();
+        Box::pin(async move {
+            let Some(session) = session else {
+                return false;
+            };
+            session.notify(JsonRpcNotification::new(
+                "notifications/resources/updated",
+                Some(json!({ "uri": uri })),
+            ));
+            true
+        })
+    });
+
+    match session
+        .subscriptions()
+        .subscribe(&params.uri, fetch, notify)
+    {
+        Ok(()) => {
+            info!(
+                uri = %params.uri,
+                session_id = %session.id(),
+                user_id = %session.user_id(),
+                "Subscribed to resource"
+            );
+            JsonRpcResponse::success(json!({}), req.id.clone())
+        }
+        Err(e @ SubscriptionError::Unsupported(_)) => {
+            JsonRpcResponse::error(JsonRpcError::invalid_params(e.to_string()), req.id.clone())
+        }
+        Err(e @ SubscriptionError::LimitReached(_)) => {
+            warn!(session_id = %session.id(), error = %e, "Subscription rejected");
+            JsonRpcResponse::error(
+                JsonRpcError::server_error(-32003, e.to_string()),
+                req.id.clone(),
+            )
+        }
+    }
+}
+
+/// Handle the resources/unsubscribe method
+pub fn handle_resources_unsubscribe(
+    req: &JsonRpcRequest,
+    session: Option<&Arc<McpSession>>,
+) -> JsonRpcResponse {
+    let params = match subscribe_params(req) {
+        Ok(params) => params,
+        Err(e) => return JsonRpcResponse::error(e, req.id.clone()),
+    };
+
+    if let Some(session) = session {
+        session.subscriptions().unsubscribe(&params.uri);
+    }
+    JsonRpcResponse::success(json!({}), req.id.clone())
+}
+
+/// Handle the prompts/list method
+pub fn handle_prompts_list(req: &JsonRpcRequest, version: ProtocolVersion) -> JsonRpcResponse {
+    let mut prompts = prompts::prompt_templates();
+    if !version.supports_titles() {
+        for prompt in &mut prompts {
+            prompt.title = None;
+        }
+    }
+    let result = PromptsListResult { prompts };
+
+    JsonRpcResponse::success(
+        serde_json::to_value(result).unwrap_or_else(|_| json!({})),
+        req.id.clone(),
+    )
+}
+
+/// Handle the prompts/get method
+///
+/// Resolves the template's arguments, then embeds the board resources it needs.
+pub async fn handle_prompts_get(
+    req: &JsonRpcRequest,
+    user_info: &Arc<UserInfo>,
+    token: &Arc<String>,
+) -> JsonRpcResponse {
+    let params = match req
+        .params
+        .clone()
+        .map(serde_json::from_value::<PromptGetParams>)
+    {
+        Some(Ok(params)) => params,
+        Some(Err(e)) => {
+            return JsonRpcResponse::error(
+                JsonRpcError::invalid_params(format!("Invalid prompts/get params: {}", e)),
+                req.id.clone(),
+            )
+        }
+        None => {
+            return JsonRpcResponse::error(
+                JsonRpcError::invalid_params("params field is required for prompts/get"),
+                req.id.clone(),
+            )
+        }
+    };
+
+    let plan = match prompts::build_prompt(&params.name, &params.arguments) {
+        Ok(plan) => plan,
+        Err(e) => {
+            warn!(prompt = %params.name, error = %e, "Invalid prompt request");
+            return JsonRpcResponse::error(
+                JsonRpcError::invalid_params(e.to_string()),
+                req.id.clone(),
+            );
+        }
+    };
+
+    info!(
+        prompt = %params.name,
+        user_id = %user_info.user_id,
+        "Rendering prompt"
+    );
+
+    let http_client = reqwest::Client::new();
+    let mut contents = Vec::with_capacity(plan.resource_uris.len());
+    for uri in &plan.resource_uris {
+        match read_resource_contents(&http_client, token, uri).await {
+            Ok(resource) => contents.push(resource),
+            Err(e) => {
+                error!(prompt = %params.name, uri = %uri, error = %e, "Failed to embed prompt resource");
+                return JsonRpcResponse::error(JsonRpcError::internal_error(e), req.id.clone());
+            }
+        }
+    }
+
+    JsonRpcResponse::success(
+        serde_json::to_value(plan.render(contents)).unwrap_or_else(|_| json!({})),
+        req.id.clone(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -387,19 +903,195 @@ mod tests {
         let user_info = Arc::new(UserInfo::new(
             "test-user".to_string(),
             "test-team".to_string(),
-            vec![],
+            vec!["boards:read".to_string()],
         ));
 
-        let response = handle_tools_list(&req, &user_info);
+        let response = handle_tools_list(&req, &user_info, ProtocolVersion::DEFAULT);
 
         assert!(response.result.is_some());
         assert!(response.error.is_none());
 
         if let Some(Value::Object(result)) = response.result {
             assert!(result.contains_key("tools"));
+            assert!(result["tools"][0].get("inputSchema").is_some());
+            assert!(result["tools"][0].get("outputSchema").is_none());
         }
     }
 
+    #[test]
+    fn test_handle_tools_list_advertises_output_schemas() {
+        let req = JsonRpcRequest::new("tools/list").with_id(Value::Number(1.into()));
+        let user_info = Arc::new(UserInfo::new(
+            "test-user".to_string(),
+            "test-team".to_string(),
+            vec!["boards:read".to_string()],
+        ));
+
+        let response = handle_tools_list(&req, &user_info, ProtocolVersion::V2025_06_18);
+        let tools = &response.result.unwrap()["tools"];
+
+        let schema = &tools[0]["outputSchema"];
+        assert_eq!(schema["type"], "object");
+        assert!(schema["properties"]["boards"].is_object());
+        assert!(tools[1]["outputSchema"]["properties"]["board"].is_object());
+    }
+
+    #[test]
+    fn test_handle_tools_list_annotations_depend_on_version() {
+        let req = JsonRpcRequest::new("tools/list").with_id(Value::Number(1.into()));
+        let user_info = Arc::new(UserInfo::new(
+            "test-user".to_string(),
+            "test-team".to_string(),
+            vec!["boards:read".to_string()],
+        ));
+
+        let response = handle_tools_list(&req, &user_info, ProtocolVersion::V2025_06_18);
+        let tools = &response.result.unwrap()["tools"];
+        assert_eq!(tools[0]["title"], "List boards");
+        assert_eq!(tools[0]["annotations"]["readOnlyHint"], true);
+        assert_eq!(tools[1]["annotations"]["openWorldHint"], true);
+
+        let response = handle_tools_list(&req, &user_info, ProtocolVersion::V2025_03_26);
+        let tools = &response.result.unwrap()["tools"];
+        assert!(tools[0].get("title").is_none());
+        assert_eq!(tools[0]["annotations"]["readOnlyHint"], true);
+
+        let response = handle_tools_list(&req, &user_info, ProtocolVersion::V2024_11_05);
+        let tools = &response.result.unwrap()["tools"];
+        assert!(tools[0].get("annotations").is_none());
+    }
+
+    #[test]
+    fn test_tools_gated_on_scope() {
+        let req = JsonRpcRequest::new("tools/list").with_id(Value::Number(1.into()));
+        let user_info = Arc::new(UserInfo::new(
+            "test-user".to_string(),
+            "test-team".to_string(),
+            vec!["team:read".to_string()],
+        ));
+
+        let response = handle_tools_list(&req, &user_info, ProtocolVersion::DEFAULT);
+        let tools = &response.result.unwrap()["tools"];
+        assert_eq!(tools.as_array().unwrap().len(), 1);
+        assert_eq!(tools[0]["name"], "whoami");
+
+        let req = JsonRpcRequest::new("tools/call")
+            .with_id(Value::Number(2.into()))
+            .with_params(json!({"name": "list_boards", "arguments": {}}));
+        let token = Arc::new("test-token".to_string());
+        let response = tokio::runtime::Runtime::new().unwrap().block_on(async {
+            handle_tools_call(
+                &req,
+                &user_info,
+                &token,
+                ProtocolVersion::DEFAULT,
+                &ToolProgress::none(),
+            )
+            .await
+        });
+        let error = response.error.unwrap();
+        assert_eq!(error.code, -32004);
+        assert!(error.message.contains("boards:read"));
+    }
+
+    #[test]
+    fn test_whoami_filters_claims_by_scope() {
+        let req = JsonRpcRequest::new("tools/call")
+            .with_id(Value::Number(1.into()))
+            .with_params(json!({"name": "whoami", "arguments": {}}));
+        let identity = crate::auth::Identity {
+            user_name: Some("Ada".to_string()),
+            email: Some("ada@example.com".to_string()),
+            team_name: Some("Design".to_string()),
+            organization_id: Some("org789".to_string()),
+            organization_name: Some("Example Inc".to_string()),
+        };
+        let user_info = Arc::new(
+            UserInfo::new(
+                "test-user".to_string(),
+                "test-team".to_string(),
+                vec!["team:read".to_string()],
+            )
+            .with_identity(identity),
+        );
+        let token = Arc::new("test-token".to_string());
+
+        let response = tokio::runtime::Runtime::new().unwrap().block_on(async {
+            handle_tools_call(
+                &req,
+                &user_info,
+                &token,
+                ProtocolVersion::V2025_06_18,
+                &ToolProgress::none(),
+            )
+            .await
+        });
+        let result = response.result.unwrap();
+        assert_eq!(
+            result["content"][0]["text"],
+            "User test-user in team Design"
+        );
+        let claims = &result["structuredContent"];
+        assert_eq!(claims["sub"], "test-user");
+        assert_eq!(claims["organization_name"], "Example Inc");
+        assert!(claims.get("email").is_none());
+    }
+
+    #[test]
+    fn test_board_methods_need_read_scope() {
+        let req = JsonRpcRequest::new("resources/read").with_id(Value::Number(1.into()));
+        let read_only = UserInfo::new(
+            "test-user".to_string(),
+            "test-team".to_string(),
+            vec!["boards:read".to_string()],
+        );
+        let identity_only = UserInfo::new(
+            "test-user".to_string(),
+            "test-team".to_string(),
+            vec!["identity:read".to_string()],
+        );
+
+        assert_eq!(method_scope("resources/read"), Some("boards:read"));
+        assert_eq!(method_scope("ping"), None);
+        assert!(insufficient_scope(&req, &read_only, "boards:read").is_none());
+        assert!(insufficient_scope(&req, &identity_only, "boards:read").is_some());
+    }
+
+    #[test]
+    fn test_structured_tool_result_depends_on_version() {
+        let value = GetBoardResponse {
+            board: BoardInfo {
+                id: "uXjVO".to_string(),
+                name: "Roadmap".to_string(),
+                description: None,
+                created_at: "2025-01-01T00:00:00Z".to_string(),
+            },
+        };
+
+        let result = structured_tool_result(
+            "Board 'Roadmap'".to_string(),
+            &value,
+            vec![],
+            ProtocolVersion::V2025_06_18,
+        );
+        let result = serde_json::to_value(result).unwrap();
+        assert_eq!(result["content"][0]["text"], "Board 'Roadmap'");
+        assert_eq!(result["structuredContent"]["board"]["id"], "uXjVO");
+        assert_eq!(result["isError"], false);
+
+        let result = structured_tool_result(
+            "Board 'Roadmap'".to_string(),
+            &value,
+            vec![],
+            ProtocolVersion::V2025_03_26,
+        );
+        let result = serde_json::to_value(result).unwrap();
+        assert!(result.get("structuredContent").is_none());
+        let text: Value =
+            serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+        assert_eq!(text["board"]["name"], "Roadmap");
+    }
+
     #[test]
     fn test_handle_tools_call_missing_params() {
         let req = JsonRpcRequest::new("tools/call").with_id(Value::Number(1.into()));
@@ -411,9 +1103,16 @@ mod tests {
         let token = Arc::new("test-token".to_string());
 
         // Use block_on to run async function in sync test
-        let response = tokio::runtime::Runtime::new()
-            .unwrap()
-            .block_on(async { handle_tools_call(&req, &user_info, &token).await });
+        let response = tokio::runtime::Runtime::new().unwrap().block_on(async {
+            handle_tools_call(
+                &req,
+                &user_info,
+                &token,
+                ProtocolVersion::DEFAULT,
+                &ToolProgress::none(),
+            )
+            .await
+        });
 
         assert!(response.error.is_some());
         assert_eq!(response.error.as_ref().unwrap().code, -32602);
@@ -434,11 +1133,199 @@ mod tests {
         ));
         let token = Arc::new("test-token".to_string());
 
-        let response = tokio::runtime::Runtime::new()
-            .unwrap()
-            .block_on(async { handle_tools_call(&req, &user_info, &token).await });
+        let response = tokio::runtime::Runtime::new().unwrap().block_on(async {
+            handle_tools_call(
+                &req,
+                &user_info,
+                &token,
+                ProtocolVersion::DEFAULT,
+                &ToolProgress::none(),
+            )
+            .await
+        });
 
         assert!(response.error.is_some());
         assert_eq!(response.error.as_ref().unwrap().code, -32601);
     }
+
+    #[test]
+    fn test_handle_initialize_advertises_resources() {
+        let req = JsonRpcRequest::new("initialize").with_id(Value::Number(1.into()));
+        let user_info = Arc::new(UserInfo::new(
+            "test-user".to_string(),
+            "test-team".to_string(),
+            vec![],
+        ));
+
+        let response = handle_initialize(&req, &user_info);
+        let result = response.result.unwrap();
+
+        assert!(result["capabilities"]["resources"].is_object());
+        assert!(result["capabilities"]["prompts"].is_object());
+    }
+
+    #[test]
+    fn test_handle_resource_templates_list() {
+        let req = JsonRpcRequest::new("resources/templates/list").with_id(Value::Number(1.into()));
+
+        let response = handle_resource_templates_list(&req);
+        let result = response.result.unwrap();
+        let templates = result["resourceTemplates"].as_array().unwrap();
+
+        assert_eq!(templates.len(), 2);
+        assert_eq!(
+            templates[1]["uriTemplate"],
+            "miro://boards/{board_id}/items{?type}"
+        );
+    }
+
+    #[test]
+    fn test_handle_resources_read_unknown_uri() {
+        let req = JsonRpcRequest::new("resources/read")
+            .with_id(Value::Number(1.into()))
+            .with_params(json!({ "uri": "file:///etc/passwd" }));
+        let user_info = Arc::new(UserInfo::new(
+            "test-user".to_string(),
+            "test-team".to_string(),
+            vec![],
+        ));
+        let token = Arc::new("test-token".to_string());
+
+        let response = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(async { handle_resources_read(&req, &user_info, &token).await });
+
+        assert_eq!(response.error.as_ref().unwrap().code, -32002);
+    }
+
+    #[test]
+    fn test_handle_prompts_list() {
+        let req = JsonRpcRequest::new("prompts/list").with_id(Value::Number(1.into()));
+
+        let response = handle_prompts_list(&req, ProtocolVersion::LATEST);
+        let result = response.result.unwrap();
+        let prompts = result["prompts"].as_array().unwrap();
+
+        assert_eq!(prompts.len(), 3);
+        assert_eq!(prompts[0]["name"], "board_retrospective");
+        assert_eq!(prompts[0]["arguments"][0]["required"], true);
+    }
+
+    #[test]
+    fn test_handle_prompts_get_missing_argument() {
+        let req = JsonRpcRequest::new("prompts/get")
+            .with_id(Value::Number(1.into()))
+            .with_params(
+                json!({ "name": "cluster_frame_themes", "arguments": { "board_id": "b" } }),
+            );
+        let user_info = Arc::new(UserInfo::new(
+            "test-user".to_string(),
+            "test-team".to_string(),
+            vec![],
+        ));
+        let token = Arc::new("test-token".to_string());
+
+        let response = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(async { handle_prompts_get(&req, &user_info, &token).await });
+
+        let error = response.error.unwrap();
+        assert_eq!(error.code, -32602);
+        assert!(error.message.contains("frame_id"));
+    }
+
+    #[test]
+    fn test_handle_initialize_negotiates_version() {
+        let user_info = Arc::new(UserInfo::new(
+            "test-user".to_string(),
+            "test-team".to_string(),
+            vec![],
+        ));
+
+        let req = JsonRpcRequest::new("initialize")
+            .with_id(Value::Number(1.into()))
+            .with_params(json!({
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "clientInfo": {"name": "claude-ai", "version": "1.0"}
+            }));
+        let result = handle_initialize(&req, &user_info).result.unwrap();
+        assert_eq!(result["protocolVersion"], "2025-06-18");
+        assert_eq!(result["serverInfo"]["name"], "Miro MCP Server");
+        assert_eq!(result["capabilities"]["resources"]["subscribe"], true);
+
+        let req = JsonRpcRequest::new("initialize")
+            .with_id(Value::Number(2.into()))
+            .with_params(json!({ "protocolVersion": "2023-01-01" }));
+        let error = handle_initialize(&req, &user_info).error.unwrap();
+        assert_eq!(error.code, -32602);
+        assert!(error.message.contains("Unsupported protocol version"));
+    }
+
+    #[test]
+    fn test_handle_prompts_list_omits_titles_for_old_versions() {
+        let req = JsonRpcRequest::new("prompts/list").with_id(Value::Number(1.into()));
+
+        let result = handle_prompts_list(&req, ProtocolVersion::V2024_11_05)
+            .result
+            .unwrap();
+        assert!(result["prompts"][0].get("title").is_none());
+
+        let result = handle_prompts_list(&req, ProtocolVersion::V2025_06_18)
+            .result
+            .unwrap();
+        assert_eq!(result["prompts"][0]["title"], "Run a retrospective");
+    }
+
+    #[test]
+    fn test_board_links_only_for_newer_versions() {
+        let boards = vec![Board {
+            id: "uXjVO".to_string(),
+            name: "Sprint board".to_string(),
+            description: None,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+        }];
+
+        assert!(board_links(&boards, ProtocolVersion::V2025_03_26).is_empty());
+
+        let links = board_links(&boards, ProtocolVersion::V2025_06_18);
+        let value = serde_json::to_value(&links).unwrap();
+        assert_eq!(value[0]["type"], "resource_link");
+        assert_eq!(value[0]["uri"], "miro://boards/uXjVO");
+    }
+
+    #[test]
+    fn test_progress_token_and_notification() {
+        let req = JsonRpcRequest {
+            jsonrpc: Some("2.0".to_string()),
+            method: "tools/call".to_string(),
+            params: Some(json!({
+                "name": "list_boards",
+                "_meta": {"progressToken": "abc"}
+            })),
+            id: Some(json!(1)),
+        };
+        let token = progress_token(&req).unwrap();
+        assert_eq!(token, json!("abc"));
+
+        let notification = progress_notification(
+            &token,
+            ProgressUpdate {
+                progress: 50.0,
+                total: None,
+                message: Some("Fetched 50 entries".to_string()),
+            },
+        );
+        let value = serde_json::to_value(&notification).unwrap();
+        assert_eq!(value["method"], "notifications/progress");
+        assert_eq!(value["params"]["progressToken"], "abc");
+        assert_eq!(value["params"]["progress"], 50.0);
+        assert!(value["params"].get("total").is_none());
+
+        let req = JsonRpcRequest {
+            params: Some(json!({"name": "list_boards"})),
+            ..req
+        };
+        assert!(progress_token(&req).is_none());
+    }
 }
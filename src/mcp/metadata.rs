@@ -2,6 +2,7 @@ use axum::{extract::State, response::IntoResponse, Json};
 use serde::Serialize;
 use std::sync::Arc;
 
+use crate::auth::scopes::SUPPORTED_SCOPES;
 use crate::config::Config;
 
 /// OAuth 2.0 Authorization Server Metadata (RFC 8414)
@@ -22,6 +23,20 @@ pub struct OAuthAuthorizationServerMetadata {
     pub response_types_supported: Vec<String>,
     /// Token endpoint auth methods
     pub token_endpoint_auth_methods_supported: Vec<String>,
+    /// PKCE code challenge methods (RFC 7636)
+    pub code_challenge_methods_supported: Vec<String>,
+    /// Token revocation endpoint URL (RFC 7009)
+    pub revocation_endpoint: String,
+    /// Revocation endpoint auth methods
+    pub revocation_endpoint_auth_methods_supported: Vec<String>,
+    /// Token introspection endpoint URL (RFC 7662)
+    pub introspection_endpoint: String,
+    /// Introspection endpoint auth methods (confidential clients only)
+    pub introspection_endpoint_auth_methods_supported: Vec<String>,
+    /// Scopes a client may request
+    pub scopes_supported: Vec<String>,
+    /// Userinfo endpoint URL (OpenID Connect-style claims)
+    pub userinfo_endpoint: String,
 }
 
 /// OAuth 2.0 Protected Resource Metadata
@@ -34,12 +49,15 @@ pub struct OAuthProtectedResourceMetadata {
     pub authorization_endpoint: String,
     /// OAuth 2.0 token endpoint URL (points to our proxy)
     pub token_endpoint: String,
-    /// Grant types supported (authorization_code for standard OAuth flow)
+    /// Grant types supported (authorization_code plus refresh_token)
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub grant_types_supported: Vec<String>,
     /// Response types supported (code for authorization code flow)
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub response_types_supported: Vec<String>,
+    /// Scopes a client may request
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub scopes_supported: Vec<String>,
 }
 
 /// Handle OAuth authorization server metadata endpoint (RFC 8414)
@@ -58,12 +76,30 @@ pub async fn oauth_authorization_server_metadata(
         authorization_endpoint: format!("{}/oauth/authorize", base_url),
         token_endpoint: format!("{}/oauth/token", base_url),
         registration_endpoint: format!("{}/register", base_url),
-        grant_types_supported: vec!["authorization_code".to_string()],
+        grant_types_supported: vec![
+            "authorization_code".to_string(),
+            "refresh_token".to_string(),
+        ],
         response_types_supported: vec!["code".to_string()],
         token_endpoint_auth_methods_supported: vec![
             "client_secret_basic".to_string(),
             "client_secret_post".to_string(),
+            "none".to_string(),
+        ],
+        code_challenge_methods_supported: vec!["S256".to_string()],
+        revocation_endpoint: format!("{}/oauth/revoke", base_url),
+        revocation_endpoint_auth_methods_supported: vec![
+            "client_secret_basic".to_string(),
+            "client_secret_post".to_string(),
+            "none".to_string(),
         ],
+        introspection_endpoint: format!("{}/oauth/introspect", base_url),
+        introspection_endpoint_auth_methods_supported: vec![
+            "client_secret_basic".to_string(),
+            "client_secret_post".to_string(),
+        ],
+        scopes_supported: SUPPORTED_SCOPES.iter().map(|s| s.to_string()).collect(),
+        userinfo_endpoint: format!("{}/oauth/userinfo", base_url),
     })
 }
 
@@ -89,7 +125,11 @@ pub async fn oauth_metadata(State(config): State<Arc<Config>>) -> impl IntoRespo
         issuer: "https://miro.com".to_string(),
         authorization_endpoint: format!("{}/oauth/authorize", base_url),
         token_endpoint: format!("{}/oauth/token", base_url),
-        grant_types_supported: vec!["authorization_code".to_string()],
+        grant_types_supported: vec![
+            "authorization_code".to_string(),
+            "refresh_token".to_string(),
+        ],
         response_types_supported: vec!["code".to_string()],
+        scopes_supported: SUPPORTED_SCOPES.iter().map(|s| s.to_string()).collect(),
     })
 }
@@ -1,17 +1,29 @@
 #[cfg(feature = "stdio-mcp")]
 pub mod auth_handler;
+pub mod confirmation;
 pub mod handlers;
 pub mod metadata;
+pub mod prompts;
 pub mod protocol;
+pub mod resources;
 #[cfg(feature = "stdio-mcp")]
 pub mod server;
+pub mod session;
+pub mod subscriptions;
 pub mod tools;
 
 #[cfg(feature = "stdio-mcp")]
 pub use auth_handler::AuthHandler;
-pub use handlers::{handle_initialize, handle_tools_call, handle_tools_list};
+pub use handlers::{
+    handle_initialize, handle_ping, handle_prompts_get, handle_prompts_list,
+    handle_resource_templates_list, handle_resources_list, handle_resources_read,
+    handle_resources_subscribe, handle_resources_unsubscribe, handle_tools_call, handle_tools_list,
+    insufficient_scope, method_scope, negotiate_protocol_version, progress_notification,
+    progress_token,
+};
 pub use metadata::{oauth_authorization_server_metadata, oauth_metadata};
-pub use protocol::{JsonRpcError, JsonRpcRequest, JsonRpcResponse};
+pub use protocol::{JsonRpcError, JsonRpcNotification, JsonRpcRequest, JsonRpcResponse};
 #[cfg(feature = "stdio-mcp")]
 pub use server::MiroMcpServer;
+pub use session::{McpSession, SessionStore, PROTOCOL_VERSION_HEADER, SESSION_HEADER};
 pub use tools::{get_board, list_boards};
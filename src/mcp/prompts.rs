@@ -0,0 +1,327 @@
+//! MCP prompt templates for common Miro workflows
+//!
+//! Each template takes a few string arguments, names the board resources it
+//! needs, and renders instructions around them. Transports resolve the resource
+//! URIs (via the same rendering as `resources/read`) and embed the contents
+//! ahead of the instructions.
+
+use super::protocol::{
+    Prompt, PromptArgument, PromptContent, PromptGetResult, PromptMessage, ResourceContents,
+};
+use super::resources::board_uri;
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Retrospective over a whole board
+pub const RETROSPECTIVE_PROMPT: &str = "board_retrospective";
+
+/// Theme clustering of the stickies in one frame
+pub const CLUSTER_THEMES_PROMPT: &str = "cluster_frame_themes";
+
+/// Project plan derived from a board
+pub const PROJECT_PLAN_PROMPT: &str = "board_to_project_plan";
+
+/// Prompt errors
+#[derive(Error, Debug, PartialEq)]
+pub enum PromptError {
+    #[error("Unknown prompt: {0}")]
+    UnknownPrompt(String),
+
+    #[error("Missing required argument: {0}")]
+    MissingArgument(&'static str),
+
+    #[error("Invalid value for argument {0}: {1}")]
+    InvalidArgument(&'static str, String),
+}
+
+/// A prompt with its arguments resolved, ready for resource embedding
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptPlan {
+    pub description: String,
+    /// Resources to fetch and embed, in order
+    pub resource_uris: Vec<String>,
+    pub instructions: String,
+}
+
+impl PromptPlan {
+    /// Render the final prompt: embedded resources first, then the instructions
+    pub fn render(self, contents: Vec<ResourceContents>) -> PromptGetResult {
+        let mut messages: Vec<PromptMessage> = contents
+            .into_iter()
+            .map(|resource| PromptMessage {
+                role: "user".to_string(),
+                content: PromptContent::Resource { resource },
+            })
+            .collect();
+        messages.push(PromptMessage {
+            role: "user".to_string(),
+            content: PromptContent::Text {
+                text: self.instructions,
+            },
+        });
+
+        PromptGetResult {
+            description: Some(self.description),
+            messages,
+        }
+    }
+}
+
+fn argument(name: &str, description: &str, required: bool) -> PromptArgument {
+    PromptArgument {
+        name: name.to_string(),
+        description: Some(description.to_string()),
+        required,
+    }
+}
+
+/// Prompt templates advertised by `prompts/list`
+pub fn prompt_templates() -> Vec<Prompt> {
+    vec![
+        Prompt {
+            name: RETROSPECTIVE_PROMPT.to_string(),
+            title: Some("Run a retrospective".to_string()),
+            description: Some(
+                "Run a retrospective on a board: what went well, what didn't, and action items"
+                    .to_string(),
+            ),
+            arguments: vec![
+                argument("board_id", "Board to review", true),
+                argument(
+                    "focus",
+                    "Optional topic to focus on (e.g. \"release process\")",
+                    false,
+                ),
+            ],
+        },
+        Prompt {
+            name: CLUSTER_THEMES_PROMPT.to_string(),
+            title: Some("Cluster stickies into themes".to_string()),
+            description: Some("Group the sticky notes in a frame into named themes".to_string()),
+            arguments: vec![
+                argument("board_id", "Board containing the frame", true),
+                argument("frame_id", "Frame whose stickies should be clustered", true),
+                argument("max_themes", "Maximum number of themes (default 5)", false),
+            ],
+        },
+        Prompt {
+            name: PROJECT_PLAN_PROMPT.to_string(),
+            title: Some("Turn board into a project plan".to_string()),
+            description: Some(
+                "Turn a board into a project plan with milestones, tasks and owners".to_string(),
+            ),
+            arguments: vec![
+                argument("board_id", "Board to plan from", true),
+                argument(
+                    "timeframe",
+                    "Optional delivery timeframe (e.g. \"6 weeks\")",
+                    false,
+                ),
+            ],
+        },
+    ]
+}
+
+/// Required ID argument; IDs are spliced into resource URIs so path and query
+/// separators are rejected
+fn id_argument(
+    arguments: &HashMap<String, String>,
+    name: &'static str,
+) -> Result<String, PromptError> {
+    let value = arguments
+        .get(name)
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .ok_or(PromptError::MissingArgument(name))?;
+
+    if value.contains(['/', '?', '&', '#']) {
+        return Err(PromptError::InvalidArgument(name, value.to_string()));
+    }
+    Ok(value.to_string())
+}
+
+fn optional_argument<'a>(arguments: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    arguments
+        .get(name)
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+}
+
+/// Resolve a prompt template against its arguments
+pub fn build_prompt(
+    name: &str,
+    arguments: &HashMap<String, String>,
+) -> Result<PromptPlan, PromptError> {
+    match name {
+        RETROSPECTIVE_PROMPT => {
+            let board_id = id_argument(arguments, "board_id")?;
+            let mut instructions = format!(
+                "Run a retrospective on the Miro board above (`{}`).\n\n\
+                 1. Summarize what went well.\n\
+                 2. Summarize what didn't go well, grouping related stickies.\n\
+                 3. Call out open questions or disagreements.\n\
+                 4. Propose 3-5 concrete, owner-ready action items.\n\n\
+                 Quote sticky text where it helps and keep the output scannable.",
+                board_id
+            );
+            if let Some(focus) = optional_argument(arguments, "focus") {
+                instructions.push_str(&format!("\n\nFocus the retrospective on: {}", focus));
+            }
+
+            Ok(PromptPlan {
+                description: format!("Retrospective on board {}", board_id),
+                resource_uris: vec![board_uri(&board_id)],
+                instructions,
+            })
+        }
+        CLUSTER_THEMES_PROMPT => {
+            let board_id = id_argument(arguments, "board_id")?;
+            let frame_id = id_argument(arguments, "frame_id")?;
+            let max_themes = match optional_argument(arguments, "max_themes") {
+                Some(value) => value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| PromptError::InvalidArgument("max_themes", value.to_string()))?,
+                None => 5,
+            };
+
+            Ok(PromptPlan {
+                description: format!(
+                    "Theme clustering of frame {} on board {}",
+                    frame_id, board_id
+                ),
+                resource_uris: vec![
+                    format!("{}/items?type=frame", board_uri(&board_id)),
+                    format!("{}/items?type=sticky_note", board_uri(&board_id)),
+                ],
+                instructions: format!(
+                    "Cluster the sticky notes in frame `{}` into at most {} themes.\n\n\
+                     Only consider sticky notes whose `parent_id` is `{}`. For each theme, give a \
+                     short name, a one-sentence description, and the IDs of its stickies. List \
+                     stickies that fit no theme separately. If useful, suggest which `fillColor` \
+                     each theme could use so the clusters can be recolored on the board.",
+                    frame_id, max_themes, frame_id
+                ),
+            })
+        }
+        PROJECT_PLAN_PROMPT => {
+            let board_id = id_argument(arguments, "board_id")?;
+            let mut instructions = format!(
+                "Turn the Miro board above (`{}`) into a project plan.\n\n\
+                 Include:\n\
+                 - Goal: one or two sentences.\n\
+                 - Milestones: ordered, each with its exit criteria.\n\
+                 - Tasks: grouped by milestone, with suggested owners where the board names them.\n\
+                 - Dependencies and risks drawn from connectors and open questions.\n\n\
+                 Use frames as the primary structure when the board has them.",
+                board_id
+            );
+            if let Some(timeframe) = optional_argument(arguments, "timeframe") {
+                instructions.push_str(&format!(
+                    "\n\nFit the plan into this timeframe: {}",
+                    timeframe
+                ));
+            }
+
+            Ok(PromptPlan {
+                description: format!("Project plan from board {}", board_id),
+                resource_uris: vec![format!("{}?format=json", board_uri(&board_id))],
+                instructions,
+            })
+        }
+        _ => Err(PromptError::UnknownPrompt(name.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_every_template_builds_with_required_arguments() {
+        let arguments = args(&[("board_id", "uXjVO"), ("frame_id", "3458")]);
+
+        for prompt in prompt_templates() {
+            let plan = build_prompt(&prompt.name, &arguments).unwrap();
+            assert!(!plan.resource_uris.is_empty());
+            assert!(plan
+                .resource_uris
+                .iter()
+                .all(|uri| uri.starts_with("miro://boards/uXjVO")));
+        }
+    }
+
+    #[test]
+    fn test_build_prompt_validates_arguments() {
+        assert_eq!(
+            build_prompt(RETROSPECTIVE_PROMPT, &args(&[])),
+            Err(PromptError::MissingArgument("board_id"))
+        );
+        assert_eq!(
+            build_prompt(CLUSTER_THEMES_PROMPT, &args(&[("board_id", "uXjVO")])),
+            Err(PromptError::MissingArgument("frame_id"))
+        );
+        assert!(matches!(
+            build_prompt(RETROSPECTIVE_PROMPT, &args(&[("board_id", "a/items")])),
+            Err(PromptError::InvalidArgument("board_id", _))
+        ));
+        assert!(matches!(
+            build_prompt(
+                CLUSTER_THEMES_PROMPT,
+                &args(&[("board_id", "b"), ("frame_id", "f"), ("max_themes", "zero")])
+            ),
+            Err(PromptError::InvalidArgument("max_themes", _))
+        ));
+        assert_eq!(
+            build_prompt("nope", &args(&[])),
+            Err(PromptError::UnknownPrompt("nope".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_optional_arguments_shape_instructions() {
+        let plan = build_prompt(
+            RETROSPECTIVE_PROMPT,
+            &args(&[("board_id", "b"), ("focus", "release process")]),
+        )
+        .unwrap();
+        assert!(plan
+            .instructions
+            .ends_with("Focus the retrospective on: release process"));
+
+        let plan = build_prompt(
+            CLUSTER_THEMES_PROMPT,
+            &args(&[("board_id", "b"), ("frame_id", "f"), ("max_themes", "3")]),
+        )
+        .unwrap();
+        assert!(plan.instructions.contains("at most 3 themes"));
+    }
+
+    #[test]
+    fn test_render_embeds_resources_before_instructions() {
+        let plan = build_prompt(PROJECT_PLAN_PROMPT, &args(&[("board_id", "b")])).unwrap();
+        let result = plan.render(vec![ResourceContents {
+            uri: "miro://boards/b?format=json".to_string(),
+            mime_type: Some("application/json".to_string()),
+            text: "{}".to_string(),
+        }]);
+
+        assert_eq!(result.messages.len(), 2);
+        let value = serde_json::to_value(&result).unwrap();
+        assert_eq!(value["messages"][0]["content"]["type"], "resource");
+        assert_eq!(
+            value["messages"][0]["content"]["resource"]["uri"],
+            "miro://boards/b?format=json"
+        );
+        assert_eq!(value["messages"][1]["content"]["type"], "text");
+        assert_eq!(value["messages"][1]["role"], "user");
+    }
+}
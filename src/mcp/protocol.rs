@@ -5,6 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 
 /// JSON-RPC 2.0 Request
 /// Spec: https://www.jsonrpc.org/specification
@@ -57,6 +58,25 @@ impl JsonRpcRequest {
     }
 }
 
+/// JSON-RPC 2.0 Notification sent from server to client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcNotification {
+    pub jsonrpc: String, // "2.0"
+    pub method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+impl JsonRpcNotification {
+    pub fn new(method: impl Into<String>, params: Option<Value>) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            method: method.into(),
+            params,
+        }
+    }
+}
+
 /// JSON-RPC 2.0 Success Response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsonRpcResponse {
@@ -101,6 +121,15 @@ pub struct JsonRpcError {
 }
 
 impl JsonRpcError {
+    /// Parse error (-32700): the body is not valid JSON
+    pub fn parse_error(message: impl Into<String>) -> Self {
+        Self {
+            code: -32700,
+            message: message.into(),
+            data: None,
+        }
+    }
+
     /// Invalid Request (-32600)
     pub fn invalid_request(message: impl Into<String>) -> Self {
         Self {
@@ -156,14 +185,124 @@ impl JsonRpcError {
 
 // ===================== MCP Protocol Messages =====================
 
+/// MCP protocol revisions supported by this server, oldest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProtocolVersion {
+    V2024_11_05,
+    V2025_03_26,
+    V2025_06_18,
+}
+
+impl ProtocolVersion {
+    /// Every supported revision, oldest first
+    pub const SUPPORTED: [ProtocolVersion; 3] = [
+        ProtocolVersion::V2024_11_05,
+        ProtocolVersion::V2025_03_26,
+        ProtocolVersion::V2025_06_18,
+    ];
+
+    /// Newest supported revision
+    pub const LATEST: ProtocolVersion = ProtocolVersion::V2025_06_18;
+
+    /// Assumed when a client doesn't say (the revision before version headers existed)
+    pub const DEFAULT: ProtocolVersion = ProtocolVersion::V2024_11_05;
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProtocolVersion::V2024_11_05 => "2024-11-05",
+            ProtocolVersion::V2025_03_26 => "2025-03-26",
+            ProtocolVersion::V2025_06_18 => "2025-06-18",
+        }
+    }
+
+    /// Parse an exact supported revision
+    pub fn parse(version: &str) -> Option<Self> {
+        Self::SUPPORTED
+            .into_iter()
+            .find(|supported| supported.as_str() == version)
+    }
+
+    /// Pick the revision to use for a client's requested version
+    ///
+    /// Supported versions are echoed back. Newer, well-formed revisions fall back
+    /// to [`ProtocolVersion::LATEST`] so the client can decide whether to continue.
+    /// Older or malformed versions are rejected.
+    pub fn negotiate(requested: Option<&str>) -> Result<Self, JsonRpcError> {
+        let Some(requested) = requested else {
+            return Ok(Self::DEFAULT);
+        };
+        if let Some(version) = Self::parse(requested) {
+            return Ok(version);
+        }
+
+        // Revisions are ISO dates, so lexical order is release order
+        let is_revision = requested.len() == 10
+            && requested.chars().enumerate().all(|(i, c)| {
+                if i == 4 || i == 7 {
+                    c == '-'
+                } else {
+                    c.is_ascii_digit()
+                }
+            });
+        if is_revision && requested > Self::LATEST.as_str() {
+            return Ok(Self::LATEST);
+        }
+
+        Err(JsonRpcError {
+            code: -32602,
+            message: format!(
+                "Unsupported protocol version: {} (supported: {})",
+                requested,
+                Self::SUPPORTED.map(Self::as_str).join(", ")
+            ),
+            data: Some(serde_json::json!({
+                "supported": Self::SUPPORTED.map(Self::as_str),
+                "requested": requested,
+            })),
+        })
+    }
+
+    /// Tool results may carry `structuredContent` validated by `outputSchema`
+    pub fn supports_structured_output(self) -> bool {
+        self >= ProtocolVersion::V2025_06_18
+    }
+
+    /// Tool results may contain `resource_link` content
+    pub fn supports_resource_links(self) -> bool {
+        self >= ProtocolVersion::V2025_06_18
+    }
+
+    /// Tools, prompts and resources may carry a display `title`
+    pub fn supports_titles(self) -> bool {
+        self >= ProtocolVersion::V2025_06_18
+    }
+
+    /// Tools may carry behavior annotations
+    pub fn supports_tool_annotations(self) -> bool {
+        self >= ProtocolVersion::V2025_03_26
+    }
+
+    /// Requests may be sent as JSON-RPC batches (removed in 2025-06-18)
+    pub fn supports_batching(self) -> bool {
+        self < ProtocolVersion::V2025_06_18
+    }
+}
+
+impl std::fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// MCP Initialize Request
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct InitializeRequest {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", alias = "protocol_version")]
     pub protocol_version: Option<String>, // "2024-11-05" or similar
     #[serde(skip_serializing_if = "Option::is_none")]
     pub capabilities: Option<Value>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", alias = "client_info")]
     pub client_info: Option<ClientInfo>,
 }
 
@@ -177,6 +316,7 @@ pub struct ClientInfo {
 
 /// MCP Server Capabilities
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ServerCapabilities {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tools: Option<ToolsCapability>,
@@ -187,12 +327,14 @@ pub struct ServerCapabilities {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ToolsCapability {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub list_changed: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ResourcesCapability {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub subscribe: Option<bool>,
@@ -201,6 +343,7 @@ pub struct ResourcesCapability {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PromptsCapability {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub list_changed: Option<bool>,
@@ -208,6 +351,7 @@ pub struct PromptsCapability {
 
 /// Initialize Result
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct InitializeResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub protocol_version: Option<String>,
@@ -227,11 +371,57 @@ pub struct ServerInfo {
 
 /// Tool definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Tool {
     pub name: String,
-    pub description: String,
+    /// Display name (2025-06-18+)
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none", alias = "input_schema")]
     pub input_schema: Option<Value>, // JSON Schema
+    /// JSON Schema of `structuredContent` (2025-06-18+)
+    #[serde(skip_serializing_if = "Option::is_none", alias = "output_schema")]
+    pub output_schema: Option<Value>,
+    /// Behavior hints (2025-03-26+)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<ToolAnnotations>,
+}
+
+/// Tool behavior hints
+///
+/// Clients use these to decide which calls need approval, e.g. auto-approving
+/// read-only tools. They are hints only and never replace authorization.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolAnnotations {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The tool doesn't modify anything
+    #[serde(skip_serializing_if = "Option::is_none", alias = "read_only_hint")]
+    pub read_only_hint: Option<bool>,
+    /// The tool may delete or overwrite existing data
+    #[serde(skip_serializing_if = "Option::is_none", alias = "destructive_hint")]
+    pub destructive_hint: Option<bool>,
+    /// Repeating a call with the same arguments has no further effect
+    #[serde(skip_serializing_if = "Option::is_none", alias = "idempotent_hint")]
+    pub idempotent_hint: Option<bool>,
+    /// The tool talks to systems outside the server (the Miro API)
+    #[serde(skip_serializing_if = "Option::is_none", alias = "open_world_hint")]
+    pub open_world_hint: Option<bool>,
+}
+
+impl ToolAnnotations {
+    /// Hints for a tool that only reads from Miro
+    pub fn read_only(title: &str) -> Self {
+        Self {
+            title: Some(title.to_string()),
+            read_only_hint: Some(true),
+            destructive_hint: None,
+            idempotent_hint: None,
+            open_world_hint: Some(true),
+        }
+    }
 }
 
 /// Tools List Result
@@ -252,8 +442,12 @@ pub struct ToolCallParams {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ToolCallResult {
+    #[serde(rename_all = "camelCase")]
     Success {
-        content: Vec<TextContent>,
+        content: Vec<ToolContent>,
+        /// Typed result matching the tool's `outputSchema` (2025-06-18+)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        structured_content: Option<Value>,
         #[serde(skip_serializing_if = "Option::is_none")]
         is_error: Option<bool>,
     },
@@ -263,6 +457,14 @@ pub enum ToolCallResult {
     },
 }
 
+/// A content block of a tool result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ToolContent {
+    Text(TextContent),
+    ResourceLink(ResourceLink),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TextContent {
     #[serde(rename = "type")]
@@ -270,6 +472,164 @@ pub struct TextContent {
     pub text: String,
 }
 
+impl From<TextContent> for ToolContent {
+    fn from(content: TextContent) -> Self {
+        ToolContent::Text(content)
+    }
+}
+
+/// Link to a resource the client can read later (2025-06-18+)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceLink {
+    #[serde(rename = "type")]
+    pub content_type: String, // "resource_link"
+    pub uri: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+impl From<Resource> for ResourceLink {
+    fn from(resource: Resource) -> Self {
+        Self {
+            content_type: "resource_link".to_string(),
+            uri: resource.uri,
+            name: resource.name,
+            description: resource.description,
+            mime_type: resource.mime_type,
+        }
+    }
+}
+
+// ===================== Resources Messages =====================
+
+/// Resource definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Resource {
+    pub uri: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+/// Resource template definition (RFC 6570 URI template)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceTemplate {
+    pub uri_template: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+/// Resources List Result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourcesListResult {
+    pub resources: Vec<Resource>,
+}
+
+/// Resource Templates List Result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceTemplatesListResult {
+    pub resource_templates: Vec<ResourceTemplate>,
+}
+
+/// Resource Read Request Params
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceReadParams {
+    pub uri: String,
+}
+
+/// Parameters of resources/subscribe and resources/unsubscribe
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceSubscribeParams {
+    pub uri: String,
+}
+
+/// Text contents of a resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceContents {
+    pub uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    pub text: String,
+}
+
+/// Resource Read Result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceReadResult {
+    pub contents: Vec<ResourceContents>,
+}
+
+/// Prompt template definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Prompt {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<PromptArgument>,
+}
+
+/// Argument accepted by a prompt template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptArgument {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub required: bool,
+}
+
+/// Prompts List Result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptsListResult {
+    pub prompts: Vec<Prompt>,
+}
+
+/// Parameters of prompts/get
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptGetParams {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: HashMap<String, String>,
+}
+
+/// Content of a prompt message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PromptContent {
+    Text { text: String },
+    Resource { resource: ResourceContents },
+}
+
+/// A message in a rendered prompt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptMessage {
+    pub role: String,
+    pub content: PromptContent,
+}
+
+/// Prompt Get Result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptGetResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub messages: Vec<PromptMessage>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -326,10 +686,52 @@ mod tests {
     fn test_tool_definition_serialization() {
         let tool = Tool {
             name: "list_boards".to_string(),
+            title: None,
             description: "List all boards".to_string(),
-            input_schema: None,
+            input_schema: Some(json!({"type": "object"})),
+            output_schema: None,
+            annotations: Some(ToolAnnotations::read_only("List boards")),
         };
         let json = serde_json::to_string(&tool).unwrap();
         assert!(json.contains("list_boards"));
+        assert!(json.contains("\"inputSchema\""));
+        assert!(!json.contains("outputSchema"));
+        assert!(!json.contains("\"title\":null"));
+        assert!(json.contains("\"readOnlyHint\":true"));
+        assert!(json.contains("\"openWorldHint\":true"));
+        assert!(!json.contains("destructiveHint"));
+    }
+
+    #[test]
+    fn test_protocol_version_negotiation() {
+        assert_eq!(
+            ProtocolVersion::negotiate(Some("2025-03-26")).unwrap(),
+            ProtocolVersion::V2025_03_26
+        );
+        assert_eq!(
+            ProtocolVersion::negotiate(None).unwrap(),
+            ProtocolVersion::DEFAULT
+        );
+        // Newer revisions fall back to the latest we speak
+        assert_eq!(
+            ProtocolVersion::negotiate(Some("2099-01-01")).unwrap(),
+            ProtocolVersion::LATEST
+        );
+
+        let error = ProtocolVersion::negotiate(Some("2023-01-01")).unwrap_err();
+        assert_eq!(error.code, -32602);
+        assert!(error.message.contains("2023-01-01"));
+        assert_eq!(error.data.unwrap()["supported"][2], "2025-06-18");
+        assert!(ProtocolVersion::negotiate(Some("latest")).is_err());
+    }
+
+    #[test]
+    fn test_protocol_version_features() {
+        assert!(!ProtocolVersion::V2024_11_05.supports_tool_annotations());
+        assert!(ProtocolVersion::V2025_03_26.supports_tool_annotations());
+        assert!(!ProtocolVersion::V2025_03_26.supports_structured_output());
+        assert!(ProtocolVersion::V2025_06_18.supports_resource_links());
+        assert!(ProtocolVersion::V2025_03_26.supports_batching());
+        assert!(!ProtocolVersion::V2025_06_18.supports_batching());
     }
 }
@@ -0,0 +1,294 @@
+//! MCP resources exposing Miro boards as readable context
+//!
+//! URIs:
+//! - `miro://boards` - markdown list of accessible boards
+//! - `miro://boards/{board_id}` - markdown outline of a board (`?format=json` for a snapshot)
+//! - `miro://boards/{board_id}/items` - compact JSON items (`?type=frame` to filter)
+//!
+//! Parsing and rendering live here so both the HTTP and stdio transports serve
+//! identical content; each transport only supplies the Miro fetches.
+
+use super::protocol::{Resource, ResourceContents, ResourceTemplate};
+use crate::miro::snapshot::{BoardSnapshot, SnapshotItem};
+use crate::miro::summary::{render_summary, SummaryOptions};
+use crate::miro::types::{Board, Connector, Item};
+
+/// URI scheme prefix for board resources
+const BOARDS_URI: &str = "miro://boards";
+
+/// MIME type of markdown renderings
+pub const MARKDOWN_MIME: &str = "text/markdown";
+
+/// MIME type of JSON renderings
+pub const JSON_MIME: &str = "application/json";
+
+/// A parsed board resource URI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceUri {
+    /// `miro://boards`
+    Boards,
+    /// `miro://boards/{board_id}`
+    Board { board_id: String, json: bool },
+    /// `miro://boards/{board_id}/items`
+    BoardItems {
+        board_id: String,
+        item_type: Option<String>,
+    },
+}
+
+impl ResourceUri {
+    /// Parse a `miro://` resource URI
+    ///
+    /// Board IDs and item types end up in Miro API URLs, so only characters
+    /// that need no escaping there are accepted.
+    pub fn parse(uri: &str) -> Result<Self, String> {
+        let unknown = || format!("Unknown resource URI: {}", uri);
+        let rest = uri
+            .strip_prefix(BOARDS_URI)
+            .filter(|rest| rest.is_empty() || rest.starts_with(['/', '?']))
+            .ok_or_else(unknown)?;
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let query_param = |name: &str| {
+            query
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        match segments.as_slice() {
+            [] => Ok(ResourceUri::Boards),
+            [board_id] if is_board_id(board_id) => Ok(ResourceUri::Board {
+                board_id: board_id.to_string(),
+                json: query_param("format").as_deref() == Some("json"),
+            }),
+            [board_id, "items"] if is_board_id(board_id) => {
+                let item_type = query_param("type");
+                if item_type.as_deref().is_some_and(|t| !is_item_type(t)) {
+                    return Err(format!("Invalid item type in resource URI: {}", uri));
+                }
+                Ok(ResourceUri::BoardItems {
+                    board_id: board_id.to_string(),
+                    item_type,
+                })
+            }
+            _ => Err(unknown()),
+        }
+    }
+
+    /// Board the URI refers to, if any
+    pub fn board_id(&self) -> Option<&str> {
+        match self {
+            ResourceUri::Boards => None,
+            ResourceUri::Board { board_id, .. } | ResourceUri::BoardItems { board_id, .. } => {
+                Some(board_id)
+            }
+        }
+    }
+}
+
+/// Board IDs look like `uXjVO-abc_12=`
+fn is_board_id(value: &str) -> bool {
+    value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '='))
+}
+
+/// Item types look like `sticky_note`
+fn is_item_type(value: &str) -> bool {
+    value.chars().all(|c| c.is_ascii_lowercase() || c == '_')
+}
+
+/// URI of a board resource
+pub fn board_uri(board_id: &str) -> String {
+    format!("{}/{}", BOARDS_URI, board_id)
+}
+
+/// Resource templates advertised by `resources/templates/list`
+pub fn resource_templates() -> Vec<ResourceTemplate> {
+    vec![
+        ResourceTemplate {
+            uri_template: format!("{}/{{board_id}}{{?format}}", BOARDS_URI),
+            name: "Miro board".to_string(),
+            description: Some(
+                "Markdown outline of a board (frames, stickies, connectors); format=json returns a snapshot"
+                    .to_string(),
+            ),
+            mime_type: Some(MARKDOWN_MIME.to_string()),
+        },
+        ResourceTemplate {
+            uri_template: format!("{}/{{board_id}}/items{{?type}}", BOARDS_URI),
+            name: "Miro board items".to_string(),
+            description: Some(
+                "Compact JSON list of board items, optionally filtered by type (e.g. type=frame)"
+                    .to_string(),
+            ),
+            mime_type: Some(JSON_MIME.to_string()),
+        },
+    ]
+}
+
+/// Resources advertised by `resources/list`: the board index plus one entry per board
+pub fn board_resources(boards: &[Board]) -> Vec<Resource> {
+    let mut resources = vec![Resource {
+        uri: BOARDS_URI.to_string(),
+        name: "Miro boards".to_string(),
+        description: Some("All boards accessible to the authenticated user".to_string()),
+        mime_type: Some(MARKDOWN_MIME.to_string()),
+    }];
+
+    resources.extend(boards.iter().map(|board| Resource {
+        uri: board_uri(&board.id),
+        name: board.name.clone(),
+        description: board.description.clone().filter(|d| !d.is_empty()),
+        mime_type: Some(MARKDOWN_MIME.to_string()),
+    }));
+
+    resources
+}
+
+/// Render the board index as markdown
+pub fn render_boards(uri: &str, boards: &[Board]) -> ResourceContents {
+    let mut text = format!("# Miro boards ({})", boards.len());
+    for board in boards {
+        text.push_str(&format!("\n- {} (`{}`)", board.name, board_uri(&board.id)));
+        if let Some(description) = board.description.as_deref().filter(|d| !d.is_empty()) {
+            text.push_str(&format!(" - {}", description));
+        }
+    }
+
+    ResourceContents {
+        uri: uri.to_string(),
+        mime_type: Some(MARKDOWN_MIME.to_string()),
+        text,
+    }
+}
+
+/// Render a board as a markdown outline, or as a JSON snapshot
+pub fn render_board(
+    uri: &str,
+    board: Board,
+    items: &[Item],
+    connectors: Vec<Connector>,
+    json: bool,
+) -> ResourceContents {
+    let snapshot = BoardSnapshot::new(board, items, connectors, vec![]);
+
+    let (mime_type, text) = if json {
+        (
+            JSON_MIME,
+            serde_json::to_string(&snapshot).unwrap_or_else(|_| "{}".to_string()),
+        )
+    } else {
+        (
+            MARKDOWN_MIME,
+            render_summary(&snapshot, &SummaryOptions::default()),
+        )
+    };
+
+    ResourceContents {
+        uri: uri.to_string(),
+        mime_type: Some(mime_type.to_string()),
+        text,
+    }
+}
+
+/// Render board items as compact JSON
+pub fn render_items(uri: &str, items: &[Item]) -> ResourceContents {
+    let items: Vec<SnapshotItem> = items.iter().map(SnapshotItem::from).collect();
+
+    ResourceContents {
+        uri: uri.to_string(),
+        mime_type: Some(JSON_MIME.to_string()),
+        text: serde_json::to_string(&items).unwrap_or_else(|_| "[]".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board() -> Board {
+        Board {
+            id: "uXjVO".to_string(),
+            name: "Sprint board".to_string(),
+            description: Some("Team sprint".to_string()),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_resource_uris() {
+        assert_eq!(
+            ResourceUri::parse("miro://boards").unwrap(),
+            ResourceUri::Boards
+        );
+        assert_eq!(
+            ResourceUri::parse("miro://boards/uXjVO").unwrap(),
+            ResourceUri::Board {
+                board_id: "uXjVO".to_string(),
+                json: false
+            }
+        );
+        assert_eq!(
+            ResourceUri::parse("miro://boards/uXjVO?format=json").unwrap(),
+            ResourceUri::Board {
+                board_id: "uXjVO".to_string(),
+                json: true
+            }
+        );
+        assert_eq!(
+            ResourceUri::parse("miro://boards/uXjVO/items?type=frame").unwrap(),
+            ResourceUri::BoardItems {
+                board_id: "uXjVO".to_string(),
+                item_type: Some("frame".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_uris() {
+        assert!(ResourceUri::parse("file:///etc/passwd").is_err());
+        assert!(ResourceUri::parse("miro://boards/uXjVO/connectors").is_err());
+        assert!(ResourceUri::parse("miro://boardsXYZ").is_err());
+        assert!(ResourceUri::parse("miro://boardsXYZ/items").is_err());
+        assert!(ResourceUri::parse("miro://boards/uXj%2F..").is_err());
+        assert!(ResourceUri::parse("miro://boards/uXjVO#frag").is_err());
+        assert!(ResourceUri::parse("miro://boards/uXjVO/items?type=frame%26limit=1").is_err());
+        assert!(ResourceUri::parse("miro://boards/uXjVO/items?type=../tags").is_err());
+    }
+
+    #[test]
+    fn test_board_resources_include_index() {
+        let resources = board_resources(&[board()]);
+
+        assert_eq!(resources.len(), 2);
+        assert_eq!(resources[0].uri, "miro://boards");
+        assert_eq!(resources[1].uri, "miro://boards/uXjVO");
+        assert_eq!(resources[1].name, "Sprint board");
+    }
+
+    #[test]
+    fn test_render_boards_markdown() {
+        let contents = render_boards("miro://boards", &[board()]);
+
+        assert_eq!(contents.mime_type.as_deref(), Some(MARKDOWN_MIME));
+        assert!(contents.text.starts_with("# Miro boards (1)"));
+        assert!(contents
+            .text
+            .contains("- Sprint board (`miro://boards/uXjVO`) - Team sprint"));
+    }
+
+    #[test]
+    fn test_render_board_formats() {
+        let markdown = render_board("miro://boards/uXjVO", board(), &[], vec![], false);
+        assert!(markdown.text.starts_with("# Sprint board"));
+
+        let json = render_board("miro://boards/uXjVO", board(), &[], vec![], true);
+        assert_eq!(json.mime_type.as_deref(), Some(JSON_MIME));
+        let value: serde_json::Value = serde_json::from_str(&json.text).unwrap();
+        assert_eq!(value["board"]["id"], "uXjVO");
+    }
+}
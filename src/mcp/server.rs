@@ -1,5 +1,21 @@
 use crate::auth::{MiroOAuthClient, TokenStore};
 use crate::config::Config;
+use crate::mcp::confirmation::{self, PendingChange};
+use crate::mcp::prompts;
+use crate::mcp::protocol;
+use crate::mcp::resources::{self, ResourceUri};
+use crate::mcp::subscriptions::{
+    FetchItems, NotifyUpdated, SubscriptionConfig, SubscriptionError, SubscriptionManager,
+};
+use crate::mcp::tools::{BoardInfo, ListBoardsResponse};
+use crate::miro::csv_import::CsvImportReport;
+use crate::miro::csv_import::{self, CsvColumnMapping, CsvLayout, CsvTable};
+use crate::miro::diff::{self, BoardDiff};
+use crate::miro::progress::{ProgressUpdate, ToolProgress};
+use crate::miro::restore::{self, RestoreReport, RestoreTarget};
+use crate::miro::snapshot::{self, BoardSnapshot, SnapshotDir, SnapshotError};
+use crate::miro::summary::{self, SummaryOptions};
+use crate::miro::types::Item;
 use crate::miro::MiroClient;
 use rmcp::{
     handler::server::tool::{ToolCallContext, ToolRouter},
@@ -7,8 +23,11 @@ use rmcp::{
     service::RequestContext,
     tool, tool_router, ErrorData as McpError, RoleServer, ServerHandler,
 };
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Parameters for creating a sticky note
 #[derive(Debug, Serialize, Deserialize)]
@@ -98,6 +117,9 @@ pub struct UpdateItemParams {
 pub struct DeleteItemParams {
     pub board_id: String,
     pub item_id: String,
+    /// Confirms the deletion for clients without elicitation support
+    #[serde(default)]
+    pub confirm: bool,
 }
 
 /// Parameters for creating a connector
@@ -125,6 +147,242 @@ pub struct BulkCreateItemsParams {
     pub items: Vec<serde_json::Value>, // Array of item definitions (type-specific)
 }
 
+/// Parameters for importing CSV rows as sticky notes or cards
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportCsvParams {
+    pub board_id: String,
+    pub csv: String,
+    #[serde(flatten)]
+    pub mapping: CsvColumnMapping,
+    #[serde(flatten)]
+    pub layout: CsvLayout,
+    /// Confirms a large import for clients without elicitation support
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// Parameters for exporting a board snapshot
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportBoardParams {
+    pub board_id: String,
+    /// File to write the snapshot to, relative to the snapshot directory; the
+    /// snapshot JSON is returned when omitted
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Replace the file if it already exists
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+/// Parameters for restoring a board snapshot
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportSnapshotParams {
+    /// Snapshot file to read, relative to the snapshot directory
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Inline snapshot object (alternative to path)
+    #[serde(default)]
+    pub snapshot: Option<serde_json::Value>,
+    /// Existing board to restore into
+    #[serde(default)]
+    pub board_id: Option<String>,
+    /// Name of the new board to create when board_id is omitted
+    #[serde(default)]
+    pub board_name: Option<String>,
+    /// Confirms a large restore for clients without elicitation support
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// Parameters for diffing board states
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DiffBoardParams {
+    /// Baseline snapshot file, relative to the snapshot directory
+    #[serde(default)]
+    pub before_path: Option<String>,
+    /// Inline baseline snapshot (alternative to before_path)
+    #[serde(default)]
+    pub before: Option<serde_json::Value>,
+    /// Snapshot file to compare against, relative to the snapshot directory; the
+    /// live board is used when no "after" is given
+    #[serde(default)]
+    pub after_path: Option<String>,
+    /// Inline snapshot to compare against (alternative to after_path)
+    #[serde(default)]
+    pub after: Option<serde_json::Value>,
+    /// Live board to compare against (defaults to the baseline snapshot's board)
+    #[serde(default)]
+    pub board_id: Option<String>,
+}
+
+/// Parameters for summarizing a board
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummarizeBoardParams {
+    pub board_id: String,
+    #[serde(flatten)]
+    pub options: SummaryOptions,
+}
+
+/// Result of create_board
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CreatedItemSummary {
+    pub id: String,
+    /// Kind of object created, e.g. "board" or "sticky_note"
+    pub item_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Result of list_items
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ListItemsResponse {
+    pub board_id: String,
+    pub items: Vec<Item>,
+    pub count: usize,
+}
+
+/// Result of export_board
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ExportBoardResponse {
+    pub board_id: String,
+    pub board_name: String,
+    /// File the snapshot was written to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    pub items: usize,
+    pub connectors: usize,
+    pub frames: usize,
+    pub tags: usize,
+    /// The snapshot itself, when no path was given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<serde_json::Value>,
+}
+
+/// `outputSchema` for a tool returning `T` as `structuredContent`
+fn output_schema<T: JsonSchema + 'static>() -> Arc<JsonObject> {
+    rmcp::handler::server::common::cached_schema_for_type::<T>()
+}
+
+/// Successful tool result: a short summary for people, `value` for programs
+fn structured_result<T: Serialize>(
+    summary: impl Into<String>,
+    value: &T,
+) -> Result<CallToolResult, McpError> {
+    let structured =
+        serde_json::to_value(value).map_err(|e| McpError::internal_error(e.to_string(), None))?;
+    Ok(CallToolResult {
+        content: vec![Content::text(summary)],
+        structured_content: Some(structured),
+        is_error: Some(false),
+        meta: None,
+    })
+}
+
+/// Protocol version negotiated with the connected client
+fn negotiated_version(ctx: &RequestContext<RoleServer>) -> protocol::ProtocolVersion {
+    ctx.peer
+        .peer_info()
+        .and_then(|info| {
+            protocol::ProtocolVersion::negotiate(Some(&info.protocol_version.to_string())).ok()
+        })
+        .unwrap_or(protocol::ProtocolVersion::DEFAULT)
+}
+
+/// Adapt a tool result to clients that predate structured output: they get
+/// the typed value serialized as text instead of the summary
+fn downgrade_structured_result(result: CallToolResult) -> CallToolResult {
+    match result.structured_content {
+        Some(structured) => CallToolResult {
+            content: vec![Content::text(
+                serde_json::to_string_pretty(&structured)
+                    .unwrap_or_else(|_| structured.to_string()),
+            )],
+            structured_content: None,
+            ..result
+        },
+        None => result,
+    }
+}
+
+/// How long to wait for the user to answer a confirmation prompt
+const ELICITATION_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// Short description of an item for confirmation prompts, e.g. `sticky_note "Ship it"`
+fn describe_item(item: &serde_json::Value) -> String {
+    let item_type = item.get("type").and_then(|t| t.as_str()).unwrap_or("item");
+    let label = item.get("data").and_then(|data| {
+        ["content", "title"]
+            .iter()
+            .find_map(|key| data.get(*key).and_then(|v| v.as_str()))
+    });
+    match label.map(str::trim).filter(|label| !label.is_empty()) {
+        Some(label) if label.chars().count() > 60 => {
+            let short: String = label.chars().take(57).collect();
+            format!("{} \"{}...\"", item_type, short)
+        }
+        Some(label) => format!("{} \"{}\"", item_type, label),
+        None => item_type.to_string(),
+    }
+}
+
+/// Load a snapshot from either a file in the snapshot directory or an inline JSON value
+fn load_snapshot(
+    snapshot_dir: &SnapshotDir,
+    path: Option<&str>,
+    inline: Option<&serde_json::Value>,
+    label: &str,
+) -> Result<Option<BoardSnapshot>, McpError> {
+    let snapshot = match (path, inline) {
+        (Some(path), None) => snapshot_dir.read(path),
+        (None, Some(value)) => BoardSnapshot::from_json(&value.to_string()),
+        (None, None) => return Ok(None),
+        (Some(_), Some(_)) => {
+            return Err(McpError::invalid_params(
+                format!("Provide only one of '{0}_path' or '{0}'", label),
+                None,
+            ))
+        }
+    };
+    snapshot
+        .map(Some)
+        .map_err(|e| McpError::invalid_params(e.to_string(), None))
+}
+
+/// Progress handle for a tool call
+///
+/// Updates are sent as `notifications/progress` when the client supplied a
+/// `progressToken`, in order, from a forwarding task that ends with the handle.
+/// Cancellation follows the request's cancellation token, which rmcp trips on
+/// `notifications/cancelled`.
+fn tool_progress(ctx: &RequestContext<RoleServer>) -> ToolProgress {
+    let ct = ctx.ct.clone();
+    let progress = ToolProgress::none().with_cancel_check(Arc::new(move || ct.is_cancelled()));
+
+    let Some(progress_token) = ctx.meta.get_progress_token() else {
+        return progress;
+    };
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<ProgressUpdate>();
+    let peer = ctx.peer.clone();
+    tokio::spawn(async move {
+        while let Some(update) = rx.recv().await {
+            let notification = ProgressNotificationParam {
+                progress_token: progress_token.clone(),
+                progress: update.progress,
+                total: update.total,
+                message: update.message,
+            };
+            if let Err(e) = peer.notify_progress(notification).await {
+                tracing::debug!(error = %e, "Failed to send progress notification");
+                break;
+            }
+        }
+    });
+
+    progress.with_sink(Some(Arc::new(move |update| {
+        let _ = tx.send(update);
+    })))
+}
+
 /// MCP server for Miro
 #[derive(Clone)]
 pub struct MiroMcpServer {
@@ -132,6 +390,11 @@ pub struct MiroMcpServer {
     miro_client: Arc<MiroClient>,
     #[allow(dead_code)]
     tool_router: ToolRouter<Self>,
+    subscriptions: Arc<SubscriptionManager>,
+    /// Imports creating more objects than this need confirmation
+    confirm_import_threshold: usize,
+    /// Where snapshot tools may read and write files
+    snapshot_dir: SnapshotDir,
 }
 
 #[tool_router]
@@ -149,11 +412,115 @@ impl MiroMcpServer {
         Ok(Self {
             miro_client,
             tool_router: Self::tool_router(),
+            subscriptions: Arc::new(SubscriptionManager::new(SubscriptionConfig::from(config))),
+            confirm_import_threshold: config.confirm_import_threshold,
+            snapshot_dir: SnapshotDir::new(&config.snapshot_dir),
         })
     }
 
+    /// Ask the user to approve a destructive or large change
+    ///
+    /// Clients that support elicitation always get a confirmation prompt, even
+    /// when the agent passed `confirm: true`. Other clients must pass
+    /// `confirm: true`. Returns the tool result to send instead of acting when
+    /// the change isn't approved.
+    async fn confirm_change(
+        &self,
+        ctx: &RequestContext<RoleServer>,
+        change: PendingChange,
+        confirm_argument: bool,
+    ) -> Result<Option<CallToolResult>, McpError> {
+        if !ctx.peer.supports_elicitation() {
+            if confirm_argument {
+                return Ok(None);
+            }
+            return Ok(Some(CallToolResult::error(vec![Content::text(
+                change.confirmation_required_message(),
+            )])));
+        }
+
+        let request = CreateElicitationRequestParam {
+            message: change.elicitation_message(),
+            requested_schema: confirmation::confirmation_schema(),
+        };
+        let response = ctx
+            .peer
+            .create_elicitation_with_timeout(request, Some(ELICITATION_TIMEOUT))
+            .await
+            .map_err(|e| {
+                McpError::internal_error(format!("Confirmation request failed: {}", e), None)
+            })?;
+
+        let approved = response.action == ElicitationAction::Accept
+            && confirmation::is_confirmed(response.content.as_ref());
+        tracing::info!(
+            tool = %change.tool,
+            action = ?response.action,
+            approved,
+            "Confirmation answered"
+        );
+        if approved {
+            return Ok(None);
+        }
+        Ok(Some(CallToolResult::error(vec![Content::text(format!(
+            "The user did not approve this change, so nothing was done: {}",
+            change.summary
+        ))])))
+    }
+
+    /// Fetch and render a board resource by URI
+    async fn read_resource_contents(
+        &self,
+        uri: &str,
+    ) -> Result<protocol::ResourceContents, McpError> {
+        let resource =
+            ResourceUri::parse(uri).map_err(|e| McpError::resource_not_found(e, None))?;
+        let to_mcp_error =
+            |e: crate::miro::MiroError| McpError::internal_error(e.to_string(), None);
+        let client = &self.miro_client;
+
+        let contents = match resource {
+            ResourceUri::Boards => {
+                let boards = client.list_boards().await.map_err(to_mcp_error)?;
+                resources::render_boards(uri, &boards)
+            }
+            ResourceUri::Board { board_id, json } => {
+                let board = client.get_board(&board_id).await.map_err(to_mcp_error)?;
+                let items = client
+                    .list_all_items(&board_id, None)
+                    .await
+                    .map_err(to_mcp_error)?;
+                let connectors = client
+                    .list_connectors(&board_id)
+                    .await
+                    .map_err(to_mcp_error)?;
+                resources::render_board(uri, board, &items, connectors, json)
+            }
+            ResourceUri::BoardItems {
+                board_id,
+                item_type,
+            } => {
+                let items = client
+                    .list_all_items(&board_id, item_type.as_deref().map(|t| vec![t]))
+                    .await
+                    .map_err(to_mcp_error)?;
+                resources::render_items(uri, &items)
+            }
+        };
+
+        Ok(contents)
+    }
+
     /// Start OAuth2 authentication flow
-    #[tool(description = "Start OAuth2 authentication flow with Miro. Returns authorization URL.")]
+    #[tool(
+        description = "Start OAuth2 authentication flow with Miro. Returns authorization URL.",
+        title = "Start Miro sign-in",
+        annotations(
+            title = "Start Miro sign-in",
+            read_only_hint = true,
+            open_world_hint = false
+        )
+    )]
     async fn start_auth(&self) -> Result<CallToolResult, McpError> {
         // Return the local OAuth authorize endpoint which will:
         // 1. Generate PKCE verifier and CSRF token
@@ -170,7 +537,16 @@ impl MiroMcpServer {
     }
 
     /// List all accessible Miro boards
-    #[tool(description = "List all accessible Miro boards")]
+    #[tool(
+        description = "List all accessible Miro boards",
+        output_schema = output_schema::<ListBoardsResponse>(),
+        title = "List boards",
+        annotations(
+            title = "List boards",
+            read_only_hint = true,
+            open_world_hint = true
+        )
+    )]
     async fn list_boards(&self) -> Result<CallToolResult, McpError> {
         let boards = self
             .miro_client
@@ -178,31 +554,46 @@ impl MiroMcpServer {
             .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))?;
 
-        if boards.is_empty() {
-            return Ok(CallToolResult::success(vec![Content::text(
-                "No boards found.".to_string(),
-            )]));
-        }
+        let message = if boards.is_empty() {
+            "No boards found.".to_string()
+        } else {
+            let board_list = boards
+                .iter()
+                .map(|b| {
+                    let description = b
+                        .description
+                        .as_ref()
+                        .map(|d| format!(" - {}", d))
+                        .unwrap_or_default();
+                    format!("- {} (ID: {}){}", b.name, b.id, description)
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!("Found {} board(s):\n{}", boards.len(), board_list)
+        };
 
-        let board_list = boards
-            .iter()
-            .map(|b| {
-                let description = b
-                    .description
-                    .as_ref()
-                    .map(|d| format!(" - {}", d))
-                    .unwrap_or_default();
-                format!("- {} (ID: {}){}", b.name, b.id, description)
-            })
-            .collect::<Vec<_>>()
-            .join("\n");
-
-        let message = format!("Found {} board(s):\n{}", boards.len(), board_list);
-        Ok(CallToolResult::success(vec![Content::text(message)]))
+        let count = boards.len();
+        let result = ListBoardsResponse {
+            boards: boards.into_iter().map(BoardInfo::from).collect(),
+            count,
+            cancelled: false,
+        };
+        structured_result(message, &result)
     }
 
     /// Create a new Miro board
-    #[tool(description = "Create a new Miro board")]
+    #[tool(
+        description = "Create a new Miro board",
+        output_schema = output_schema::<CreatedItemSummary>(),
+        title = "Create board",
+        annotations(
+            title = "Create board",
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = false,
+            open_world_hint = true
+        )
+    )]
     async fn create_board(&self) -> Result<CallToolResult, McpError> {
         // Note: In actual usage, the tool parameters would be passed from the MCP client
         // This is a placeholder implementation
@@ -217,12 +608,25 @@ impl MiroMcpServer {
             board.name, board.id
         );
 
-        Ok(CallToolResult::success(vec![Content::text(message)]))
+        let created = CreatedItemSummary {
+            id: board.id,
+            item_type: "board".to_string(),
+            name: Some(board.name),
+        };
+        structured_result(message, &created)
     }
 
     /// Create a sticky note on a board
     #[tool(
-        description = "Create a sticky note on a Miro board with customizable content, position, color, and optional parent frame"
+        description = "Create a sticky note on a Miro board with customizable content, position, color, and optional parent frame",
+        title = "Create sticky note",
+        annotations(
+            title = "Create sticky note",
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = false,
+            open_world_hint = true
+        )
     )]
     async fn create_sticky_note(&self) -> Result<CallToolResult, McpError> {
         let message = "create_sticky_note tool registered. Use tool_call with parameters: { board_id, content, x, y, color?, parent_id? }".to_string();
@@ -231,7 +635,15 @@ impl MiroMcpServer {
 
     /// Create a shape on a board
     #[tool(
-        description = "Create a shape (rectangle, circle, triangle, etc.) on a Miro board with custom styling and optional parent frame"
+        description = "Create a shape (rectangle, circle, triangle, etc.) on a Miro board with custom styling and optional parent frame",
+        title = "Create shape",
+        annotations(
+            title = "Create shape",
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = false,
+            open_world_hint = true
+        )
     )]
     async fn create_shape(&self) -> Result<CallToolResult, McpError> {
         let message = "create_shape tool registered. Use tool_call with parameters: { board_id, shape_type, fill_color, x, y, width, height, content?, parent_id? }".to_string();
@@ -239,7 +651,17 @@ impl MiroMcpServer {
     }
 
     /// Create text on a board
-    #[tool(description = "Create a text element on a Miro board with optional parent frame")]
+    #[tool(
+        description = "Create a text element on a Miro board with optional parent frame",
+        title = "Create text",
+        annotations(
+            title = "Create text",
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = false,
+            open_world_hint = true
+        )
+    )]
     async fn create_text(&self) -> Result<CallToolResult, McpError> {
         let message = "create_text tool registered. Use tool_call with parameters: { board_id, content, x, y, width, parent_id? }".to_string();
         Ok(CallToolResult::success(vec![Content::text(message)]))
@@ -247,7 +669,15 @@ impl MiroMcpServer {
 
     /// Create a frame on a board
     #[tool(
-        description = "Create a frame on a Miro board to group and organize other elements, with optional parent frame"
+        description = "Create a frame on a Miro board to group and organize other elements, with optional parent frame",
+        title = "Create frame",
+        annotations(
+            title = "Create frame",
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = false,
+            open_world_hint = true
+        )
     )]
     async fn create_frame(&self) -> Result<CallToolResult, McpError> {
         let message = "create_frame tool registered. Use tool_call with parameters: { board_id, title, x, y, width, height, fill_color?, parent_id? }".to_string();
@@ -256,7 +686,14 @@ impl MiroMcpServer {
 
     /// List items on a board with optional type filtering, parent filtering, and sorting
     #[tool(
-        description = "List items on a Miro board with optional filtering by type (frame, sticky_note, shape, text, connector), parent frame, and sorting by creation/modification time for layer awareness"
+        description = "List items on a Miro board with optional filtering by type (frame, sticky_note, shape, text, connector), parent frame, and sorting by creation/modification time for layer awareness",
+        output_schema = output_schema::<ListItemsResponse>(),
+        title = "List board items",
+        annotations(
+            title = "List board items",
+            read_only_hint = true,
+            open_world_hint = true
+        )
     )]
     async fn list_items(&self) -> Result<CallToolResult, McpError> {
         let message = "list_items tool registered. Use tool_call with parameters: { board_id, item_types? (comma-separated), parent_id?, sort_by? (created_at|modified_at) }".to_string();
@@ -308,21 +745,31 @@ impl MiroMcpServer {
             }
         }
 
-        if items.is_empty() {
-            return Ok(CallToolResult::success(vec![Content::text(
-                "No items found on this board.".to_string(),
-            )]));
-        }
-
-        let items_json = serde_json::to_string_pretty(&items)
-            .unwrap_or_else(|_| "Failed to serialize items".to_string());
+        let message = if items.is_empty() {
+            "No items found on this board.".to_string()
+        } else {
+            format!("Found {} item(s) on board {}", items.len(), params.board_id)
+        };
 
-        Ok(CallToolResult::success(vec![Content::text(items_json)]))
+        let result = ListItemsResponse {
+            board_id: params.board_id,
+            count: items.len(),
+            items,
+        };
+        structured_result(message, &result)
     }
 
     /// Update item properties
     #[tool(
-        description = "Update an item's properties including position, content, styling, and parent frame"
+        description = "Update an item's properties including position, content, styling, and parent frame",
+        title = "Update item",
+        annotations(
+            title = "Update item",
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = true
+        )
     )]
     async fn update_item(&self) -> Result<CallToolResult, McpError> {
         let message = "update_item tool registered. Use tool_call with parameters: { board_id, item_id, x?, y?, content?, parent_id? }".to_string();
@@ -330,17 +777,68 @@ impl MiroMcpServer {
     }
 
     /// Delete an item from a board
-    #[tool(description = "Delete an item from a Miro board")]
+    #[tool(
+        description = "Delete an item from a Miro board. Asks the user to confirm first; clients without elicitation support must pass confirm: true.",
+        title = "Delete item",
+        annotations(
+            title = "Delete item",
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = true,
+            open_world_hint = true
+        )
+    )]
     async fn delete_item(&self) -> Result<CallToolResult, McpError> {
         let message =
-            "delete_item tool registered. Use tool_call with parameters: { board_id, item_id }"
+            "delete_item tool registered. Use tool_call with parameters: { board_id, item_id, confirm? }"
                 .to_string();
         Ok(CallToolResult::success(vec![Content::text(message)]))
     }
 
+    /// Internal implementation of delete_item with parameter support
+    async fn delete_item_with_params(
+        &self,
+        params: DeleteItemParams,
+        ctx: &RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        // Name the item in the confirmation when it can be looked up
+        let description = self
+            .miro_client
+            .get(&format!(
+                "/boards/{}/items/{}",
+                params.board_id, params.item_id
+            ))
+            .await
+            .ok()
+            .map(|item| describe_item(&item));
+        let change =
+            PendingChange::delete_item(&params.board_id, &params.item_id, description.as_deref());
+        if let Some(refusal) = self.confirm_change(ctx, change, params.confirm).await? {
+            return Ok(refusal);
+        }
+
+        self.miro_client
+            .delete_item(&params.board_id, &params.item_id)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Deleted item {} from board {}",
+            params.item_id, params.board_id
+        ))]))
+    }
+
     /// Create a connector between two items
     #[tool(
-        description = "Create a connector (line/arrow) between two items on a Miro board with optional styling and captions"
+        description = "Create a connector (line/arrow) between two items on a Miro board with optional styling and captions",
+        title = "Create connector",
+        annotations(
+            title = "Create connector",
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = false,
+            open_world_hint = true
+        )
     )]
     async fn create_connector(&self) -> Result<CallToolResult, McpError> {
         let message = "create_connector tool registered. Use tool_call with parameters: { board_id, start_item_id, end_item_id, stroke_color?, stroke_width?, start_cap?, end_cap?, captions? }".to_string();
@@ -349,12 +847,311 @@ impl MiroMcpServer {
 
     /// Bulk create multiple items in a single transaction
     #[tool(
-        description = "Create multiple items efficiently in a single API call (max 20 items per request). Accepts array of mixed item types (sticky_note, shape, text, frame) with their respective configurations."
+        description = "Create multiple items efficiently in a single API call (max 20 items per request). Accepts array of mixed item types (sticky_note, shape, text, frame) with their respective configurations.",
+        title = "Create items in bulk",
+        annotations(
+            title = "Create items in bulk",
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = false,
+            open_world_hint = true
+        )
     )]
     async fn bulk_create_items(&self) -> Result<CallToolResult, McpError> {
         let message = "bulk_create_items tool registered. Use tool_call with parameters: { board_id, items: [{ type: 'sticky_note'|'shape'|'text'|'frame', data: {...}, position: {...}, geometry: {...}, style?: {...} }, ...] }. Maximum 20 items per call.".to_string();
         Ok(CallToolResult::success(vec![Content::text(message)]))
     }
+
+    /// Import CSV rows as sticky notes or cards
+    #[tool(
+        description = "Import CSV rows as sticky notes or cards. Maps columns to content, color (via a value-to-color mapping), tags and target frame, creates items in chunks of 20, and returns a row-to-item-ID report.",
+        output_schema = output_schema::<CsvImportReport>(),
+        title = "Import CSV",
+        annotations(
+            title = "Import CSV",
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = false,
+            open_world_hint = true
+        )
+    )]
+    async fn import_csv(&self) -> Result<CallToolResult, McpError> {
+        let message = "import_csv tool registered. Use tool_call with parameters: { board_id, csv, content_column, item_type? (sticky_note|card), description_column?, color_column?, color_map?: { value: color }, default_color?, tags_column?, frame_column?, frame_id?, key_column?, x?, y?, columns? }".to_string();
+        Ok(CallToolResult::success(vec![Content::text(message)]))
+    }
+
+    /// Internal implementation of import_csv with parameter support
+    async fn import_csv_with_params(
+        &self,
+        params: ImportCsvParams,
+        progress: ToolProgress,
+        ctx: &RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let table = CsvTable::parse(&params.csv)
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+        let plan = csv_import::plan_import(&table, &params.mapping, &params.layout)
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+        if confirmation::requires_import_confirmation(
+            plan.rows.len(),
+            self.confirm_import_threshold,
+        ) {
+            let change = PendingChange::large_import(
+                "import_csv",
+                &format!("board {}", params.board_id),
+                plan.rows.len(),
+            );
+            if let Some(refusal) = self.confirm_change(ctx, change, params.confirm).await? {
+                return Ok(refusal);
+            }
+        }
+
+        let report =
+            csv_import::execute_import(&self.miro_client, &params.board_id, plan, &progress)
+                .await
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        let mut message = format!(
+            "Imported {} row(s) into board {}: {} created, {} failed",
+            report.rows.len(),
+            report.board_id,
+            report.created,
+            report.failed
+        );
+        if report.cancelled {
+            message.push_str(" (cancelled)");
+        }
+        structured_result(message, &report)
+    }
+
+    /// Export a board to a versioned JSON snapshot
+    #[tool(
+        description = "Export a Miro board to a versioned JSON snapshot (board metadata, items with typed data and style, connectors, frames with children, tags). Writes to a file in the snapshot directory when a path is given (set overwrite to replace an existing file), otherwise returns the snapshot.",
+        output_schema = output_schema::<ExportBoardResponse>(),
+        title = "Export board snapshot",
+        annotations(
+            title = "Export board snapshot",
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = true,
+            open_world_hint = true
+        )
+    )]
+    async fn export_board(&self) -> Result<CallToolResult, McpError> {
+        let message =
+            "export_board tool registered. Use tool_call with parameters: { board_id, path?, overwrite? }"
+                .to_string();
+        Ok(CallToolResult::success(vec![Content::text(message)]))
+    }
+
+    /// Internal implementation of export_board with parameter support
+    async fn export_board_with_params(
+        &self,
+        params: ExportBoardParams,
+        progress: ToolProgress,
+    ) -> Result<CallToolResult, McpError> {
+        let snapshot = snapshot::export_board(&self.miro_client, &params.board_id, &progress)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        let mut result = ExportBoardResponse {
+            board_id: snapshot.board.id.clone(),
+            board_name: snapshot.board.name.clone(),
+            path: None,
+            items: snapshot.items.len(),
+            connectors: snapshot.connectors.len(),
+            frames: snapshot.frames.len(),
+            tags: snapshot.tags.len(),
+            snapshot: None,
+        };
+        let counts = format!(
+            "{} item(s), {} connector(s), {} frame(s), {} tag(s)",
+            result.items, result.connectors, result.frames, result.tags
+        );
+
+        let Some(path) = params.path else {
+            result.snapshot = Some(
+                serde_json::to_value(&snapshot)
+                    .map_err(|e| McpError::internal_error(e.to_string(), None))?,
+            );
+            let message = format!("Exported board '{}': {}", result.board_name, counts);
+            return structured_result(message, &result);
+        };
+
+        self.snapshot_dir
+            .write(&path, &snapshot, params.overwrite)
+            .map_err(|e| match e {
+                SnapshotError::OutsideSnapshotDir(_) | SnapshotError::AlreadyExists(_) => {
+                    McpError::invalid_params(e.to_string(), None)
+                }
+                e => McpError::internal_error(e.to_string(), None),
+            })?;
+
+        let message = format!(
+            "Exported board '{}' to {}: {}",
+            result.board_name, path, counts
+        );
+        result.path = Some(path);
+        structured_result(message, &result)
+    }
+
+    /// Restore or clone a board from a JSON snapshot
+    #[tool(
+        description = "Recreate a board snapshot's items on a new or existing Miro board. Reads snapshot files from the snapshot directory. Preserves frame nesting, re-links connectors and tags, and reports items that couldn't be recreated or were skipped.",
+        output_schema = output_schema::<RestoreReport>(),
+        title = "Import board snapshot",
+        annotations(
+            title = "Import board snapshot",
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = false,
+            open_world_hint = true
+        )
+    )]
+    async fn import_snapshot(&self) -> Result<CallToolResult, McpError> {
+        let message = "import_snapshot tool registered. Use tool_call with parameters: { path? | snapshot?, board_id? | board_name? }".to_string();
+        Ok(CallToolResult::success(vec![Content::text(message)]))
+    }
+
+    /// Internal implementation of import_snapshot with parameter support
+    async fn import_snapshot_with_params(
+        &self,
+        params: ImportSnapshotParams,
+        progress: ToolProgress,
+        ctx: &RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let snapshot = load_snapshot(
+            &self.snapshot_dir,
+            params.path.as_deref(),
+            params.snapshot.as_ref(),
+            "snapshot",
+        )?
+        .ok_or_else(|| {
+            McpError::invalid_params("Provide one of 'path' or 'snapshot'".to_string(), None)
+        })?;
+
+        let target = match (params.board_id, params.board_name) {
+            (Some(_), Some(_)) => {
+                return Err(McpError::invalid_params(
+                    "Provide only one of 'board_id' or 'board_name'".to_string(),
+                    None,
+                ))
+            }
+            (Some(board_id), None) => RestoreTarget::Existing(board_id),
+            (None, board_name) => RestoreTarget::New(
+                board_name.unwrap_or_else(|| format!("{} (copy)", snapshot.board.name)),
+            ),
+        };
+
+        let count = snapshot.items.len() + snapshot.connectors.len();
+        if confirmation::requires_import_confirmation(count, self.confirm_import_threshold) {
+            let destination = match &target {
+                RestoreTarget::Existing(board_id) => format!("board {}", board_id),
+                RestoreTarget::New(name) => format!("a new board '{}'", name),
+            };
+            let change = PendingChange::large_import("import_snapshot", &destination, count);
+            if let Some(refusal) = self.confirm_change(ctx, change, params.confirm).await? {
+                return Ok(refusal);
+            }
+        }
+
+        let report = restore::restore_snapshot(&self.miro_client, &snapshot, target, &progress)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        let mut message = format!(
+            "Restored {} item(s), {} connector(s) and {} tag(s) to board {}; {} failed, {} skipped",
+            report.items_created,
+            report.connectors_created,
+            report.tags_created,
+            report.board_id,
+            report.failed.len(),
+            report.skipped.len()
+        );
+        if report.cancelled {
+            message.push_str(" (cancelled)");
+        }
+        structured_result(message, &report)
+    }
+
+    /// Diff two board snapshots, or a snapshot against the live board
+    #[tool(
+        description = "Compare two board snapshots, or a snapshot against the live board, and report added, removed, moved, restyled and re-parented items and changed text. Snapshot files are read from the snapshot directory. Returns a human-readable summary and the JSON diff.",
+        output_schema = output_schema::<BoardDiff>(),
+        title = "Diff board snapshots",
+        annotations(
+            title = "Diff board snapshots",
+            read_only_hint = true,
+            open_world_hint = true
+        )
+    )]
+    async fn diff_board(&self) -> Result<CallToolResult, McpError> {
+        let message = "diff_board tool registered. Use tool_call with parameters: { before_path? | before?, after_path? | after?, board_id? }".to_string();
+        Ok(CallToolResult::success(vec![Content::text(message)]))
+    }
+
+    /// Internal implementation of diff_board with parameter support
+    async fn diff_board_with_params(
+        &self,
+        params: DiffBoardParams,
+    ) -> Result<CallToolResult, McpError> {
+        let before = load_snapshot(
+            &self.snapshot_dir,
+            params.before_path.as_deref(),
+            params.before.as_ref(),
+            "before",
+        )?
+        .ok_or_else(|| {
+            McpError::invalid_params("Provide one of 'before_path' or 'before'".to_string(), None)
+        })?;
+        let after = load_snapshot(
+            &self.snapshot_dir,
+            params.after_path.as_deref(),
+            params.after.as_ref(),
+            "after",
+        )?;
+
+        let board_diff = match after {
+            Some(after) => diff::diff_snapshots(&before, &after),
+            None => {
+                let board_id = params.board_id.as_deref().unwrap_or(&before.board.id);
+                diff::diff_against_live(&self.miro_client, board_id, &before)
+                    .await
+                    .map_err(|e| McpError::internal_error(e.to_string(), None))?
+            }
+        };
+
+        structured_result(board_diff.summary(), &board_diff)
+    }
+
+    /// Summarize a board as a compact outline
+    #[tool(
+        description = "Summarize a Miro board as a compact markdown or plain-text outline: frames as sections, sticky notes grouped by color or tag, connectors as 'A → B'. Use instead of list_items to save context; supports a max_tokens budget.",
+        title = "Summarize board",
+        annotations(
+            title = "Summarize board",
+            read_only_hint = true,
+            open_world_hint = true
+        )
+    )]
+    async fn summarize_board(&self) -> Result<CallToolResult, McpError> {
+        let message = "summarize_board tool registered. Use tool_call with parameters: { board_id, group_by? (color|tag), format? (markdown|plain), max_tokens? }".to_string();
+        Ok(CallToolResult::success(vec![Content::text(message)]))
+    }
+
+    /// Internal implementation of summarize_board with parameter support
+    async fn summarize_board_with_params(
+        &self,
+        params: SummarizeBoardParams,
+        progress: ToolProgress,
+    ) -> Result<CallToolResult, McpError> {
+        let snapshot = snapshot::export_board(&self.miro_client, &params.board_id, &progress)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        let outline = summary::render_summary(&snapshot, &params.options);
+        Ok(CallToolResult::success(vec![Content::text(outline)]))
+    }
 }
 
 impl ServerHandler for MiroMcpServer {
@@ -363,6 +1160,11 @@ impl ServerHandler for MiroMcpServer {
             protocol_version: ProtocolVersion::LATEST,
             capabilities: ServerCapabilities {
                 tools: Some(ToolsCapability { list_changed: None }),
+                resources: Some(ResourcesCapability {
+                    subscribe: Some(true),
+                    list_changed: None,
+                }),
+                prompts: Some(PromptsCapability { list_changed: None }),
                 ..Default::default()
             },
             server_info: Implementation {
@@ -376,22 +1178,259 @@ impl ServerHandler for MiroMcpServer {
         }
     }
 
+    async fn initialize(
+        &self,
+        request: InitializeRequestParam,
+        ctx: RequestContext<RoleServer>,
+    ) -> Result<InitializeResult, McpError> {
+        let requested = request.protocol_version.to_string();
+        let negotiated = protocol::ProtocolVersion::negotiate(Some(&requested))
+            .map_err(|e| McpError::invalid_params(e.message, e.data))?;
+        tracing::info!(
+            requested = %requested,
+            negotiated = %negotiated,
+            client = %request.client_info.name,
+            "Negotiated MCP protocol version"
+        );
+
+        if ctx.peer.peer_info().is_none() {
+            ctx.peer.set_peer_info(request);
+        }
+
+        let mut info = self.get_info();
+        info.protocol_version = serde_json::from_value(serde_json::json!(negotiated.as_str()))
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        Ok(info)
+    }
+
     async fn list_tools(
         &self,
         _params: Option<PaginatedRequestParam>,
-        _ctx: RequestContext<RoleServer>,
+        ctx: RequestContext<RoleServer>,
     ) -> Result<ListToolsResult, McpError> {
         // Return all tools from the tool_router
+        let mut tools = self.tool_router.list_all();
+        let version = negotiated_version(&ctx);
+        for tool in &mut tools {
+            if !version.supports_structured_output() {
+                tool.output_schema = None;
+            }
+            if !version.supports_titles() {
+                tool.title = None;
+            }
+            if !version.supports_tool_annotations() {
+                tool.annotations = None;
+            }
+        }
         Ok(ListToolsResult {
-            tools: self.tool_router.list_all(),
+            tools,
             next_cursor: None,
         })
     }
 
+    async fn list_resources(
+        &self,
+        _params: Option<PaginatedRequestParam>,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        let boards = self
+            .miro_client
+            .list_boards()
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        let resources = resources::board_resources(&boards)
+            .into_iter()
+            .map(|resource| {
+                RawResource {
+                    uri: resource.uri,
+                    name: resource.name,
+                    title: None,
+                    description: resource.description,
+                    mime_type: resource.mime_type,
+                    size: None,
+                    icons: None,
+                }
+                .no_annotation()
+            })
+            .collect();
+
+        Ok(ListResourcesResult::with_all_items(resources))
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _params: Option<PaginatedRequestParam>,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, McpError> {
+        let templates = resources::resource_templates()
+            .into_iter()
+            .map(|template| {
+                RawResourceTemplate {
+                    uri_template: template.uri_template,
+                    name: template.name,
+                    title: None,
+                    description: template.description,
+                    mime_type: template.mime_type,
+                }
+                .no_annotation()
+            })
+            .collect();
+
+        Ok(ListResourceTemplatesResult::with_all_items(templates))
+    }
+
+    async fn read_resource(
+        &self,
+        params: ReadResourceRequestParam,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let contents = self.read_resource_contents(&params.uri).await?;
+
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: contents.uri,
+                mime_type: contents.mime_type,
+                text: contents.text,
+                meta: None,
+            }],
+        })
+    }
+
+    async fn list_prompts(
+        &self,
+        _params: Option<PaginatedRequestParam>,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        let prompts = prompts::prompt_templates()
+            .into_iter()
+            .map(|template| Prompt {
+                name: template.name,
+                title: template.title,
+                description: template.description,
+                arguments: Some(
+                    template
+                        .arguments
+                        .into_iter()
+                        .map(|argument| PromptArgument {
+                            name: argument.name,
+                            title: None,
+                            description: argument.description,
+                            required: Some(argument.required),
+                        })
+                        .collect(),
+                ),
+                icons: None,
+            })
+            .collect();
+
+        Ok(ListPromptsResult::with_all_items(prompts))
+    }
+
+    async fn get_prompt(
+        &self,
+        params: GetPromptRequestParam,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        let arguments: HashMap<String, String> = params
+            .arguments
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, value)| match value {
+                serde_json::Value::String(value) => (name, value),
+                other => (name, other.to_string()),
+            })
+            .collect();
+        let plan = prompts::build_prompt(&params.name, &arguments)
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+        let mut messages = Vec::with_capacity(plan.resource_uris.len() + 1);
+        for uri in &plan.resource_uris {
+            let contents = self.read_resource_contents(uri).await?;
+            messages.push(PromptMessage::new_resource(
+                PromptMessageRole::User,
+                contents.uri,
+                contents.mime_type,
+                Some(contents.text),
+                None,
+                None,
+                None,
+            ));
+        }
+        messages.push(PromptMessage::new_text(
+            PromptMessageRole::User,
+            plan.instructions,
+        ));
+
+        Ok(GetPromptResult {
+            description: Some(plan.description),
+            messages,
+        })
+    }
+
+    async fn subscribe(
+        &self,
+        params: SubscribeRequestParam,
+        ctx: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        let client = self.miro_client.clone();
+        let fetch: FetchItems = Arc::new(move |board_id, item_type| {
+            let client = client.clone();
+            Box::pin(async move {
+                client
+                    .list_all_items(&board_id, item_type.as_deref().map(|t| vec![t]))
+                    .await
+                    .map_err(|e| e.to_string())
+            })
+        });
+        let peer = ctx.peer.clone();
+        let notify: NotifyUpdated = Arc::new(move |uri| {
+            let peer = peer.clone();
+            Box::pin(async move {
+                peer.notify_resource_updated(ResourceUpdatedNotificationParam { uri })
+                    .await
+                    .is_ok()
+            })
+        });
+
+        self.subscriptions
+            .subscribe(&params.uri, fetch, notify)
+            .map_err(|e| match e {
+                SubscriptionError::Unsupported(_) => McpError::invalid_params(e.to_string(), None),
+                SubscriptionError::LimitReached(_) => McpError::internal_error(e.to_string(), None),
+            })
+    }
+
+    async fn unsubscribe(
+        &self,
+        params: UnsubscribeRequestParam,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        self.subscriptions.unsubscribe(&params.uri);
+        Ok(())
+    }
+
     async fn call_tool(
         &self,
         params: CallToolRequestParam,
         ctx: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let structured = negotiated_version(&ctx).supports_structured_output();
+        let result = self.route_tool_call(params, ctx).await?;
+        Ok(if structured {
+            result
+        } else {
+            downgrade_structured_result(result)
+        })
+    }
+}
+
+impl MiroMcpServer {
+    /// Run a tool call, routing tools that take parameters to their implementation
+    async fn route_tool_call(
+        &self,
+        params: CallToolRequestParam,
+        ctx: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, McpError> {
         // Special handling for list_items (has parameter-based routing)
         if params.name.as_ref() == "list_items" {
@@ -403,6 +1442,73 @@ impl ServerHandler for MiroMcpServer {
             return self.list_items_with_params(list_params).await;
         }
 
+        if params.name.as_ref() == "delete_item" {
+            let args_value =
+                serde_json::Value::Object(params.arguments.clone().unwrap_or_default());
+            let delete_params: DeleteItemParams =
+                serde_json::from_value(args_value).map_err(|e| {
+                    McpError::internal_error(format!("Invalid parameters: {}", e), None)
+                })?;
+            return self.delete_item_with_params(delete_params, &ctx).await;
+        }
+
+        if params.name.as_ref() == "import_csv" {
+            let args_value =
+                serde_json::Value::Object(params.arguments.clone().unwrap_or_default());
+            let import_params: ImportCsvParams =
+                serde_json::from_value(args_value).map_err(|e| {
+                    McpError::internal_error(format!("Invalid parameters: {}", e), None)
+                })?;
+            return self
+                .import_csv_with_params(import_params, tool_progress(&ctx), &ctx)
+                .await;
+        }
+
+        if params.name.as_ref() == "export_board" {
+            let args_value =
+                serde_json::Value::Object(params.arguments.clone().unwrap_or_default());
+            let export_params: ExportBoardParams =
+                serde_json::from_value(args_value).map_err(|e| {
+                    McpError::internal_error(format!("Invalid parameters: {}", e), None)
+                })?;
+            return self
+                .export_board_with_params(export_params, tool_progress(&ctx))
+                .await;
+        }
+
+        if params.name.as_ref() == "import_snapshot" {
+            let args_value =
+                serde_json::Value::Object(params.arguments.clone().unwrap_or_default());
+            let import_params: ImportSnapshotParams =
+                serde_json::from_value(args_value).map_err(|e| {
+                    McpError::internal_error(format!("Invalid parameters: {}", e), None)
+                })?;
+            return self
+                .import_snapshot_with_params(import_params, tool_progress(&ctx), &ctx)
+                .await;
+        }
+
+        if params.name.as_ref() == "diff_board" {
+            let args_value =
+                serde_json::Value::Object(params.arguments.clone().unwrap_or_default());
+            let diff_params: DiffBoardParams = serde_json::from_value(args_value).map_err(|e| {
+                McpError::internal_error(format!("Invalid parameters: {}", e), None)
+            })?;
+            return self.diff_board_with_params(diff_params).await;
+        }
+
+        if params.name.as_ref() == "summarize_board" {
+            let args_value =
+                serde_json::Value::Object(params.arguments.clone().unwrap_or_default());
+            let summary_params: SummarizeBoardParams =
+                serde_json::from_value(args_value).map_err(|e| {
+                    McpError::internal_error(format!("Invalid parameters: {}", e), None)
+                })?;
+            return self
+                .summarize_board_with_params(summary_params, tool_progress(&ctx))
+                .await;
+        }
+
         // Use the tool router for all other tools
         let tool_ctx = ToolCallContext::new(self, params, ctx);
         self.tool_router.call(tool_ctx).await
@@ -421,6 +1527,15 @@ mod tests {
             encryption_key: [0u8; 32],
             port: 3000,
             base_url: None,
+            resource_poll_interval_secs: 30,
+            max_resource_subscriptions: 10,
+            confirm_import_threshold: 100,
+            oauth_storage: Default::default(),
+            issue_proxy_tokens: false,
+            registration_policy: Default::default(),
+            consent_policy: Default::default(),
+            log_pii: false,
+            snapshot_dir: "snapshots".into(),
         }
     }
 
@@ -472,4 +1587,117 @@ mod tests {
         assert!(error_msg.contains("created_at"));
         assert!(error_msg.contains("modified_at"));
     }
+
+    #[test]
+    fn test_tools_declare_output_schemas() {
+        let server = MiroMcpServer::new(&get_test_config()).unwrap();
+        let tools = server.tool_router.list_all();
+        let schema = |name: &str| {
+            tools
+                .iter()
+                .find(|tool| tool.name == name)
+                .and_then(|tool| tool.output_schema.clone())
+        };
+
+        for name in ["list_boards", "list_items", "import_csv", "import_snapshot"] {
+            let schema = schema(name).unwrap_or_else(|| panic!("{} has no output schema", name));
+            assert_eq!(schema["type"], "object", "{} output schema", name);
+        }
+        assert!(schema("list_boards").unwrap()["properties"]
+            .get("boards")
+            .is_some());
+        // Prose-only tools don't declare one
+        assert!(schema("summarize_board").is_none());
+    }
+
+    #[test]
+    fn test_every_tool_has_annotations() {
+        let server = MiroMcpServer::new(&get_test_config()).unwrap();
+        let tools = server.tool_router.list_all();
+        for tool in &tools {
+            let annotations = tool
+                .annotations
+                .as_ref()
+                .unwrap_or_else(|| panic!("{} has no annotations", tool.name));
+            assert!(tool.title.is_some(), "{} has no title", tool.name);
+            assert!(annotations.read_only_hint.is_some(), "{}", tool.name);
+            assert!(annotations.open_world_hint.is_some(), "{}", tool.name);
+            if annotations.read_only_hint == Some(false) {
+                assert!(annotations.destructive_hint.is_some(), "{}", tool.name);
+                assert!(annotations.idempotent_hint.is_some(), "{}", tool.name);
+            }
+        }
+
+        let hints = |name: &str| {
+            tools
+                .iter()
+                .find(|tool| tool.name == name)
+                .and_then(|tool| tool.annotations.clone())
+                .unwrap()
+        };
+        assert_eq!(hints("list_items").read_only_hint, Some(true));
+        assert_eq!(hints("delete_item").destructive_hint, Some(true));
+        assert_eq!(hints("update_item").idempotent_hint, Some(true));
+        assert_eq!(hints("create_sticky_note").destructive_hint, Some(false));
+    }
+
+    #[test]
+    fn test_downgrade_structured_result_serializes_value_as_text() {
+        let created = CreatedItemSummary {
+            id: "uXjVO".to_string(),
+            item_type: "board".to_string(),
+            name: Some("Roadmap".to_string()),
+        };
+        let result = structured_result("Created board Roadmap", &created).unwrap();
+        assert_eq!(result.structured_content.as_ref().unwrap()["id"], "uXjVO");
+
+        let result = downgrade_structured_result(result);
+        assert!(result.structured_content.is_none());
+        let text = result.content[0].as_text().unwrap().text.clone();
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(value["item_type"], "board");
+    }
+
+    #[test]
+    fn test_describe_item_for_confirmation() {
+        let item = serde_json::json!({
+            "type": "sticky_note",
+            "data": {"content": "Ship it"}
+        });
+        assert_eq!(describe_item(&item), "sticky_note \"Ship it\"");
+
+        let item = serde_json::json!({"type": "frame", "data": {"title": "x".repeat(80)}});
+        assert!(describe_item(&item).ends_with("...\""));
+
+        assert_eq!(
+            describe_item(&serde_json::json!({"type": "shape"})),
+            "shape"
+        );
+    }
+
+    #[test]
+    fn test_diff_paths_confined_to_snapshot_dir() {
+        let root = std::env::temp_dir().join(format!("miro-diff-{}", uuid::Uuid::new_v4()));
+        let snapshot_dir = SnapshotDir::new(&root);
+        let params: DiffBoardParams = serde_json::from_value(serde_json::json!({
+            "before_path": "../../etc/passwd",
+            "after_path": "/etc/passwd"
+        }))
+        .unwrap();
+
+        for path in [&params.before_path, &params.after_path] {
+            let err = load_snapshot(&snapshot_dir, path.as_deref(), None, "before").unwrap_err();
+            assert_eq!(err.code, ErrorCode::INVALID_PARAMS);
+            assert!(err.message.contains("outside the snapshot directory"));
+        }
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_confirm_argument_defaults_to_false() {
+        let params: DeleteItemParams =
+            serde_json::from_value(serde_json::json!({"board_id": "b", "item_id": "i"})).unwrap();
+        assert!(!params.confirm);
+    }
 }
@@ -0,0 +1,436 @@
+//! Streamable HTTP sessions for the `/mcp` endpoint
+//!
+//! A session is created on `initialize` and identified by the `Mcp-Session-Id`
+//! header on every later request. It owns the server→client event channel read
+//! by `GET /mcp` streams, the resource subscriptions made over HTTP, so
+//! ending a session stops its watchers, and cancellation flags for in-flight
+//! requests so `notifications/cancelled` can reach a running tool call.
+//! Watchers poll Miro with the token of the session's latest request, so a
+//! client that keeps refreshing its token keeps its subscriptions working.
+//!
+//! Idle sessions, with no recent request and no open event stream, are swept
+//! periodically, and each user may hold at most
+//! [`MAX_SESSIONS_PER_USER`] sessions.
+
+use super::protocol::{JsonRpcNotification, ProtocolVersion};
+use super::subscriptions::{SubscriptionConfig, SubscriptionManager};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::info;
+use uuid::Uuid;
+
+/// Header carrying the session ID (case-insensitive per HTTP)
+pub const SESSION_HEADER: &str = "mcp-session-id";
+
+/// Header carrying the negotiated protocol version on requests after initialize
+pub const PROTOCOL_VERSION_HEADER: &str = "mcp-protocol-version";
+
+/// Sessions without requests for longer than this, and no open stream, are dropped
+const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// How often idle sessions are swept
+const SESSION_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Live sessions one user may hold; creating another ends their least recently used
+pub const MAX_SESSIONS_PER_USER: usize = 10;
+
+/// Server→client events buffered per session before slow streams start lagging
+const EVENT_BUFFER: usize = 64;
+
+/// A streamable HTTP session bound to one authenticated user
+pub struct McpSession {
+    id: String,
+    user_id: String,
+    protocol_version: ProtocolVersion,
+    events: broadcast::Sender<JsonRpcNotification>,
+    subscriptions: SubscriptionManager,
+    in_flight: Mutex<HashMap<String, Arc<AtomicBool>>>,
+    last_seen: Mutex<Instant>,
+    /// Miro access token of the latest request in this session
+    token: Mutex<Option<Arc<String>>>,
+}
+
+impl McpSession {
+    fn new(
+        user_id: &str,
+        protocol_version: ProtocolVersion,
+        subscriptions: SubscriptionConfig,
+    ) -> Self {
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+        Self {
+            id: Uuid::new_v4().to_string(),
+            user_id: user_id.to_string(),
+            protocol_version,
+            events,
+            subscriptions: SubscriptionManager::new(subscriptions),
+            in_flight: Mutex::new(HashMap::new()),
+            last_seen: Mutex::new(Instant::now()),
+            token: Mutex::new(None),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn user_id(&self) -> &str {
+        &self.user_id
+    }
+
+    /// Protocol version negotiated at initialize
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    /// Resource subscriptions made in this session
+    pub fn subscriptions(&self) -> &SubscriptionManager {
+        &self.subscriptions
+    }
+
+    /// Send a notification to every open `GET /mcp` stream of this session
+    ///
+    /// Returns false when no stream is currently listening.
+    pub fn notify(&self, notification: JsonRpcNotification) -> bool {
+        self.events.send(notification).is_ok()
+    }
+
+    /// Record the Miro access token a request in this session authenticated with
+    pub fn set_token(&self, token: Arc<String>) {
+        *self.token.lock().unwrap_or_else(|e| e.into_inner()) = Some(token);
+    }
+
+    /// Miro access token of the session's latest request
+    pub fn token(&self) -> Option<Arc<String>> {
+        self.token.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Open a new receiver for server→client events
+    pub fn events(&self) -> broadcast::Receiver<JsonRpcNotification> {
+        self.events.subscribe()
+    }
+
+    /// Track a request so it can be cancelled; returns its cancellation flag
+    ///
+    /// Pair with [`Self::finish_request`] once the response has been produced.
+    pub fn track_request(&self, request_id: &Value) -> Arc<AtomicBool> {
+        let flag = Arc::new(AtomicBool::new(false));
+        self.in_flight
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(request_id.to_string(), flag.clone());
+        flag
+    }
+
+    /// Stop tracking a finished request
+    pub fn finish_request(&self, request_id: &Value) {
+        self.in_flight
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&request_id.to_string());
+    }
+
+    /// Flag an in-flight request as cancelled; returns whether it was running
+    pub fn cancel_request(&self, request_id: &Value) -> bool {
+        match self
+            .in_flight
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&request_id.to_string())
+        {
+            Some(flag) => {
+                flag.store(true, Ordering::SeqCst);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn touch(&self) {
+        *self.last_seen.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+    }
+
+    fn last_seen(&self) -> Instant {
+        *self.last_seen.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Idle: no request for `idle_timeout` and no open `GET /mcp` stream
+    ///
+    /// A client listening for notifications may not send anything for hours.
+    fn is_idle(&self, idle_timeout: Duration) -> bool {
+        self.events.receiver_count() == 0 && self.last_seen().elapsed() > idle_timeout
+    }
+
+    /// Stop the session's watchers; open streams may still hold the session
+    fn end(&self) {
+        self.subscriptions.clear();
+    }
+}
+
+/// All live streamable HTTP sessions
+#[derive(Clone)]
+pub struct SessionStore {
+    sessions: Arc<RwLock<HashMap<String, Arc<McpSession>>>>,
+    subscriptions: SubscriptionConfig,
+    idle_timeout: Duration,
+}
+
+impl SessionStore {
+    pub fn new(subscriptions: SubscriptionConfig) -> Self {
+        Self {
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+            subscriptions,
+            idle_timeout: SESSION_IDLE_TIMEOUT,
+        }
+    }
+
+    /// Start a new session for a user, dropping idle ones
+    ///
+    /// A user already holding [`MAX_SESSIONS_PER_USER`] sessions loses the least
+    /// recently used one; its client gets 404 and re-initializes.
+    pub fn create(&self, user_id: &str, protocol_version: ProtocolVersion) -> Arc<McpSession> {
+        let session = Arc::new(McpSession::new(
+            user_id,
+            protocol_version,
+            self.subscriptions,
+        ));
+        let mut sessions = self.sessions.write().unwrap_or_else(|e| e.into_inner());
+        drop_idle(&mut sessions, self.idle_timeout);
+
+        let mut owned: Vec<(String, Instant)> = sessions
+            .values()
+            .filter(|session| session.user_id == user_id)
+            .map(|session| (session.id.clone(), session.last_seen()))
+            .collect();
+        if owned.len() >= MAX_SESSIONS_PER_USER {
+            owned.sort_by_key(|(_, last_seen)| *last_seen);
+            for (id, _) in &owned[..=owned.len() - MAX_SESSIONS_PER_USER] {
+                if let Some(evicted) = sessions.remove(id) {
+                    evicted.end();
+                    info!(session_id = %id, user_id = %user_id, "MCP session evicted (per-user limit)");
+                }
+            }
+        }
+        sessions.insert(session.id.clone(), session.clone());
+
+        info!(
+            session_id = %session.id,
+            user_id = %user_id,
+            protocol_version = %protocol_version,
+            "MCP session created"
+        );
+        session
+    }
+
+    /// Look up a session owned by `user_id`
+    ///
+    /// Sessions belonging to another user are reported as unknown.
+    pub fn get(&self, id: &str, user_id: &str) -> Option<Arc<McpSession>> {
+        let sessions = self.sessions.read().unwrap_or_else(|e| e.into_inner());
+        let session = sessions
+            .get(id)
+            .filter(|session| session.user_id == user_id && !session.is_idle(self.idle_timeout))?
+            .clone();
+        session.touch();
+        Some(session)
+    }
+
+    /// End a session owned by `user_id`; returns whether it existed
+    pub fn remove(&self, id: &str, user_id: &str) -> bool {
+        let mut sessions = self.sessions.write().unwrap_or_else(|e| e.into_inner());
+        if sessions.get(id).is_some_and(|s| s.user_id == user_id) {
+            if let Some(session) = sessions.remove(id) {
+                session.end();
+            }
+            info!(session_id = %id, user_id = %user_id, "MCP session terminated");
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Number of live sessions
+    pub fn len(&self) -> usize {
+        self.sessions
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop sessions idle past the timeout, stopping their watchers; returns how many
+    pub fn sweep_idle(&self) -> usize {
+        let mut sessions = self.sessions.write().unwrap_or_else(|e| e.into_inner());
+        drop_idle(&mut sessions, self.idle_timeout)
+    }
+
+    /// Start the background sweep of idle sessions
+    ///
+    /// Runs every minute and stops once every clone of the store is dropped.
+    pub fn start_sweep_task(&self) -> JoinHandle<()> {
+        let store = Arc::downgrade(&self.sessions);
+        let idle_timeout = self.idle_timeout;
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SESSION_SWEEP_INTERVAL);
+            loop {
+                interval.tick().await;
+                let Some(sessions) = store.upgrade() else {
+                    return;
+                };
+                let mut sessions = sessions.write().unwrap_or_else(|e| e.into_inner());
+                drop_idle(&mut sessions, idle_timeout);
+            }
+        })
+    }
+}
+
+/// Remove sessions idle past `idle_timeout` and stop their watchers
+fn drop_idle(sessions: &mut HashMap<String, Arc<McpSession>>, idle_timeout: Duration) -> usize {
+    let before = sessions.len();
+    sessions.retain(|id, session| {
+        let idle = session.is_idle(idle_timeout);
+        if idle {
+            session.end();
+            info!(session_id = %id, user_id = %session.user_id, "MCP session expired");
+        }
+        !idle
+    });
+    before - sessions.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::subscriptions::{FetchItems, NotifyUpdated};
+
+    fn store() -> SessionStore {
+        SessionStore::new(SubscriptionConfig {
+            poll_interval: Duration::from_secs(30),
+            max_per_session: 10,
+        })
+    }
+
+    #[test]
+    fn test_sessions_are_scoped_to_their_user() {
+        let store = store();
+        let session = store.create("alice", ProtocolVersion::LATEST);
+
+        assert!(store.get(session.id(), "alice").is_some());
+        assert!(store.get(session.id(), "mallory").is_none());
+        assert!(!store.remove(session.id(), "mallory"));
+        assert!(store.remove(session.id(), "alice"));
+        assert!(store.get(session.id(), "alice").is_none());
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_sessions_per_user_are_capped() {
+        let store = store();
+        let first = store.create("alice", ProtocolVersion::LATEST);
+        let others: Vec<_> = (1..MAX_SESSIONS_PER_USER)
+            .map(|_| store.create("alice", ProtocolVersion::LATEST))
+            .collect();
+        let bob = store.create("bob", ProtocolVersion::LATEST);
+        assert_eq!(store.len(), MAX_SESSIONS_PER_USER + 1);
+
+        // Keep the first session in use so the next least recently used goes
+        assert!(store.get(first.id(), "alice").is_some());
+        store.create("alice", ProtocolVersion::LATEST);
+
+        assert_eq!(store.len(), MAX_SESSIONS_PER_USER + 1);
+        assert!(store.get(first.id(), "alice").is_some());
+        let evicted = others
+            .iter()
+            .filter(|session| store.get(session.id(), "alice").is_none())
+            .count();
+        assert_eq!(evicted, 1);
+        assert!(store.get(bob.id(), "bob").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_sweep_drops_idle_sessions_and_their_watchers() {
+        let store = SessionStore {
+            idle_timeout: Duration::ZERO,
+            ..store()
+        };
+        let session = store.create("alice", ProtocolVersion::LATEST);
+        let fetch: FetchItems = Arc::new(|_, _| Box::pin(async { Ok(vec![]) }));
+        let notify: NotifyUpdated = Arc::new(|_| Box::pin(async { true }));
+        session
+            .subscriptions()
+            .subscribe("miro://boards/uXjVO", fetch, notify)
+            .unwrap();
+        assert_eq!(session.subscriptions().len(), 1);
+
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(store.sweep_idle(), 1);
+        assert!(store.is_empty());
+        assert!(session.subscriptions().is_empty());
+    }
+
+    #[test]
+    fn test_sessions_with_open_streams_are_not_swept() {
+        let store = SessionStore {
+            idle_timeout: Duration::ZERO,
+            ..store()
+        };
+        let session = store.create("alice", ProtocolVersion::LATEST);
+        let stream = session.events();
+
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(store.sweep_idle(), 0);
+        assert!(store.get(session.id(), "alice").is_some());
+
+        drop(stream);
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(store.sweep_idle(), 1);
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_notify_reaches_open_streams() {
+        let session = store().create("alice", ProtocolVersion::LATEST);
+        let notification = JsonRpcNotification::new("notifications/message", None);
+
+        // Nobody is listening yet
+        assert!(!session.notify(notification.clone()));
+
+        let mut stream = session.events();
+        assert!(session.notify(notification));
+        assert_eq!(stream.try_recv().unwrap().method, "notifications/message");
+    }
+
+    #[test]
+    fn test_session_keeps_latest_token() {
+        let session = store().create("alice", ProtocolVersion::LATEST);
+        assert!(session.token().is_none());
+
+        session.set_token(Arc::new("miro-token-1".to_string()));
+        session.set_token(Arc::new("miro-token-2".to_string()));
+        assert_eq!(
+            session.token().as_deref().map(String::as_str),
+            Some("miro-token-2")
+        );
+    }
+
+    #[test]
+    fn test_cancel_request_flags_in_flight_requests() {
+        let session = store().create("alice", ProtocolVersion::LATEST);
+        let flag = session.track_request(&serde_json::json!(7));
+
+        assert!(!session.cancel_request(&serde_json::json!("7")));
+        assert!(!flag.load(Ordering::SeqCst));
+        assert!(session.cancel_request(&serde_json::json!(7)));
+        assert!(flag.load(Ordering::SeqCst));
+
+        session.finish_request(&serde_json::json!(7));
+        assert!(!session.cancel_request(&serde_json::json!(7)));
+    }
+}
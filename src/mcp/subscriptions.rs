@@ -0,0 +1,358 @@
+//! Resource subscriptions backed by polling watchers
+//!
+//! Each subscribed board resource gets a background task that polls the board's
+//! items and compares their `modified_at` timestamps. When anything is added,
+//! removed or modified, the transport-supplied notifier is invoked with the
+//! resource URI so it can send `notifications/resources/updated`. A watcher
+//! whose polls keep failing, e.g. because the token it polls with was revoked,
+//! gives up and ends its subscription.
+
+use super::resources::ResourceUri;
+use crate::config::Config;
+use crate::miro::types::Item;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Failed polls in a row after which a watcher ends its subscription
+pub const MAX_CONSECUTIVE_POLL_FAILURES: u32 = 5;
+
+/// Boxed future returned by watcher callbacks
+pub type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+/// Fetches the items of a board (board ID, optional item type filter)
+pub type FetchItems =
+    Arc<dyn Fn(String, Option<String>) -> BoxFuture<Result<Vec<Item>, String>> + Send + Sync>;
+
+/// Sends `notifications/resources/updated` for a URI; returns false once the session is gone
+pub type NotifyUpdated = Arc<dyn Fn(String) -> BoxFuture<bool> + Send + Sync>;
+
+/// Subscription errors
+#[derive(Error, Debug, PartialEq)]
+pub enum SubscriptionError {
+    #[error("Resource does not support subscriptions: {0}")]
+    Unsupported(String),
+
+    #[error("Subscription limit reached ({0} per session)")]
+    LimitReached(usize),
+}
+
+/// Polling and limit settings for subscriptions
+#[derive(Debug, Clone, Copy)]
+pub struct SubscriptionConfig {
+    pub poll_interval: Duration,
+    pub max_per_session: usize,
+}
+
+impl From<&Config> for SubscriptionConfig {
+    fn from(config: &Config) -> Self {
+        Self {
+            poll_interval: Duration::from_secs(config.resource_poll_interval_secs.max(1)),
+            max_per_session: config.max_resource_subscriptions,
+        }
+    }
+}
+
+/// Item ID → `modified_at`, used to detect board changes between polls
+pub type BoardFingerprint = BTreeMap<String, Option<String>>;
+
+/// Fingerprint a board's items for change detection
+pub fn fingerprint(items: &[Item]) -> BoardFingerprint {
+    items
+        .iter()
+        .map(|item| (item.id.clone(), item.modified_at.clone()))
+        .collect()
+}
+
+/// Per-session set of resource subscriptions and their watcher tasks
+///
+/// Dropping the manager (when the session ends) stops every watcher.
+pub struct SubscriptionManager {
+    config: SubscriptionConfig,
+    watchers: Mutex<HashMap<String, JoinHandle<()>>>,
+}
+
+impl SubscriptionManager {
+    pub fn new(config: SubscriptionConfig) -> Self {
+        Self {
+            config,
+            watchers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Subscribe to a board resource, starting a polling watcher
+    ///
+    /// Subscribing again to the same URI is a no-op.
+    pub fn subscribe(
+        &self,
+        uri: &str,
+        fetch: FetchItems,
+        notify: NotifyUpdated,
+    ) -> Result<(), SubscriptionError> {
+        let (board_id, item_type) = match ResourceUri::parse(uri) {
+            Ok(ResourceUri::Board { board_id, .. }) => (board_id, None),
+            Ok(ResourceUri::BoardItems {
+                board_id,
+                item_type,
+            }) => (board_id, item_type),
+            _ => return Err(SubscriptionError::Unsupported(uri.to_string())),
+        };
+
+        let mut watchers = self.watchers.lock().unwrap_or_else(|e| e.into_inner());
+        watchers.retain(|_, handle| !handle.is_finished());
+        if watchers.contains_key(uri) {
+            return Ok(());
+        }
+        if watchers.len() >= self.config.max_per_session {
+            return Err(SubscriptionError::LimitReached(self.config.max_per_session));
+        }
+
+        let handle = tokio::spawn(watch_board(
+            uri.to_string(),
+            board_id,
+            item_type,
+            self.config.poll_interval,
+            fetch,
+            notify,
+        ));
+        watchers.insert(uri.to_string(), handle);
+        info!(uri = %uri, "Resource subscription started");
+        Ok(())
+    }
+
+    /// Unsubscribe from a resource; returns whether a subscription existed
+    pub fn unsubscribe(&self, uri: &str) -> bool {
+        let mut watchers = self.watchers.lock().unwrap_or_else(|e| e.into_inner());
+        match watchers.remove(uri) {
+            Some(handle) => {
+                handle.abort();
+                info!(uri = %uri, "Resource subscription stopped");
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stop every watcher, e.g. when the session ends
+    pub fn clear(&self) {
+        let mut watchers = self.watchers.lock().unwrap_or_else(|e| e.into_inner());
+        for (_, handle) in watchers.drain() {
+            handle.abort();
+        }
+    }
+
+    /// Number of active subscriptions
+    pub fn len(&self) -> usize {
+        let watchers = self.watchers.lock().unwrap_or_else(|e| e.into_inner());
+        watchers.values().filter(|h| !h.is_finished()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Drop for SubscriptionManager {
+    fn drop(&mut self) {
+        let watchers = self.watchers.get_mut().unwrap_or_else(|e| e.into_inner());
+        for (_, handle) in watchers.drain() {
+            handle.abort();
+        }
+    }
+}
+
+/// Poll a board and notify whenever its fingerprint changes
+async fn watch_board(
+    uri: String,
+    board_id: String,
+    item_type: Option<String>,
+    poll_interval: Duration,
+    fetch: FetchItems,
+    notify: NotifyUpdated,
+) {
+    let mut ticker = tokio::time::interval(poll_interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let mut last: Option<BoardFingerprint> = None;
+    let mut failures = 0;
+
+    loop {
+        ticker.tick().await;
+
+        let items = match fetch(board_id.clone(), item_type.clone()).await {
+            Ok(items) => items,
+            Err(e) => {
+                failures += 1;
+                warn!(uri = %uri, error = %e, failures, "Resource poll failed");
+                if failures >= MAX_CONSECUTIVE_POLL_FAILURES {
+                    warn!(uri = %uri, "Resource polls keep failing, ending subscription");
+                    return;
+                }
+                continue;
+            }
+        };
+        failures = 0;
+
+        let current = fingerprint(&items);
+        let changed = last.as_ref().is_some_and(|previous| *previous != current);
+        last = Some(current);
+
+        if changed {
+            debug!(uri = %uri, "Board changed, notifying subscriber");
+            if !notify(uri.clone()).await {
+                info!(uri = %uri, "Subscriber gone, stopping watcher");
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::miro::types::ItemBuilder;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn item(id: &str, modified_at: &str) -> Item {
+        ItemBuilder::new(id, "sticky_note")
+            .modified_at(modified_at)
+            .build()
+    }
+
+    fn config(max_per_session: usize) -> SubscriptionConfig {
+        SubscriptionConfig {
+            poll_interval: Duration::from_millis(10),
+            max_per_session,
+        }
+    }
+
+    fn noop_fetch() -> FetchItems {
+        Arc::new(|_, _| Box::pin(async { Ok(vec![]) }))
+    }
+
+    fn noop_notify() -> NotifyUpdated {
+        Arc::new(|_| Box::pin(async { true }))
+    }
+
+    #[test]
+    fn test_fingerprint_detects_changes() {
+        let before = fingerprint(&[item("a", "t1"), item("b", "t1")]);
+
+        assert_eq!(before, fingerprint(&[item("b", "t1"), item("a", "t1")]));
+        assert_ne!(before, fingerprint(&[item("a", "t2"), item("b", "t1")]));
+        assert_ne!(before, fingerprint(&[item("a", "t1")]));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_rejects_unsupported_uris() {
+        let manager = SubscriptionManager::new(config(5));
+
+        assert_eq!(
+            manager.subscribe("miro://boards", noop_fetch(), noop_notify()),
+            Err(SubscriptionError::Unsupported("miro://boards".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subscription_cap_per_session() {
+        let manager = SubscriptionManager::new(config(1));
+
+        assert!(manager
+            .subscribe("miro://boards/a", noop_fetch(), noop_notify())
+            .is_ok());
+        // Re-subscribing to the same URI doesn't count against the cap
+        assert!(manager
+            .subscribe("miro://boards/a", noop_fetch(), noop_notify())
+            .is_ok());
+        assert_eq!(
+            manager.subscribe("miro://boards/b", noop_fetch(), noop_notify()),
+            Err(SubscriptionError::LimitReached(1))
+        );
+
+        assert!(manager.unsubscribe("miro://boards/a"));
+        assert!(!manager.unsubscribe("miro://boards/a"));
+        assert!(manager.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_watcher_notifies_on_change() {
+        let polls = Arc::new(AtomicUsize::new(0));
+        let notified = Arc::new(AtomicUsize::new(0));
+
+        let fetch: FetchItems = {
+            let polls = polls.clone();
+            Arc::new(move |_, _| {
+                let poll = polls.fetch_add(1, Ordering::SeqCst);
+                Box::pin(async move {
+                    // Board changes between the second and third poll
+                    let stamp = if poll < 2 { "t1" } else { "t2" };
+                    Ok(vec![item("a", stamp)])
+                })
+            })
+        };
+        let notify: NotifyUpdated = {
+            let notified = notified.clone();
+            Arc::new(move |uri| {
+                assert_eq!(uri, "miro://boards/a");
+                notified.fetch_add(1, Ordering::SeqCst);
+                Box::pin(async { false })
+            })
+        };
+
+        let manager = SubscriptionManager::new(config(5));
+        manager.subscribe("miro://boards/a", fetch, notify).unwrap();
+
+        for _ in 0..100 {
+            if notified.load(Ordering::SeqCst) > 0 && manager.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(notified.load(Ordering::SeqCst), 1);
+        assert!(polls.load(Ordering::SeqCst) >= 3);
+        // Watcher stops once the notifier reports the session is gone
+        assert!(manager.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_watcher_gives_up_after_repeated_failures() {
+        let polls = Arc::new(AtomicUsize::new(0));
+        let fetch: FetchItems = {
+            let polls = polls.clone();
+            Arc::new(move |_, _| {
+                let poll = polls.fetch_add(1, Ordering::SeqCst);
+                Box::pin(async move {
+                    // One success resets the count; then the token stops working
+                    if poll == 2 {
+                        Ok(vec![])
+                    } else {
+                        Err("Bearer token is invalid or expired (401)".to_string())
+                    }
+                })
+            })
+        };
+
+        let manager = SubscriptionManager::new(config(5));
+        manager
+            .subscribe("miro://boards/a", fetch, noop_notify())
+            .unwrap();
+
+        for _ in 0..100 {
+            if manager.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        assert!(manager.is_empty());
+        assert_eq!(
+            polls.load(Ordering::SeqCst),
+            3 + MAX_CONSECUTIVE_POLL_FAILURES as usize
+        );
+    }
+}
@@ -1,12 +1,15 @@
+use crate::auth::scopes;
 use crate::auth::token_validator::UserInfo;
-use crate::miro::types::Board;
+use crate::miro::progress::ToolProgress;
+use crate::miro::types::{Board, Connector, Item};
 use axum::{
     extract::Path,
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
     Extension, Json,
 };
 use reqwest::Client;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::{error, info, warn};
@@ -38,14 +41,17 @@ impl<T: Serialize> ToolResponse<T> {
 }
 
 /// Response for list_boards tool
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ListBoardsResponse {
     pub boards: Vec<BoardInfo>,
     pub count: usize,
+    /// Listing was cancelled before every page was fetched
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cancelled: bool,
 }
 
 /// Board info in tool response
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct BoardInfo {
     pub id: String,
     pub name: String,
@@ -65,7 +71,7 @@ impl From<Board> for BoardInfo {
 }
 
 /// Response for get_board tool
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct GetBoardResponse {
     pub board: BoardInfo,
 }
@@ -76,6 +82,7 @@ pub struct GetBoardResponse {
 ///
 /// Extracts Bearer token from request extensions (provided by bearer_auth_middleware),
 /// then calls Miro API to list boards accessible to the authenticated user.
+/// Requires the `boards:read` scope.
 ///
 /// # Arguments
 ///
@@ -89,6 +96,7 @@ pub async fn list_boards(
     Extension(token): Extension<Arc<String>>,
     Extension(user_info): Extension<Arc<UserInfo>>,
 ) -> Result<Json<ToolResponse<ListBoardsResponse>>, ToolError> {
+    require_scope(&user_info, scopes::BOARDS_READ)?;
     info!(
         user_id = %user_info.user_id,
         "Listing boards for user"
@@ -112,6 +120,7 @@ pub async fn list_boards(
             Ok(Json(ToolResponse::ok(ListBoardsResponse {
                 boards: board_infos,
                 count,
+                cancelled: false,
             })))
         }
         Err(e) => {
@@ -128,7 +137,7 @@ pub async fn list_boards(
 /// Get details of a specific Miro board
 ///
 /// Extracts Bearer token from request extensions and board_id from path,
-/// then calls Miro API to get board details.
+/// then calls Miro API to get board details. Requires the `boards:read` scope.
 ///
 /// # Arguments
 ///
@@ -144,6 +153,7 @@ pub async fn get_board(
     Extension(user_info): Extension<Arc<UserInfo>>,
     Path(board_id): Path<String>,
 ) -> Result<Json<ToolResponse<GetBoardResponse>>, ToolError> {
+    require_scope(&user_info, scopes::BOARDS_READ)?;
     info!(
         user_id = %user_info.user_id,
         board_id = %board_id,
@@ -188,8 +198,22 @@ pub async fn get_board(
 
 // ==================== Helper Functions ====================
 
+/// Check the token was granted `scope`, as the JSON-RPC dispatch does for tool calls
+fn require_scope(user_info: &UserInfo, scope: &'static str) -> Result<(), ToolError> {
+    if user_info.has_scope(scope) {
+        return Ok(());
+    }
+    warn!(
+        user_id = %user_info.user_id,
+        required = %scope,
+        granted = ?user_info.scopes,
+        "Insufficient scope"
+    );
+    Err(ToolError::InsufficientScope(scope))
+}
+
 /// Fetch boards from Miro API using Bearer token
-async fn fetch_boards_from_miro(
+pub(crate) async fn fetch_boards_from_miro(
     http_client: &Client,
     bearer_token: &str,
 ) -> Result<Vec<Board>, String> {
@@ -244,7 +268,7 @@ async fn fetch_boards_from_miro(
 }
 
 /// Fetch a specific board from Miro API using Bearer token
-async fn fetch_board_from_miro(
+pub(crate) async fn fetch_board_from_miro(
     http_client: &Client,
     bearer_token: &str,
     board_id: &str,
@@ -284,12 +308,201 @@ async fn fetch_board_from_miro(
     }
 }
 
+/// Page size requested from paginated Miro list endpoints (API maximum)
+const PAGE_LIMIT: usize = 50;
+
+/// How a Miro list endpoint pages its results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Paging {
+    /// Each page carries the `cursor` of the next (items, connectors)
+    Cursor,
+    /// Pages are addressed by `offset` up to the reported `total` (boards)
+    Offset,
+}
+
+/// Fetch every page of a cursor-paginated Miro list endpoint using Bearer token
+async fn fetch_all_pages_from_miro<T: serde::de::DeserializeOwned>(
+    http_client: &Client,
+    bearer_token: &str,
+    url: &str,
+) -> Result<Vec<T>, String> {
+    fetch_pages_from_miro(
+        http_client,
+        bearer_token,
+        url,
+        Paging::Cursor,
+        &ToolProgress::none(),
+    )
+    .await
+    .map(|pages| pages.entries)
+}
+
+/// Entries fetched from a paginated endpoint
+pub(crate) struct FetchedPages<T> {
+    pub entries: Vec<T>,
+    /// Whether every page was fetched, i.e. the walk wasn't cancelled
+    pub complete: bool,
+}
+
+/// Walk a paginated Miro list endpoint, reporting progress per page
+///
+/// Stops before requesting the next page once `progress` is cancelled and
+/// returns the entries fetched so far.
+async fn fetch_pages_from_miro<T: serde::de::DeserializeOwned>(
+    http_client: &Client,
+    bearer_token: &str,
+    url: &str,
+    paging: Paging,
+    progress: &ToolProgress,
+) -> Result<FetchedPages<T>, String> {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let mut results = Vec::new();
+    let mut cursor: Option<String> = None;
+    let mut offset = 0;
+
+    loop {
+        if progress.is_cancelled() {
+            return Ok(FetchedPages {
+                entries: results,
+                complete: false,
+            });
+        }
+
+        let mut page_url = format!("{}{}limit={}", url, separator, PAGE_LIMIT);
+        if paging == Paging::Offset {
+            page_url.push_str(&format!("&offset={}", offset));
+        }
+        if let Some(cursor) = &cursor {
+            page_url.push_str("&cursor=");
+            page_url.extend(url::form_urlencoded::byte_serialize(cursor.as_bytes()));
+        }
+
+        let response = http_client
+            .get(&page_url)
+            .bearer_auth(bearer_token)
+            .send()
+            .await
+            .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+        let page = match response.status() {
+            reqwest::StatusCode::OK => response
+                .json::<serde_json::Value>()
+                .await
+                .map_err(|e| format!("Failed to parse response: {}", e))?,
+            reqwest::StatusCode::UNAUTHORIZED => {
+                return Err("Bearer token is invalid or expired (401)".to_string())
+            }
+            reqwest::StatusCode::FORBIDDEN => {
+                return Err("Access forbidden - insufficient permissions (403)".to_string())
+            }
+            reqwest::StatusCode::NOT_FOUND => return Err(format!("Not found: {}", url)),
+            status => {
+                let error_text = response
+                    .text()
+                    .await
+                    .unwrap_or_else(|_| "Unknown error".to_string());
+                return Err(format!(
+                    "Miro API error {}: {}",
+                    status.as_u16(),
+                    error_text
+                ));
+            }
+        };
+
+        let data = page
+            .get("data")
+            .and_then(|v| v.as_array())
+            .ok_or("Invalid response format: missing 'data' array")?;
+        let fetched = data.len();
+        for entry in data {
+            results.push(
+                serde_json::from_value::<T>(entry.clone())
+                    .map_err(|e| format!("Failed to parse list entry: {}", e))?,
+            );
+        }
+        let total = page
+            .get("total")
+            .and_then(|t| t.as_u64())
+            .map(|t| t as usize);
+        progress.report(
+            results.len(),
+            total,
+            format!("Fetched {} entries", results.len()),
+        );
+
+        let has_more = match paging {
+            Paging::Cursor => {
+                cursor = page
+                    .get("cursor")
+                    .and_then(|c| c.as_str())
+                    .filter(|c| !c.is_empty())
+                    .map(str::to_string);
+                cursor.is_some()
+            }
+            Paging::Offset => {
+                offset += PAGE_LIMIT;
+                // Without a total, a short page is the last one
+                fetched > 0 && total.map_or(fetched == PAGE_LIMIT, |total| offset < total)
+            }
+        };
+        if !has_more {
+            return Ok(FetchedPages {
+                entries: results,
+                complete: true,
+            });
+        }
+    }
+}
+
+/// Fetch every board the token can access, page by page, using Bearer token
+pub(crate) async fn fetch_all_boards_from_miro(
+    http_client: &Client,
+    bearer_token: &str,
+    progress: &ToolProgress,
+) -> Result<FetchedPages<Board>, String> {
+    fetch_pages_from_miro(
+        http_client,
+        bearer_token,
+        "https://api.miro.com/v2/boards",
+        Paging::Offset,
+        progress,
+    )
+    .await
+}
+
+/// Fetch every item on a board, optionally filtered by type, using Bearer token
+pub(crate) async fn fetch_items_from_miro(
+    http_client: &Client,
+    bearer_token: &str,
+    board_id: &str,
+    item_type: Option<&str>,
+) -> Result<Vec<Item>, String> {
+    let mut url = format!("https://api.miro.com/v2/boards/{}/items", board_id);
+    if let Some(item_type) = item_type {
+        url.push_str("?type=");
+        url.extend(url::form_urlencoded::byte_serialize(item_type.as_bytes()));
+    }
+    fetch_all_pages_from_miro(http_client, bearer_token, &url).await
+}
+
+/// Fetch every connector on a board using Bearer token
+pub(crate) async fn fetch_connectors_from_miro(
+    http_client: &Client,
+    bearer_token: &str,
+    board_id: &str,
+) -> Result<Vec<Connector>, String> {
+    let url = format!("https://api.miro.com/v2/boards/{}/connectors", board_id);
+    fetch_all_pages_from_miro(http_client, bearer_token, &url).await
+}
+
 // ==================== Error Handling ====================
 
 /// Tool error types
 #[derive(Debug)]
 pub enum ToolError {
     Unauthorized,
+    /// The token lacks the scope the tool needs
+    InsufficientScope(&'static str),
     InvalidInput(String),
     MiroApiError(String),
     InternalError(String),
@@ -308,6 +521,22 @@ impl IntoResponse for ToolError {
                 )
                     .into_response()
             }
+            ToolError::InsufficientScope(scope) => {
+                // RFC 6750 §3.1: name the scope the client should request
+                let challenge = format!(
+                    "Bearer realm=\"miro-mcp-server\", error=\"insufficient_scope\", scope=\"{}\"",
+                    scope
+                );
+                (
+                    StatusCode::FORBIDDEN,
+                    [(header::WWW_AUTHENTICATE, challenge)],
+                    Json(ToolResponse::<()>::err(format!(
+                        "Insufficient scope: {} required",
+                        scope
+                    ))),
+                )
+                    .into_response()
+            }
             ToolError::InvalidInput(msg) => {
                 warn!("Invalid tool input: {}", msg);
                 (
@@ -339,6 +568,130 @@ impl IntoResponse for ToolError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn board_json(i: usize) -> serde_json::Value {
+        serde_json::json!({
+            "id": format!("board-{}", i),
+            "name": format!("Board {}", i),
+            "createdAt": "2025-01-01T00:00:00Z"
+        })
+    }
+
+    /// Mock `/v2/boards` with `total` boards served in offset pages
+    async fn mock_boards(total: usize) -> MockServer {
+        let server = MockServer::start().await;
+        for offset in (0..total).step_by(PAGE_LIMIT) {
+            let data: Vec<_> = (offset..total.min(offset + PAGE_LIMIT))
+                .map(board_json)
+                .collect();
+            Mock::given(method("GET"))
+                .and(path("/v2/boards"))
+                .and(query_param("offset", offset.to_string()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "data": data,
+                    "total": total,
+                    "size": data.len(),
+                    "offset": offset,
+                    "limit": PAGE_LIMIT
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        server
+    }
+
+    #[tokio::test]
+    async fn test_boards_are_paged_by_offset() {
+        let server = mock_boards(120).await;
+        let updates = Arc::new(Mutex::new(Vec::new()));
+        let sink = updates.clone();
+        let progress = ToolProgress::none().with_sink(Some(Arc::new(move |update| {
+            sink.lock().unwrap().push(update);
+        })));
+
+        let pages: FetchedPages<Board> = fetch_pages_from_miro(
+            &Client::new(),
+            "token",
+            &format!("{}/v2/boards", server.uri()),
+            Paging::Offset,
+            &progress,
+        )
+        .await
+        .unwrap();
+
+        assert!(pages.complete);
+        assert_eq!(pages.entries.len(), 120);
+        assert_eq!(pages.entries[119].id, "board-119");
+        let updates = updates.lock().unwrap();
+        assert_eq!(updates.len(), 3);
+        assert_eq!(updates[2].total, Some(120.0));
+    }
+
+    #[tokio::test]
+    async fn test_board_paging_stops_when_cancelled() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/boards"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": (0..PAGE_LIMIT).map(board_json).collect::<Vec<_>>(),
+                "total": 500
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let flag = cancelled.clone();
+        let progress = ToolProgress::none()
+            .with_sink(Some(Arc::new(move |_| flag.store(true, Ordering::SeqCst))))
+            .with_cancel_flag(cancelled);
+
+        let pages: FetchedPages<Board> = fetch_pages_from_miro(
+            &Client::new(),
+            "token",
+            &format!("{}/v2/boards", server.uri()),
+            Paging::Offset,
+            &progress,
+        )
+        .await
+        .unwrap();
+
+        assert!(!pages.complete);
+        assert_eq!(pages.entries.len(), PAGE_LIMIT);
+    }
+
+    /// The REST routes apply the same scope gate as tools/call, before calling Miro
+    #[tokio::test]
+    async fn test_rest_board_routes_require_boards_read() {
+        let token = Extension(Arc::new("token".to_string()));
+        let user_info = Extension(Arc::new(UserInfo::new(
+            "user-1".to_string(),
+            "team-1".to_string(),
+            vec!["identity:read".to_string()],
+        )));
+
+        let responses = [
+            list_boards(token.clone(), user_info.clone())
+                .await
+                .into_response(),
+            get_board(token, user_info, Path("board-123".to_string()))
+                .await
+                .into_response(),
+        ];
+        for response in responses {
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+            let challenge = response.headers()[header::WWW_AUTHENTICATE]
+                .to_str()
+                .unwrap();
+            assert!(challenge.contains(r#"error="insufficient_scope""#));
+            assert!(challenge.contains(r#"scope="boards:read""#));
+        }
+    }
 
     #[test]
     fn test_board_info_from_board() {
@@ -1,12 +1,14 @@
 use crate::auth::{AuthError, MiroOAuthClient, TokenSet, TokenStore};
 use crate::miro::types::{
-    Board, BoardsResponse, BulkCreateRequest, BulkCreateResponse, Caption, ConnectorResponse,
-    ConnectorStyle, CreateBoardRequest, CreateBoardResponse, CreateConnectorRequest,
-    CreateFrameRequest, CreateShapeRequest, CreateStickyNoteRequest, CreateTextRequest,
-    FrameResponse, Geometry, Item, ItemsResponse, Parent, Position, ShapeResponse,
-    StickyNoteResponse, TextResponse, UpdateItemRequest,
+    Board, BoardsResponse, BulkCreateRequest, BulkCreateResponse, Caption, Connector,
+    ConnectorResponse, ConnectorStyle, CreateBoardRequest, CreateBoardResponse,
+    CreateConnectorRequest, CreateFrameRequest, CreateShapeRequest, CreateStickyNoteRequest,
+    CreateTagRequest, CreateTextRequest, FrameResponse, Geometry, Item, ItemsResponse, Parent,
+    Position, ShapeResponse, StickyNoteResponse, Tag, TextResponse, UpdateItemRequest,
 };
 use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
 use serde_json::Value;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -34,6 +36,25 @@ pub enum MiroError {
 
     #[error("Invalid bulk operation: {0}")]
     BulkOperationError(String),
+
+    #[error("Cancelled: {0}")]
+    Cancelled(String),
+}
+
+/// Page size requested from paginated list endpoints (API maximum)
+const PAGE_LIMIT: usize = 50;
+
+/// One page of a paginated list endpoint
+///
+/// Items and connectors page by `cursor`; tags and items-by-tag page by
+/// `offset` and report `total` instead.
+#[derive(Debug, Deserialize)]
+struct Page<T> {
+    data: Vec<T>,
+    #[serde(default)]
+    cursor: Option<String>,
+    #[serde(default)]
+    total: Option<usize>,
 }
 
 /// Miro API client with automatic token refresh
@@ -253,8 +274,12 @@ impl MiroClient {
             data: crate::miro::types::StickyNoteData {
                 content,
                 shape: Some("square".to_string()),
+                extra: Default::default(),
+            },
+            style: crate::miro::types::StickyNoteStyle {
+                fill_color: color,
+                extra: Default::default(),
             },
-            style: crate::miro::types::StickyNoteStyle { fill_color: color },
             position: Position {
                 x,
                 y,
@@ -290,11 +315,13 @@ impl MiroClient {
         let shape_data = crate::miro::types::ShapeData {
             content,
             shape: shape_type,
+            extra: Default::default(),
         };
         let shape_style = crate::miro::types::ShapeStyle {
             fill_color,
             border_color: None,
             border_width: None,
+            extra: Default::default(),
         };
         let position = Position { x, y, origin: None };
         let geometry = Geometry {
@@ -328,7 +355,10 @@ impl MiroClient {
         parent_id: Option<String>,
     ) -> Result<TextResponse, MiroError> {
         let request_body = CreateTextRequest {
-            data: crate::miro::types::TextData { content },
+            data: crate::miro::types::TextData {
+                content,
+                extra: Default::default(),
+            },
             position: Position { x, y, origin: None },
             geometry: Geometry {
                 width,
@@ -359,9 +389,11 @@ impl MiroClient {
         let frame_data = crate::miro::types::FrameData {
             title,
             frame_type: "frame".to_string(),
+            extra: Default::default(),
         };
         let frame_style = crate::miro::types::FrameStyle {
             fill_color: fill_color.unwrap_or_else(|| "light_gray".to_string()),
+            extra: Default::default(),
         };
         let position = Position { x, y, origin: None };
         let geometry = Geometry {
@@ -492,6 +524,127 @@ impl MiroClient {
         Ok(())
     }
 
+    /// Get board metadata
+    pub async fn get_board(&self, board_id: &str) -> Result<Board, MiroError> {
+        let path = format!("/boards/{}", board_id);
+        let response = self.get(&path).await?;
+        let board: Board = serde_json::from_value(response)?;
+        Ok(board)
+    }
+
+    /// Follow cursor pagination until every page of a list endpoint has been fetched
+    async fn get_all_pages<T: DeserializeOwned>(&self, path: &str) -> Result<Vec<T>, MiroError> {
+        let separator = if path.contains('?') { '&' } else { '?' };
+        let mut results = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let mut page_path = format!("{}{}limit={}", path, separator, PAGE_LIMIT);
+            if let Some(cursor) = &cursor {
+                page_path.push_str("&cursor=");
+                page_path.extend(url::form_urlencoded::byte_serialize(cursor.as_bytes()));
+            }
+
+            let response = self.get(&page_path).await?;
+            let page: Page<T> = serde_json::from_value(response)?;
+            results.extend(page.data);
+
+            match page.cursor.filter(|c| !c.is_empty()) {
+                Some(next) => cursor = Some(next),
+                None => return Ok(results),
+            }
+        }
+    }
+
+    /// Follow offset pagination until `total` entries have been fetched
+    async fn get_all_offset_pages<T: DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<Vec<T>, MiroError> {
+        let separator = if path.contains('?') { '&' } else { '?' };
+        let mut results = Vec::new();
+        let mut offset = 0;
+
+        loop {
+            let page_path = format!(
+                "{}{}limit={}&offset={}",
+                path, separator, PAGE_LIMIT, offset
+            );
+
+            let response = self.get(&page_path).await?;
+            let page: Page<T> = serde_json::from_value(response)?;
+            let fetched = page.data.len();
+            results.extend(page.data);
+
+            offset += PAGE_LIMIT;
+            if !has_more_offset_pages(offset, fetched, page.total) {
+                return Ok(results);
+            }
+        }
+    }
+
+    /// List every item on a board, following pagination
+    pub async fn list_all_items(
+        &self,
+        board_id: &str,
+        item_types: Option<Vec<&str>>,
+    ) -> Result<Vec<Item>, MiroError> {
+        let mut path = format!("/boards/{}/items", board_id);
+        if let Some(types) = item_types {
+            path.push_str(&format!("?type={}", types.join(",")));
+        }
+        self.get_all_pages(&path).await
+    }
+
+    /// List every connector on a board, following pagination
+    pub async fn list_connectors(&self, board_id: &str) -> Result<Vec<Connector>, MiroError> {
+        let path = format!("/boards/{}/connectors", board_id);
+        self.get_all_pages(&path).await
+    }
+
+    /// List every item carrying a tag, following pagination
+    pub async fn list_items_by_tag(
+        &self,
+        board_id: &str,
+        tag_id: &str,
+    ) -> Result<Vec<Item>, MiroError> {
+        let path = format!("/boards/{}/items?tag_id={}", board_id, tag_id);
+        self.get_all_offset_pages(&path).await
+    }
+
+    /// List every tag defined on a board, following pagination
+    pub async fn list_tags(&self, board_id: &str) -> Result<Vec<Tag>, MiroError> {
+        let path = format!("/boards/{}/tags", board_id);
+        self.get_all_offset_pages(&path).await
+    }
+
+    /// Create a tag on a board
+    pub async fn create_tag(
+        &self,
+        board_id: &str,
+        title: String,
+        fill_color: Option<String>,
+    ) -> Result<Tag, MiroError> {
+        let request_body = CreateTagRequest { title, fill_color };
+        let json_body = serde_json::to_value(&request_body)?;
+        let path = format!("/boards/{}/tags", board_id);
+        let response = self.post(&path, Some(json_body)).await?;
+        let tag: Tag = serde_json::from_value(response)?;
+        Ok(tag)
+    }
+
+    /// Attach an existing tag to a sticky note or card
+    pub async fn attach_tag(
+        &self,
+        board_id: &str,
+        item_id: &str,
+        tag_id: &str,
+    ) -> Result<(), MiroError> {
+        let path = format!("/boards/{}/items/{}?tag_id={}", board_id, item_id, tag_id);
+        let _response = self.post(&path, None).await?;
+        Ok(())
+    }
+
     /// Bulk create multiple items in a single API call (max 20 items per request)
     pub async fn bulk_create_items(
         &self,
@@ -622,6 +775,18 @@ impl MiroClient {
     }
 }
 
+/// Whether an offset-paginated walk should request another page
+///
+/// `offset` is the offset of the next page. Without a `total` the walk stops on
+/// the first short page; an empty page always ends it.
+pub(crate) fn has_more_offset_pages(offset: usize, fetched: usize, total: Option<usize>) -> bool {
+    match total {
+        _ if fetched == 0 => false,
+        Some(total) => offset < total,
+        None => fetched == PAGE_LIMIT,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -635,6 +800,15 @@ mod tests {
             encryption_key: [0u8; 32],
             port: 3000,
             base_url: None,
+            resource_poll_interval_secs: 30,
+            max_resource_subscriptions: 10,
+            confirm_import_threshold: 100,
+            oauth_storage: Default::default(),
+            issue_proxy_tokens: false,
+            registration_policy: Default::default(),
+            consent_policy: Default::default(),
+            log_pii: false,
+            snapshot_dir: "snapshots".into(),
         }
     }
 
@@ -830,6 +1004,7 @@ mod tests {
                     item_type: "text".to_string(),
                     data: TextData {
                         content: format!("Text {}", i),
+                        extra: Default::default(),
                     },
                     position: Position {
                         x: i as f64 * 100.0,
@@ -859,4 +1034,16 @@ mod tests {
             _ => panic!("Expected BulkOperationError"),
         }
     }
+
+    #[test]
+    fn test_offset_paging_stops_at_total() {
+        assert!(has_more_offset_pages(50, 50, Some(120)));
+        assert!(has_more_offset_pages(100, 50, Some(120)));
+        assert!(!has_more_offset_pages(150, 20, Some(120)));
+        assert!(!has_more_offset_pages(50, 50, Some(50)));
+        // Without a total, a full page means there may be more
+        assert!(has_more_offset_pages(50, 50, None));
+        assert!(!has_more_offset_pages(50, 49, None));
+        assert!(!has_more_offset_pages(50, 0, Some(500)));
+    }
 }
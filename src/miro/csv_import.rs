@@ -0,0 +1,851 @@
+//! CSV import into sticky notes and cards
+//!
+//! Maps spreadsheet rows to Miro items: one column provides the item content, optional
+//! columns provide color (through a value→color mapping), tags and target frame.
+//! Items are created through chunked bulk creation, and the import returns a
+//! row→item-ID report so rows can be re-synced later.
+
+use crate::miro::types::{
+    BulkItemRequest, CardData, CardStyle, Geometry, Parent, Position, StickyNoteData,
+    StickyNoteStyle,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[cfg(feature = "stdio-mcp")]
+use crate::miro::client::{MiroClient, MiroError};
+#[cfg(feature = "stdio-mcp")]
+use crate::miro::progress::ToolProgress;
+#[cfg(feature = "stdio-mcp")]
+use tracing::{info, warn};
+
+/// Maximum items per bulk create request (Miro API limit)
+pub const BULK_CHUNK_SIZE: usize = 20;
+
+/// Row error for rows skipped because the import was cancelled
+#[cfg(feature = "stdio-mcp")]
+const CANCELLED_ERROR: &str = "import cancelled before this row was created";
+
+/// Sticky note colors accepted by the Miro API
+pub const STICKY_NOTE_COLORS: &[&str] = &[
+    "gray",
+    "light_yellow",
+    "yellow",
+    "orange",
+    "light_green",
+    "green",
+    "dark_green",
+    "cyan",
+    "light_pink",
+    "pink",
+    "violet",
+    "red",
+    "light_blue",
+    "blue",
+    "dark_blue",
+    "black",
+];
+
+/// Default sticky note width in board units
+const STICKY_NOTE_WIDTH: f64 = 200.0;
+
+/// Default card width in board units
+const CARD_WIDTH: f64 = 320.0;
+
+/// Approximate card height, used for grid spacing only
+const CARD_HEIGHT: f64 = 100.0;
+
+/// Gap between items laid out in the grid
+const GRID_GAP: f64 = 20.0;
+
+/// CSV parsing and mapping errors
+#[derive(Error, Debug, PartialEq)]
+pub enum CsvError {
+    #[error("CSV input has no header row")]
+    Empty,
+
+    #[error("Unterminated quoted field starting on line {0}")]
+    UnterminatedQuote(usize),
+
+    #[error("Column not found in CSV header: {0}")]
+    UnknownColumn(String),
+
+    #[error("Invalid color: {0} (sticky notes take a Miro color name, cards a #rrggbb hex value)")]
+    InvalidColor(String),
+}
+
+/// Parsed CSV table: header row plus data rows
+#[derive(Debug, Clone)]
+pub struct CsvTable {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl CsvTable {
+    /// Parse RFC 4180 CSV text (quoted fields, escaped quotes, CRLF or LF line endings)
+    ///
+    /// The first record is used as the header row. Blank lines are ignored.
+    pub fn parse(input: &str) -> Result<Self, CsvError> {
+        let mut records: Vec<Vec<String>> = Vec::new();
+        let mut record: Vec<String> = Vec::new();
+        let mut field = String::new();
+        let mut in_quotes = false;
+        let mut quote_start_line = 0;
+        let mut line = 1;
+        let mut chars = input.trim_start_matches('\u{feff}').chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if in_quotes {
+                match c {
+                    '"' if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    '"' => in_quotes = false,
+                    '\n' => {
+                        line += 1;
+                        field.push(c);
+                    }
+                    _ => field.push(c),
+                }
+                continue;
+            }
+
+            match c {
+                '"' if field.is_empty() => {
+                    in_quotes = true;
+                    quote_start_line = line;
+                }
+                ',' => record.push(std::mem::take(&mut field)),
+                '\r' if chars.peek() == Some(&'\n') => {}
+                '\n' | '\r' => {
+                    line += 1;
+                    record.push(std::mem::take(&mut field));
+                    Self::push_record(&mut records, std::mem::take(&mut record));
+                }
+                _ => field.push(c),
+            }
+        }
+
+        if in_quotes {
+            return Err(CsvError::UnterminatedQuote(quote_start_line));
+        }
+
+        if !field.is_empty() || !record.is_empty() {
+            record.push(field);
+            Self::push_record(&mut records, record);
+        }
+
+        let mut records = records.into_iter();
+        let headers = records
+            .next()
+            .ok_or(CsvError::Empty)?
+            .into_iter()
+            .map(|h| h.trim().to_string())
+            .collect();
+
+        Ok(Self {
+            headers,
+            rows: records.collect(),
+        })
+    }
+
+    /// Skip records made of a single empty field (blank lines)
+    fn push_record(records: &mut Vec<Vec<String>>, record: Vec<String>) {
+        if !(record.len() == 1 && record[0].trim().is_empty()) {
+            records.push(record);
+        }
+    }
+
+    /// Find a column index by header name (trimmed, case-insensitive)
+    pub fn column_index(&self, name: &str) -> Result<usize, CsvError> {
+        let wanted = name.trim();
+        self.headers
+            .iter()
+            .position(|h| h.eq_ignore_ascii_case(wanted))
+            .ok_or_else(|| CsvError::UnknownColumn(wanted.to_string()))
+    }
+}
+
+/// Kind of item created for each CSV row
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CsvItemType {
+    #[default]
+    StickyNote,
+    Card,
+}
+
+/// How CSV columns map onto item fields
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvColumnMapping {
+    /// Column providing sticky note content or card title
+    pub content_column: String,
+    /// Item type to create for every row
+    #[serde(default)]
+    pub item_type: CsvItemType,
+    /// Column providing the card description (cards only)
+    #[serde(default)]
+    pub description_column: Option<String>,
+    /// Column whose values select the item color
+    #[serde(default)]
+    pub color_column: Option<String>,
+    /// Column value → color (sticky color name or card hex theme)
+    #[serde(default)]
+    pub color_map: HashMap<String, String>,
+    /// Color used when the color column is missing or its value is unmapped
+    #[serde(default)]
+    pub default_color: Option<String>,
+    /// Column with comma- or semicolon-separated tag titles
+    #[serde(default)]
+    pub tags_column: Option<String>,
+    /// Column with the target frame (frame ID or frame title)
+    #[serde(default)]
+    pub frame_column: Option<String>,
+    /// Frame used for rows without a frame column value
+    #[serde(default)]
+    pub frame_id: Option<String>,
+    /// Column holding a stable row key, echoed in the report for re-syncing
+    #[serde(default)]
+    pub key_column: Option<String>,
+}
+
+/// Grid layout for imported items
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvLayout {
+    /// X coordinate of the first item placed directly on the board
+    #[serde(default)]
+    pub x: f64,
+    /// Y coordinate of the first item placed directly on the board
+    #[serde(default)]
+    pub y: f64,
+    /// Number of items per grid row
+    #[serde(default = "default_columns")]
+    pub columns: usize,
+}
+
+fn default_columns() -> usize {
+    5
+}
+
+impl Default for CsvLayout {
+    fn default() -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            columns: default_columns(),
+        }
+    }
+}
+
+/// A CSV row ready to be created on the board
+#[derive(Debug, Clone)]
+pub struct PlannedRow {
+    /// 1-based data row number (header excluded)
+    pub row: usize,
+    pub key: Option<String>,
+    pub content: String,
+    pub description: Option<String>,
+    pub color: Option<String>,
+    pub tags: Vec<String>,
+    /// Frame reference (ID or title) still to be resolved against the board
+    pub frame: Option<String>,
+    pub position: Position,
+}
+
+impl PlannedRow {
+    /// Build the bulk create payload for this row
+    pub fn to_bulk_item(
+        &self,
+        item_type: CsvItemType,
+        parent_id: Option<String>,
+    ) -> BulkItemRequest {
+        let parent = parent_id.map(|id| Parent { id });
+        match item_type {
+            CsvItemType::StickyNote => BulkItemRequest::StickyNote {
+                item_type: "sticky_note".to_string(),
+                data: StickyNoteData {
+                    content: escape_html(&self.content),
+                    shape: Some("square".to_string()),
+                    extra: Default::default(),
+                },
+                style: StickyNoteStyle {
+                    fill_color: self
+                        .color
+                        .clone()
+                        .unwrap_or_else(|| "light_yellow".to_string()),
+                    extra: Default::default(),
+                },
+                position: self.position.clone(),
+                geometry: Geometry {
+                    width: STICKY_NOTE_WIDTH,
+                    height: None,
+                },
+                parent,
+            },
+            CsvItemType::Card => BulkItemRequest::Card {
+                item_type: "card".to_string(),
+                data: CardData {
+                    title: Some(escape_html(&self.content)),
+                    description: self.description.as_deref().map(escape_html),
+                    extra: Default::default(),
+                },
+                style: CardStyle {
+                    card_theme: self.color.clone(),
+                    extra: Default::default(),
+                },
+                position: self.position.clone(),
+                geometry: Geometry {
+                    width: CARD_WIDTH,
+                    height: None,
+                },
+                parent,
+            },
+        }
+    }
+}
+
+/// Import plan: rows to create plus rows rejected up front
+#[derive(Debug, Clone)]
+pub struct CsvImportPlan {
+    pub item_type: CsvItemType,
+    pub rows: Vec<PlannedRow>,
+    pub skipped: Vec<RowResult>,
+}
+
+/// Outcome for a single CSV row
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct RowResult {
+    /// 1-based data row number (header excluded)
+    pub row: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Row→item-ID report returned by an import
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CsvImportReport {
+    pub board_id: String,
+    pub created: usize,
+    pub failed: usize,
+    pub rows: Vec<RowResult>,
+    /// The import was cancelled; rows after the last completed chunk weren't created
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cancelled: bool,
+}
+
+impl CsvImportReport {
+    /// Build a report from per-row results, sorted by row number
+    pub fn from_results(board_id: &str, mut rows: Vec<RowResult>) -> Self {
+        rows.sort_by_key(|r| r.row);
+        let created = rows.iter().filter(|r| r.item_id.is_some()).count();
+        Self {
+            board_id: board_id.to_string(),
+            created,
+            failed: rows.len() - created,
+            rows,
+            cancelled: false,
+        }
+    }
+}
+
+/// Map CSV rows to planned items and lay them out in a grid per target frame
+pub fn plan_import(
+    table: &CsvTable,
+    mapping: &CsvColumnMapping,
+    layout: &CsvLayout,
+) -> Result<CsvImportPlan, CsvError> {
+    let optional_column = |name: &Option<String>| -> Result<Option<usize>, CsvError> {
+        name.as_deref().map(|n| table.column_index(n)).transpose()
+    };
+
+    let content_idx = table.column_index(&mapping.content_column)?;
+    let description_idx = optional_column(&mapping.description_column)?;
+    let color_idx = optional_column(&mapping.color_column)?;
+    let tags_idx = optional_column(&mapping.tags_column)?;
+    let frame_idx = optional_column(&mapping.frame_column)?;
+    let key_idx = optional_column(&mapping.key_column)?;
+
+    // Colors outside the CSV are checked up front so a typo can't fail every chunk at Miro
+    let valid_color = |value: &str| {
+        literal_color(value, mapping.item_type)
+            .ok_or_else(|| CsvError::InvalidColor(value.to_string()))
+    };
+    let color_map = mapping
+        .color_map
+        .iter()
+        .map(|(k, v)| Ok((k.trim().to_lowercase(), valid_color(v)?)))
+        .collect::<Result<HashMap<String, String>, CsvError>>()?;
+    let default_color = mapping
+        .default_color
+        .as_deref()
+        .map(valid_color)
+        .transpose()?;
+
+    let (step_x, step_y, width, height) = match mapping.item_type {
+        CsvItemType::StickyNote => (
+            STICKY_NOTE_WIDTH + GRID_GAP,
+            STICKY_NOTE_WIDTH + GRID_GAP,
+            STICKY_NOTE_WIDTH,
+            STICKY_NOTE_WIDTH,
+        ),
+        CsvItemType::Card => (
+            CARD_WIDTH + GRID_GAP,
+            CARD_HEIGHT + GRID_GAP,
+            CARD_WIDTH,
+            CARD_HEIGHT,
+        ),
+    };
+    let columns = layout.columns.max(1);
+
+    let cell = |record: &[String], idx: Option<usize>| -> Option<String> {
+        idx.and_then(|i| record.get(i))
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+
+    let mut rows = Vec::new();
+    let mut skipped = Vec::new();
+    let mut slots_per_frame: HashMap<Option<String>, usize> = HashMap::new();
+
+    for (i, record) in table.rows.iter().enumerate() {
+        let row = i + 1;
+        let key = cell(record, key_idx);
+
+        let Some(content) = cell(record, Some(content_idx)) else {
+            skipped.push(RowResult {
+                row,
+                key,
+                item_id: None,
+                error: Some(format!("Empty '{}' value", mapping.content_column)),
+            });
+            continue;
+        };
+
+        let color = cell(record, color_idx)
+            .and_then(|value| resolve_color(&value, &color_map, mapping.item_type))
+            .or_else(|| default_color.clone());
+
+        let tags = cell(record, tags_idx)
+            .map(|v| {
+                v.split([',', ';'])
+                    .map(|t| t.trim().to_string())
+                    .filter(|t| !t.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let frame = cell(record, frame_idx).or_else(|| mapping.frame_id.clone());
+
+        // Frame children are positioned relative to the frame's top-left corner
+        let slot = slots_per_frame.entry(frame.clone()).or_insert(0);
+        let (origin_x, origin_y) = match frame {
+            Some(_) => (GRID_GAP + width / 2.0, GRID_GAP + height / 2.0),
+            None => (layout.x, layout.y),
+        };
+        let position = Position {
+            x: origin_x + (*slot % columns) as f64 * step_x,
+            y: origin_y + (*slot / columns) as f64 * step_y,
+            origin: None,
+        };
+        *slot += 1;
+
+        rows.push(PlannedRow {
+            row,
+            key,
+            content,
+            description: cell(record, description_idx),
+            color,
+            tags,
+            frame,
+            position,
+        });
+    }
+
+    Ok(CsvImportPlan {
+        item_type: mapping.item_type,
+        rows,
+        skipped,
+    })
+}
+
+/// Resolve a color column value: explicit mapping first, then a literal color
+fn resolve_color(
+    value: &str,
+    color_map: &HashMap<String, String>,
+    item_type: CsvItemType,
+) -> Option<String> {
+    if let Some(mapped) = color_map.get(&value.to_lowercase()) {
+        return Some(mapped.clone());
+    }
+    literal_color(value, item_type)
+}
+
+/// Normalize a color Miro accepts for the item type: a sticky note color name
+/// (any case) or a `#rrggbb` card theme
+fn literal_color(value: &str, item_type: CsvItemType) -> Option<String> {
+    match item_type {
+        CsvItemType::StickyNote => {
+            let name = value.trim().to_lowercase();
+            STICKY_NOTE_COLORS.contains(&name.as_str()).then_some(name)
+        }
+        CsvItemType::Card => {
+            let valid = value.len() == 7
+                && value.starts_with('#')
+                && value[1..].chars().all(|c| c.is_ascii_hexdigit());
+            valid.then(|| value.to_string())
+        }
+    }
+}
+
+/// Escape text for Miro's HTML content fields
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Execute an import plan against a board
+///
+/// Frames referenced by title or ID are resolved first, missing tags are created,
+/// then items are created in chunks of [`BULK_CHUNK_SIZE`]. A failed chunk marks
+/// its rows as failed without aborting the rest of the import. Progress is
+/// reported per chunk; once cancelled, the remaining rows are reported as not
+/// created.
+#[cfg(feature = "stdio-mcp")]
+pub async fn execute_import(
+    client: &MiroClient,
+    board_id: &str,
+    plan: CsvImportPlan,
+    progress: &ToolProgress,
+) -> Result<CsvImportReport, MiroError> {
+    let mut results = plan.skipped;
+
+    // Resolve frame references (ID or title) to frame IDs
+    let mut frame_ids: HashMap<String, String> = HashMap::new();
+    if plan.rows.iter().any(|r| r.frame.is_some()) {
+        let frames = client
+            .list_items(board_id, Some(vec!["frame"]), None)
+            .await?;
+        for frame in frames {
+            if let Some(title) = frame
+                .data
+                .as_ref()
+                .and_then(|d| d.get("title"))
+                .and_then(|t| t.as_str())
+            {
+                frame_ids
+                    .entry(title.to_string())
+                    .or_insert_with(|| frame.id.clone());
+            }
+            frame_ids.insert(frame.id.clone(), frame.id);
+        }
+    }
+
+    // Resolve tag titles, creating tags that don't exist yet
+    let mut tag_ids: HashMap<String, String> = HashMap::new();
+    if plan.rows.iter().any(|r| !r.tags.is_empty()) {
+        for tag in client.list_tags(board_id).await? {
+            tag_ids.insert(tag.title, tag.id);
+        }
+        for row in &plan.rows {
+            for title in &row.tags {
+                if tag_ids.contains_key(title) {
+                    continue;
+                }
+                match client.create_tag(board_id, title.clone(), None).await {
+                    Ok(tag) => {
+                        tag_ids.insert(tag.title, tag.id);
+                    }
+                    Err(e) => warn!(tag = %title, error = %e, "Failed to create tag"),
+                }
+            }
+        }
+    }
+
+    let mut pending = Vec::new();
+    for row in plan.rows {
+        let parent_id = match &row.frame {
+            Some(frame) => match frame_ids.get(frame) {
+                Some(id) => Some(id.clone()),
+                None => {
+                    results.push(RowResult {
+                        row: row.row,
+                        key: row.key,
+                        item_id: None,
+                        error: Some(format!("Frame not found: {}", frame)),
+                    });
+                    continue;
+                }
+            },
+            None => None,
+        };
+        let item = row.to_bulk_item(plan.item_type, parent_id);
+        pending.push((row, item));
+    }
+
+    let total = pending.len();
+    let mut done = 0;
+    let mut cancelled = false;
+    for chunk in pending.chunks(BULK_CHUNK_SIZE) {
+        if progress.is_cancelled() {
+            cancelled = true;
+            for (row, _) in &pending[done..] {
+                results.push(RowResult {
+                    row: row.row,
+                    key: row.key.clone(),
+                    item_id: None,
+                    error: Some(CANCELLED_ERROR.to_string()),
+                });
+            }
+            break;
+        }
+
+        let items = chunk.iter().map(|(_, item)| item.clone()).collect();
+        match client.bulk_create_items(board_id, items).await {
+            Ok(created) => {
+                let returned = created.len();
+                for ((row, _), item) in chunk.iter().zip(created) {
+                    let mut errors = Vec::new();
+                    for title in &row.tags {
+                        let attached = match tag_ids.get(title) {
+                            Some(tag_id) => client
+                                .attach_tag(board_id, &item.id, tag_id)
+                                .await
+                                .map_err(|e| e.to_string()),
+                            None => Err("tag could not be created".to_string()),
+                        };
+                        if let Err(e) = attached {
+                            errors.push(format!("tag '{}': {}", title, e));
+                        }
+                    }
+                    results.push(RowResult {
+                        row: row.row,
+                        key: row.key.clone(),
+                        item_id: Some(item.id),
+                        error: (!errors.is_empty()).then(|| errors.join("; ")),
+                    });
+                }
+                for (row, _) in chunk.iter().skip(returned) {
+                    results.push(RowResult {
+                        row: row.row,
+                        key: row.key.clone(),
+                        item_id: None,
+                        error: Some("missing from bulk response".to_string()),
+                    });
+                }
+            }
+            Err(e) => {
+                warn!(board_id = %board_id, error = %e, "Bulk create chunk failed");
+                for (row, _) in chunk {
+                    results.push(RowResult {
+                        row: row.row,
+                        key: row.key.clone(),
+                        item_id: None,
+                        error: Some(e.to_string()),
+                    });
+                }
+            }
+        }
+
+        done += chunk.len();
+        progress.report(
+            done,
+            Some(total),
+            format!("Processed {} of {} rows", done, total),
+        );
+    }
+
+    let mut report = CsvImportReport::from_results(board_id, results);
+    report.cancelled = cancelled;
+    info!(
+        board_id = %board_id,
+        created = report.created,
+        failed = report.failed,
+        cancelled,
+        "CSV import completed"
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping(content_column: &str) -> CsvColumnMapping {
+        CsvColumnMapping {
+            content_column: content_column.to_string(),
+            item_type: CsvItemType::StickyNote,
+            description_column: None,
+            color_column: None,
+            color_map: HashMap::new(),
+            default_color: None,
+            tags_column: None,
+            frame_column: None,
+            frame_id: None,
+            key_column: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_quoted_fields() {
+        let csv = "id,title,notes\r\n1,\"Login, SSO\",\"He said \"\"hi\"\"\"\r\n2,Export,\"multi\nline\"\n";
+        let table = CsvTable::parse(csv).unwrap();
+
+        assert_eq!(table.headers, vec!["id", "title", "notes"]);
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.rows[0][1], "Login, SSO");
+        assert_eq!(table.rows[0][2], "He said \"hi\"");
+        assert_eq!(table.rows[1][2], "multi\nline");
+    }
+
+    #[test]
+    fn test_parse_skips_blank_lines_and_bom() {
+        let table = CsvTable::parse("\u{feff}title\n\nA\n\nB").unwrap();
+        assert_eq!(table.headers, vec!["title"]);
+        assert_eq!(table.rows, vec![vec!["A"], vec!["B"]]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(CsvTable::parse("").unwrap_err(), CsvError::Empty);
+        assert_eq!(
+            CsvTable::parse("title\n\"open").unwrap_err(),
+            CsvError::UnterminatedQuote(2)
+        );
+    }
+
+    #[test]
+    fn test_plan_unknown_column() {
+        let table = CsvTable::parse("title\nA").unwrap();
+        let result = plan_import(&table, &mapping("name"), &CsvLayout::default());
+        assert_eq!(
+            result.unwrap_err(),
+            CsvError::UnknownColumn("name".to_string())
+        );
+    }
+
+    #[test]
+    fn test_plan_colors_tags_and_keys() {
+        let table = CsvTable::parse(
+            "key,title,priority,labels\nF-1,Login,High,\"auth; ui\"\nF-2,Export,Low,\nF-3,,High,\nF-4,Search,red,\nF-5,Filter,Yellow,",
+        )
+        .unwrap();
+        let mut m = mapping("Title");
+        m.color_column = Some("priority".to_string());
+        m.color_map.insert("high".to_string(), "red".to_string());
+        m.default_color = Some("light_yellow".to_string());
+        m.tags_column = Some("labels".to_string());
+        m.key_column = Some("key".to_string());
+
+        let plan = plan_import(&table, &m, &CsvLayout::default()).unwrap();
+
+        assert_eq!(plan.rows.len(), 4);
+        assert_eq!(plan.rows[0].color.as_deref(), Some("red"));
+        assert_eq!(plan.rows[0].tags, vec!["auth", "ui"]);
+        assert_eq!(plan.rows[1].color.as_deref(), Some("light_yellow"));
+        assert!(plan.rows[1].tags.is_empty());
+        // Literal sticky color names pass through unmapped
+        assert_eq!(plan.rows[2].color.as_deref(), Some("red"));
+        assert_eq!(plan.rows[2].key.as_deref(), Some("F-4"));
+        assert_eq!(plan.rows[3].color.as_deref(), Some("yellow"));
+
+        assert_eq!(plan.skipped.len(), 1);
+        assert_eq!(plan.skipped[0].row, 3);
+        assert_eq!(plan.skipped[0].key.as_deref(), Some("F-3"));
+    }
+
+    #[test]
+    fn test_plan_rejects_invalid_colors() {
+        let table = CsvTable::parse("title,priority\nA,high").unwrap();
+        let mut m = mapping("title");
+        m.color_column = Some("priority".to_string());
+        m.color_map
+            .insert("high".to_string(), "crimson".to_string());
+        assert_eq!(
+            plan_import(&table, &m, &CsvLayout::default()).unwrap_err(),
+            CsvError::InvalidColor("crimson".to_string())
+        );
+
+        let mut m = mapping("title");
+        m.item_type = CsvItemType::Card;
+        m.default_color = Some("red".to_string());
+        assert_eq!(
+            plan_import(&table, &m, &CsvLayout::default()).unwrap_err(),
+            CsvError::InvalidColor("red".to_string())
+        );
+    }
+
+    #[test]
+    fn test_plan_grid_layout_per_frame() {
+        let table = CsvTable::parse("title,frame\nA,\nB,\nC,Backlog\nD,").unwrap();
+        let mut m = mapping("title");
+        m.frame_column = Some("frame".to_string());
+        let layout = CsvLayout {
+            x: 100.0,
+            y: 50.0,
+            columns: 2,
+        };
+
+        let plan = plan_import(&table, &m, &layout).unwrap();
+        let pos = |i: usize| (plan.rows[i].position.x, plan.rows[i].position.y);
+
+        assert_eq!(pos(0), (100.0, 50.0));
+        assert_eq!(pos(1), (320.0, 50.0));
+        assert_eq!(pos(3), (100.0, 270.0));
+        // First item in a frame starts at the frame's top-left corner plus padding
+        assert_eq!(plan.rows[2].frame.as_deref(), Some("Backlog"));
+        assert_eq!(pos(2), (120.0, 120.0));
+    }
+
+    #[test]
+    fn test_card_bulk_item_escapes_content() {
+        let table =
+            CsvTable::parse("title,details,color\n<b>Tom & Jerry</b>,desc,#2d9bf0").unwrap();
+        let mut m = mapping("title");
+        m.item_type = CsvItemType::Card;
+        m.description_column = Some("details".to_string());
+        m.color_column = Some("color".to_string());
+
+        let plan = plan_import(&table, &m, &CsvLayout::default()).unwrap();
+        let item = plan.rows[0].to_bulk_item(plan.item_type, Some("frame-1".to_string()));
+        let json = serde_json::to_value(&item).unwrap();
+
+        assert_eq!(json["type"], "card");
+        assert_eq!(json["data"]["title"], "&lt;b&gt;Tom &amp; Jerry&lt;/b&gt;");
+        assert_eq!(json["data"]["description"], "desc");
+        assert_eq!(json["style"]["cardTheme"], "#2d9bf0");
+        assert_eq!(json["parent"]["id"], "frame-1");
+    }
+
+    #[test]
+    fn test_report_counts_and_order() {
+        let report = CsvImportReport::from_results(
+            "board-1",
+            vec![
+                RowResult {
+                    row: 2,
+                    key: None,
+                    item_id: None,
+                    error: Some("failed".to_string()),
+                },
+                RowResult {
+                    row: 1,
+                    key: None,
+                    item_id: Some("item-1".to_string()),
+                    error: None,
+                },
+            ],
+        );
+
+        assert_eq!(report.created, 1);
+        assert_eq!(report.failed, 1);
+        assert_eq!(report.rows[0].row, 1);
+    }
+}
@@ -0,0 +1,355 @@
+//! Board diffs between snapshots or against live board state
+//!
+//! Items are matched by ID and compared on position, style, parent and text, so a
+//! diff answers "what changed on this board since the last export".
+
+use crate::miro::snapshot::{BoardSnapshot, SnapshotItem};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+#[cfg(feature = "stdio-mcp")]
+use crate::miro::client::{MiroClient, MiroError};
+
+/// Position changes smaller than this (in board units) are ignored
+const MOVE_TOLERANCE: f64 = 0.5;
+
+/// Maximum characters of item text shown in the human-readable summary
+const SUMMARY_TEXT_LIMIT: usize = 60;
+
+/// An added or removed item
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ItemRef {
+    pub id: String,
+    pub item_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// An item whose position changed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct MovedItem {
+    pub id: String,
+    pub item_type: String,
+    pub from: (f64, f64),
+    pub to: (f64, f64),
+}
+
+/// An item whose style changed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct RestyledItem {
+    pub id: String,
+    pub item_type: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// An item moved into, out of, or between frames
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ReparentedItem {
+    pub id: String,
+    pub item_type: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+/// An item whose text (content or title) changed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct TextChange {
+    pub id: String,
+    pub item_type: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// Differences between two states of a board
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct BoardDiff {
+    pub added: Vec<ItemRef>,
+    pub removed: Vec<ItemRef>,
+    pub moved: Vec<MovedItem>,
+    pub restyled: Vec<RestyledItem>,
+    pub reparented: Vec<ReparentedItem>,
+    pub text_changed: Vec<TextChange>,
+}
+
+impl BoardDiff {
+    /// Whether the two states are identical for every tracked property
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.moved.is_empty()
+            && self.restyled.is_empty()
+            && self.reparented.is_empty()
+            && self.text_changed.is_empty()
+    }
+
+    /// Human-readable summary: one count line, then one line per change
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return "No changes.".to_string();
+        }
+
+        let mut out = format!(
+            "{} added, {} removed, {} moved, {} restyled, {} re-parented, {} text changed",
+            self.added.len(),
+            self.removed.len(),
+            self.moved.len(),
+            self.restyled.len(),
+            self.reparented.len(),
+            self.text_changed.len()
+        );
+
+        let describe = |item_type: &str, id: &str, text: Option<&str>| match text {
+            Some(text) if !text.is_empty() => {
+                format!("{} {} \"{}\"", item_type, id, truncate(text))
+            }
+            _ => format!("{} {}", item_type, id),
+        };
+        let frame = |parent: &Option<String>| match parent {
+            Some(id) => format!("frame {}", id),
+            None => "board".to_string(),
+        };
+
+        for item in &self.added {
+            let _ = write!(
+                out,
+                "\n+ Added {}",
+                describe(&item.item_type, &item.id, item.text.as_deref())
+            );
+        }
+        for item in &self.removed {
+            let _ = write!(
+                out,
+                "\n- Removed {}",
+                describe(&item.item_type, &item.id, item.text.as_deref())
+            );
+        }
+        for item in &self.moved {
+            let _ = write!(
+                out,
+                "\n~ Moved {} {} from ({:.0}, {:.0}) to ({:.0}, {:.0})",
+                item.item_type, item.id, item.from.0, item.from.1, item.to.0, item.to.1
+            );
+        }
+        for item in &self.restyled {
+            let _ = write!(out, "\n~ Restyled {} {}", item.item_type, item.id);
+        }
+        for item in &self.reparented {
+            let _ = write!(
+                out,
+                "\n~ Re-parented {} {} from {} to {}",
+                item.item_type,
+                item.id,
+                frame(&item.from),
+                frame(&item.to)
+            );
+        }
+        for item in &self.text_changed {
+            let _ = write!(
+                out,
+                "\n~ Text of {} {}: \"{}\" → \"{}\"",
+                item.item_type,
+                item.id,
+                truncate(item.before.as_deref().unwrap_or("")),
+                truncate(item.after.as_deref().unwrap_or(""))
+            );
+        }
+
+        out
+    }
+}
+
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= SUMMARY_TEXT_LIMIT {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(SUMMARY_TEXT_LIMIT).collect();
+    format!("{}…", cut)
+}
+
+fn item_ref(item: &SnapshotItem) -> ItemRef {
+    ItemRef {
+        id: item.id.clone(),
+        item_type: item.content.item_type().to_string(),
+        text: item.content.text().map(str::to_string),
+    }
+}
+
+/// Compare two item lists, matching items by ID
+pub fn diff_items(before: &[SnapshotItem], after: &[SnapshotItem]) -> BoardDiff {
+    let before_by_id: HashMap<&str, &SnapshotItem> =
+        before.iter().map(|item| (item.id.as_str(), item)).collect();
+    let after_by_id: HashMap<&str, &SnapshotItem> =
+        after.iter().map(|item| (item.id.as_str(), item)).collect();
+
+    let mut diff = BoardDiff {
+        removed: before
+            .iter()
+            .filter(|item| !after_by_id.contains_key(item.id.as_str()))
+            .map(item_ref)
+            .collect(),
+        added: after
+            .iter()
+            .filter(|item| !before_by_id.contains_key(item.id.as_str()))
+            .map(item_ref)
+            .collect(),
+        ..Default::default()
+    };
+
+    for old in before {
+        let Some(new) = after_by_id.get(old.id.as_str()) else {
+            continue;
+        };
+        let item_type = new.content.item_type().to_string();
+
+        if let (Some(from), Some(to)) = (&old.position, &new.position) {
+            if (from.x - to.x).abs() > MOVE_TOLERANCE || (from.y - to.y).abs() > MOVE_TOLERANCE {
+                diff.moved.push(MovedItem {
+                    id: old.id.clone(),
+                    item_type: item_type.clone(),
+                    from: (from.x, from.y),
+                    to: (to.x, to.y),
+                });
+            }
+        }
+
+        let (style_before, style_after) = (old.content.style_value(), new.content.style_value());
+        if style_before != style_after {
+            diff.restyled.push(RestyledItem {
+                id: old.id.clone(),
+                item_type: item_type.clone(),
+                before: style_before,
+                after: style_after,
+            });
+        }
+
+        if old.parent_id != new.parent_id {
+            diff.reparented.push(ReparentedItem {
+                id: old.id.clone(),
+                item_type: item_type.clone(),
+                from: old.parent_id.clone(),
+                to: new.parent_id.clone(),
+            });
+        }
+
+        let (text_before, text_after) = (old.content.text(), new.content.text());
+        if text_before != text_after {
+            diff.text_changed.push(TextChange {
+                id: old.id.clone(),
+                item_type,
+                before: text_before.map(str::to_string),
+                after: text_after.map(str::to_string),
+            });
+        }
+    }
+
+    diff
+}
+
+/// Compare two snapshots of the same board
+pub fn diff_snapshots(before: &BoardSnapshot, after: &BoardSnapshot) -> BoardDiff {
+    diff_items(&before.items, &after.items)
+}
+
+/// Compare a snapshot against the board's current items
+#[cfg(feature = "stdio-mcp")]
+pub async fn diff_against_live(
+    client: &MiroClient,
+    board_id: &str,
+    before: &BoardSnapshot,
+) -> Result<BoardDiff, MiroError> {
+    let live: Vec<SnapshotItem> = client
+        .list_all_items(board_id, None)
+        .await?
+        .iter()
+        .map(SnapshotItem::from)
+        .collect();
+    Ok(diff_items(&before.items, &live))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::miro::types::ItemBuilder;
+    use serde_json::json;
+
+    fn item(id: &str, content: &str, color: &str, x: f64, parent: Option<&str>) -> SnapshotItem {
+        let mut item = ItemBuilder::new(id, "sticky_note")
+            .data(json!({ "content": content }))
+            .style(json!({ "fillColor": color }))
+            .position(x, 0.0);
+        if let Some(parent) = parent {
+            item = item.parent_id(parent);
+        }
+        SnapshotItem::from(&item.build())
+    }
+
+    #[test]
+    fn test_diff_identical_is_empty() {
+        let items = vec![item("a", "Idea", "yellow", 0.0, None)];
+        let diff = diff_items(&items, &items);
+
+        assert!(diff.is_empty());
+        assert_eq!(diff.summary(), "No changes.");
+    }
+
+    #[test]
+    fn test_diff_detects_each_change_kind() {
+        let before = vec![
+            item("moved", "A", "yellow", 0.0, None),
+            item("styled", "B", "yellow", 0.0, None),
+            item("nested", "C", "yellow", 0.0, None),
+            item("edited", "D", "yellow", 0.0, None),
+            item("gone", "E", "yellow", 0.0, None),
+            item("jitter", "F", "yellow", 0.0, None),
+        ];
+        let after = vec![
+            item("moved", "A", "yellow", 150.0, None),
+            item("styled", "B", "red", 0.0, None),
+            item("nested", "C", "yellow", 0.0, Some("frame-1")),
+            item("edited", "D2", "yellow", 0.0, None),
+            item("jitter", "F", "yellow", 0.2, None),
+            item("new", "G", "yellow", 0.0, None),
+        ];
+
+        let diff = diff_items(&before, &after);
+
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].id, "new");
+        assert_eq!(diff.removed[0].id, "gone");
+        assert_eq!(diff.moved.len(), 1);
+        assert_eq!(diff.moved[0].to, (150.0, 0.0));
+        assert_eq!(diff.restyled[0].id, "styled");
+        assert_eq!(diff.restyled[0].after, Some(json!({ "fillColor": "red" })));
+        assert_eq!(diff.reparented[0].to.as_deref(), Some("frame-1"));
+        assert_eq!(diff.text_changed[0].after.as_deref(), Some("D2"));
+    }
+
+    #[test]
+    fn test_diff_summary_lines() {
+        let before = vec![item("a", "Old", "yellow", 0.0, Some("frame-1"))];
+        let after = vec![
+            item("a", "New", "yellow", 0.0, None),
+            item("b", "Fresh", "yellow", 0.0, None),
+        ];
+
+        let summary = diff_items(&before, &after).summary();
+
+        assert!(summary
+            .starts_with("1 added, 0 removed, 0 moved, 0 restyled, 1 re-parented, 1 text changed"));
+        assert!(summary.contains("+ Added sticky_note b \"Fresh\""));
+        assert!(summary.contains("from frame frame-1 to board"));
+        assert!(summary.contains("\"Old\" → \"New\""));
+    }
+
+    #[test]
+    fn test_truncate_long_text() {
+        let long = "x".repeat(100);
+        let cut = truncate(&long);
+        assert_eq!(cut.chars().count(), SUMMARY_TEXT_LIMIT + 1);
+        assert!(cut.ends_with('…'));
+    }
+}
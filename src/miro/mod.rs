@@ -1,3 +1,9 @@
+pub mod csv_import;
+pub mod diff;
+pub mod progress;
+pub mod restore;
+pub mod snapshot;
+pub mod summary;
 pub mod types;
 
 #[cfg(feature = "stdio-mcp")]
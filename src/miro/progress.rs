@@ -0,0 +1,129 @@
+//! Progress reporting and cancellation for long-running operations
+//!
+//! Bulk creation, imports and exports take a [`ToolProgress`] handle. They
+//! report after each batch of Miro API calls and check for cancellation before
+//! starting the next one, so a cancelled operation stops early and returns what
+//! it already did. Transports decide where updates go: rmcp sends them as
+//! `notifications/progress` to the peer, the HTTP transport writes them to the
+//! request's event stream.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// One progress update
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgressUpdate {
+    /// Work done so far; increases with every update
+    pub progress: f64,
+    /// Total work, when known up front
+    pub total: Option<f64>,
+    pub message: Option<String>,
+}
+
+/// Receiver of progress updates
+pub type ProgressSink = Arc<dyn Fn(ProgressUpdate) + Send + Sync>;
+
+/// Check for whether the client cancelled the operation
+pub type CancelCheck = Arc<dyn Fn() -> bool + Send + Sync>;
+
+/// Progress and cancellation handle passed to long-running operations
+#[derive(Clone, Default)]
+pub struct ToolProgress {
+    sink: Option<ProgressSink>,
+    cancelled: Option<CancelCheck>,
+}
+
+impl ToolProgress {
+    /// A handle that reports nowhere and is never cancelled
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Report updates to `sink`, if the client asked for progress
+    pub fn with_sink(mut self, sink: Option<ProgressSink>) -> Self {
+        self.sink = sink;
+        self
+    }
+
+    /// Treat the operation as cancelled once `cancelled` returns true
+    pub fn with_cancel_check(mut self, cancelled: CancelCheck) -> Self {
+        self.cancelled = Some(cancelled);
+        self
+    }
+
+    /// Treat the operation as cancelled once `flag` is set
+    pub fn with_cancel_flag(self, flag: Arc<AtomicBool>) -> Self {
+        self.with_cancel_check(Arc::new(move || flag.load(Ordering::SeqCst)))
+    }
+
+    /// Whether anyone is listening for updates
+    pub fn is_reporting(&self) -> bool {
+        self.sink.is_some()
+    }
+
+    /// Report progress; a no-op when the client didn't ask for it
+    pub fn report(&self, progress: usize, total: Option<usize>, message: impl Into<String>) {
+        if let Some(sink) = &self.sink {
+            sink(ProgressUpdate {
+                progress: progress as f64,
+                total: total.map(|total| total as f64),
+                message: Some(message.into()),
+            });
+        }
+    }
+
+    /// Whether the client cancelled the operation
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.as_ref().is_some_and(|cancelled| cancelled())
+    }
+}
+
+impl fmt::Debug for ToolProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToolProgress")
+            .field("reporting", &self.is_reporting())
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_none_never_reports_or_cancels() {
+        let progress = ToolProgress::none();
+        progress.report(1, Some(2), "ignored");
+        assert!(!progress.is_reporting());
+        assert!(!progress.is_cancelled());
+    }
+
+    #[test]
+    fn test_reports_to_sink_and_observes_cancel_flag() {
+        let updates = Arc::new(Mutex::new(Vec::new()));
+        let sink_updates = updates.clone();
+        let flag = Arc::new(AtomicBool::new(false));
+        let progress = ToolProgress::none()
+            .with_sink(Some(Arc::new(move |update| {
+                sink_updates.lock().unwrap().push(update)
+            })))
+            .with_cancel_flag(flag.clone());
+
+        progress.report(20, Some(45), "Created 20 of 45 items");
+        assert!(!progress.is_cancelled());
+        flag.store(true, Ordering::SeqCst);
+        assert!(progress.is_cancelled());
+
+        assert_eq!(
+            *updates.lock().unwrap(),
+            vec![ProgressUpdate {
+                progress: 20.0,
+                total: Some(45.0),
+                message: Some("Created 20 of 45 items".to_string()),
+            }]
+        );
+    }
+}
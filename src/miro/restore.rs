@@ -0,0 +1,677 @@
+//! Restore or clone a board from a JSON snapshot
+//!
+//! Items are recreated level by level so frames exist before their children, with
+//! each `parent.id` remapped through an old→new ID map. Connectors and tags are
+//! re-linked through the same map once all items exist. Anything that can't be
+//! recreated is reported rather than aborting the restore, and item types the
+//! API can't create are reported as skipped.
+
+use crate::miro::snapshot::{BoardSnapshot, ItemContent, SnapshotItem};
+use crate::miro::types::{
+    BulkItemRequest, CardStyle, Connector, ConnectorEndpoint, FrameStyle, Geometry, Parent,
+    Position, ShapeStyle, StickyNoteStyle,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+#[cfg(feature = "stdio-mcp")]
+use crate::miro::client::{MiroClient, MiroError};
+#[cfg(feature = "stdio-mcp")]
+use crate::miro::csv_import::BULK_CHUNK_SIZE;
+#[cfg(feature = "stdio-mcp")]
+use crate::miro::progress::ToolProgress;
+#[cfg(feature = "stdio-mcp")]
+use tracing::{info, warn};
+
+/// Where restored items go
+#[derive(Debug, Clone)]
+pub enum RestoreTarget {
+    /// Add items to an existing board
+    Existing(String),
+    /// Create a new board with this name
+    New(String),
+}
+
+/// An item or connector that couldn't be recreated
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct RestoreFailure {
+    pub old_id: String,
+    pub item_type: String,
+    pub reason: String,
+}
+
+/// Outcome of a snapshot restore
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RestoreReport {
+    pub board_id: String,
+    pub items_created: usize,
+    pub connectors_created: usize,
+    pub tags_created: usize,
+    pub failed: Vec<RestoreFailure>,
+    /// Items of types that can't be recreated, left out without a request
+    pub skipped: Vec<RestoreFailure>,
+    /// Snapshot item ID → recreated item ID
+    pub id_map: HashMap<String, String>,
+    /// The restore was cancelled; counts cover only what was created before
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cancelled: bool,
+}
+
+/// How a single item is recreated
+#[derive(Debug, Clone)]
+pub enum CreateRequest {
+    /// Created through the bulk items endpoint
+    Bulk(Box<BulkItemRequest>),
+    /// Created through the type-specific endpoint, e.g. `/images`
+    Single { endpoint: String, body: Value },
+    /// Can't be recreated; the reason is reported
+    Skipped(String),
+}
+
+/// Endpoint for item types created one at a time, and the data fields to
+/// rename from the exported shape to the create shape
+fn single_endpoint(
+    item_type: &str,
+) -> Option<(&'static str, &'static [(&'static str, &'static str)])> {
+    match item_type {
+        "image" => Some(("images", &[("imageUrl", "url")])),
+        "document" => Some(("documents", &[("documentUrl", "url")])),
+        _ => None,
+    }
+}
+
+/// Group snapshot items by nesting depth so parents are created before children
+///
+/// Items whose parent isn't part of the snapshot are treated as top-level. Items
+/// caught in a parent cycle are returned separately so they can be reported.
+pub fn restore_levels(snapshot: &BoardSnapshot) -> (Vec<Vec<&SnapshotItem>>, Vec<&SnapshotItem>) {
+    let by_id: HashMap<&str, &SnapshotItem> = snapshot
+        .items
+        .iter()
+        .map(|item| (item.id.as_str(), item))
+        .collect();
+
+    let mut levels: Vec<Vec<&SnapshotItem>> = Vec::new();
+    let mut cyclic = Vec::new();
+
+    for item in &snapshot.items {
+        let mut depth = 0;
+        let mut current = item;
+        while let Some(parent) = current.parent_id.as_deref().and_then(|id| by_id.get(id)) {
+            depth += 1;
+            current = parent;
+            if depth > snapshot.items.len() {
+                break;
+            }
+        }
+
+        if depth > snapshot.items.len() {
+            cyclic.push(item);
+            continue;
+        }
+        if levels.len() <= depth {
+            levels.resize_with(depth + 1, Vec::new);
+        }
+        levels[depth].push(item);
+    }
+
+    (levels, cyclic)
+}
+
+/// Default geometry for items exported without one
+fn default_geometry(item_type: &str) -> Geometry {
+    let width = match item_type {
+        "frame" => 800.0,
+        "card" => 320.0,
+        _ => 200.0,
+    };
+    Geometry {
+        width,
+        height: (item_type == "frame").then_some(600.0),
+    }
+}
+
+/// Build the create request for a snapshot item under a (remapped) parent
+pub fn create_request(item: &SnapshotItem, parent_id: Option<String>) -> CreateRequest {
+    let item_type = item.content.item_type().to_string();
+    let parent = parent_id.map(|id| Parent { id });
+    let position = item.position.clone().unwrap_or(Position {
+        x: 0.0,
+        y: 0.0,
+        origin: None,
+    });
+    let geometry = item
+        .geometry
+        .clone()
+        .unwrap_or_else(|| default_geometry(&item_type));
+
+    let request = match &item.content {
+        ItemContent::StickyNote { data, style } => BulkItemRequest::StickyNote {
+            item_type,
+            data: data.clone(),
+            style: style.clone().unwrap_or(StickyNoteStyle {
+                fill_color: "light_yellow".to_string(),
+                extra: Default::default(),
+            }),
+            position,
+            // Sticky notes accept width or height, not both
+            geometry: Geometry {
+                width: geometry.width,
+                height: None,
+            },
+            parent,
+        },
+        ItemContent::Shape { data, style } => BulkItemRequest::Shape {
+            item_type,
+            data: data.clone(),
+            style: style.clone().unwrap_or(ShapeStyle {
+                fill_color: "#ffffff".to_string(),
+                border_color: None,
+                border_width: None,
+                extra: Default::default(),
+            }),
+            position,
+            geometry,
+            parent,
+        },
+        ItemContent::Text { data, .. } => BulkItemRequest::Text {
+            item_type,
+            data: data.clone(),
+            position,
+            geometry: Geometry {
+                width: geometry.width,
+                height: None,
+            },
+            parent,
+        },
+        ItemContent::Frame { data, style } => BulkItemRequest::Frame {
+            item_type,
+            data: data.clone(),
+            style: style.clone().unwrap_or(FrameStyle {
+                fill_color: "#ffffff".to_string(),
+                extra: Default::default(),
+            }),
+            position,
+            geometry,
+            parent,
+        },
+        ItemContent::Card { data, style } => BulkItemRequest::Card {
+            item_type,
+            data: data.clone(),
+            style: style.clone().unwrap_or(CardStyle {
+                card_theme: None,
+                extra: Default::default(),
+            }),
+            position,
+            geometry,
+            parent,
+        },
+        ItemContent::Other { data, .. } => {
+            let Some((endpoint, renames)) = single_endpoint(&item_type) else {
+                return CreateRequest::Skipped(format!(
+                    "{} items can't be recreated through the API",
+                    item_type
+                ));
+            };
+
+            let mut data = data
+                .as_ref()
+                .and_then(|data| data.as_object())
+                .cloned()
+                .unwrap_or_default();
+            for (from, to) in renames {
+                if let Some(value) = data.remove(*from) {
+                    data.insert(to.to_string(), value);
+                }
+            }
+            if !data.contains_key("url") {
+                return CreateRequest::Skipped(format!(
+                    "{} has no URL to recreate it from",
+                    item_type
+                ));
+            }
+
+            // Images and documents accept width or height, not both
+            let mut body = json!({
+                "data": data,
+                "position": position,
+                "geometry": { "width": geometry.width },
+            });
+            if let Some(parent) = parent {
+                body["parent"] = json!(parent);
+            }
+            return CreateRequest::Single {
+                endpoint: endpoint.to_string(),
+                body,
+            };
+        }
+    };
+
+    CreateRequest::Bulk(Box::new(request))
+}
+
+/// Build the create body for a connector, re-linking its ends through the ID map
+pub fn connector_body(
+    connector: &Connector,
+    id_map: &HashMap<String, String>,
+) -> Result<Value, String> {
+    let remap = |end: &Option<ConnectorEndpoint>, label: &str| {
+        let old_id = end
+            .as_ref()
+            .map(|e| e.id.as_str())
+            .ok_or_else(|| format!("connector has no {} item", label))?;
+        id_map
+            .get(old_id)
+            .cloned()
+            .ok_or_else(|| format!("{} item {} was not recreated", label, old_id))
+    };
+
+    let mut body = json!({
+        "startItem": { "id": remap(&connector.start_item, "start")? },
+        "endItem": { "id": remap(&connector.end_item, "end")? },
+    });
+    if let Some(shape) = &connector.shape {
+        body["shape"] = json!(shape);
+    }
+    if let Some(style) = &connector.style {
+        body["style"] = style.clone();
+    }
+    if let Some(captions) = &connector.captions {
+        body["captions"] = json!(captions);
+    }
+    Ok(body)
+}
+
+/// Recreate a snapshot's items, connectors and tags on a new or existing board
+///
+/// Progress counts items, connectors and tags. Once cancelled, no further
+/// objects are created and the report covers what was restored so far.
+#[cfg(feature = "stdio-mcp")]
+pub async fn restore_snapshot(
+    client: &MiroClient,
+    snapshot: &BoardSnapshot,
+    target: RestoreTarget,
+    progress: &ToolProgress,
+) -> Result<RestoreReport, MiroError> {
+    let board_id = match target {
+        RestoreTarget::Existing(board_id) => board_id,
+        RestoreTarget::New(name) => {
+            client
+                .create_board(name, snapshot.board.description.clone())
+                .await?
+                .id
+        }
+    };
+
+    let mut id_map: HashMap<String, String> = HashMap::new();
+    let mut failed = Vec::new();
+    let mut skipped = Vec::new();
+    let fail = |item: &SnapshotItem, reason: String| RestoreFailure {
+        old_id: item.id.clone(),
+        item_type: item.content.item_type().to_string(),
+        reason,
+    };
+
+    let total = snapshot.items.len() + snapshot.connectors.len() + snapshot.tags.len();
+    let mut done = 0;
+    let mut cancelled = false;
+
+    let (levels, cyclic) = restore_levels(snapshot);
+    for item in cyclic {
+        failed.push(fail(item, "parent chain contains a cycle".to_string()));
+        done += 1;
+    }
+
+    'levels: for level in levels {
+        let mut bulk = Vec::new();
+
+        for item in level {
+            if progress.is_cancelled() {
+                cancelled = true;
+                break 'levels;
+            }
+
+            let parent_id = match item.parent_id.as_deref() {
+                Some(old) if snapshot.item(old).is_some() => match id_map.get(old) {
+                    Some(new) => Some(new.clone()),
+                    None => {
+                        failed.push(fail(item, format!("parent {} was not recreated", old)));
+                        done += 1;
+                        continue;
+                    }
+                },
+                _ => None,
+            };
+
+            match create_request(item, parent_id) {
+                CreateRequest::Bulk(request) => bulk.push((item, *request)),
+                CreateRequest::Skipped(reason) => {
+                    skipped.push(fail(item, reason));
+                    done += 1;
+                }
+                CreateRequest::Single { endpoint, body } => {
+                    let path = format!("/boards/{}/{}", board_id, endpoint);
+                    match client.post(&path, Some(body)).await {
+                        Ok(created) => match created.get("id").and_then(|id| id.as_str()) {
+                            Some(new_id) => {
+                                id_map.insert(item.id.clone(), new_id.to_string());
+                            }
+                            None => failed.push(fail(item, "response had no item ID".to_string())),
+                        },
+                        Err(e) => failed.push(fail(item, e.to_string())),
+                    }
+                    done += 1;
+                    progress.report(
+                        done,
+                        Some(total),
+                        format!(
+                            "Restored {} of {} items",
+                            id_map.len(),
+                            snapshot.items.len()
+                        ),
+                    );
+                }
+            }
+        }
+
+        for chunk in bulk.chunks(BULK_CHUNK_SIZE) {
+            if progress.is_cancelled() {
+                cancelled = true;
+                break 'levels;
+            }
+
+            let requests = chunk.iter().map(|(_, request)| request.clone()).collect();
+            match client.bulk_create_items(&board_id, requests).await {
+                Ok(created) => {
+                    for ((item, _), new_item) in chunk.iter().zip(created) {
+                        id_map.insert(item.id.clone(), new_item.id);
+                    }
+                    for (item, _) in chunk {
+                        if !id_map.contains_key(&item.id) {
+                            failed.push(fail(item, "missing from bulk response".to_string()));
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!(board_id = %board_id, error = %e, "Bulk restore chunk failed");
+                    for (item, _) in chunk {
+                        failed.push(fail(item, e.to_string()));
+                    }
+                }
+            }
+            done += chunk.len();
+            progress.report(
+                done,
+                Some(total),
+                format!(
+                    "Restored {} of {} items",
+                    id_map.len(),
+                    snapshot.items.len()
+                ),
+            );
+        }
+    }
+
+    let mut connectors_created = 0;
+    for connector in &snapshot.connectors {
+        if cancelled || progress.is_cancelled() {
+            cancelled = true;
+            break;
+        }
+        done += 1;
+
+        let result = match connector_body(connector, &id_map) {
+            Ok(body) => {
+                let path = format!("/boards/{}/connectors", board_id);
+                client
+                    .post(&path, Some(body))
+                    .await
+                    .map_err(|e| e.to_string())
+            }
+            Err(reason) => Err(reason),
+        };
+        match result {
+            Ok(_) => connectors_created += 1,
+            Err(reason) => failed.push(RestoreFailure {
+                old_id: connector.id.clone(),
+                item_type: "connector".to_string(),
+                reason,
+            }),
+        }
+        progress.report(
+            done,
+            Some(total),
+            format!(
+                "Re-linked {} of {} connectors",
+                connectors_created,
+                snapshot.connectors.len()
+            ),
+        );
+    }
+
+    let mut tags_created = 0;
+    for tag in &snapshot.tags {
+        if cancelled || progress.is_cancelled() {
+            cancelled = true;
+            break;
+        }
+        done += 1;
+
+        let new_tag = match client
+            .create_tag(&board_id, tag.title.clone(), tag.fill_color.clone())
+            .await
+        {
+            Ok(new_tag) => new_tag,
+            Err(e) => {
+                failed.push(RestoreFailure {
+                    old_id: tag.id.clone(),
+                    item_type: "tag".to_string(),
+                    reason: e.to_string(),
+                });
+                continue;
+            }
+        };
+        tags_created += 1;
+
+        for old_item in &tag.item_ids {
+            let Some(new_item) = id_map.get(old_item) else {
+                continue;
+            };
+            if let Err(e) = client.attach_tag(&board_id, new_item, &new_tag.id).await {
+                warn!(tag = %tag.title, item_id = %new_item, error = %e, "Failed to attach tag");
+            }
+        }
+        progress.report(
+            done,
+            Some(total),
+            format!("Restored {} of {} tags", tags_created, snapshot.tags.len()),
+        );
+    }
+
+    info!(
+        board_id = %board_id,
+        items_created = id_map.len(),
+        connectors_created,
+        failed = failed.len(),
+        skipped = skipped.len(),
+        cancelled,
+        "Snapshot restored"
+    );
+
+    Ok(RestoreReport {
+        board_id,
+        items_created: id_map.len(),
+        connectors_created,
+        tags_created,
+        failed,
+        skipped,
+        id_map,
+        cancelled,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::miro::types::{test_board, Item, ItemBuilder};
+
+    fn snapshot(items: Vec<Item>, connectors: Vec<Connector>) -> BoardSnapshot {
+        BoardSnapshot::new(test_board("Workshop"), &items, connectors, vec![])
+    }
+
+    #[test]
+    fn test_restore_levels_parents_first() {
+        let snapshot = snapshot(
+            vec![
+                ItemBuilder::new("note", "sticky_note")
+                    .data(json!({"content": "A"}))
+                    .parent_id("inner")
+                    .build(),
+                ItemBuilder::new("inner", "frame")
+                    .data(json!({"title": "In", "type": "freeform"}))
+                    .parent_id("outer")
+                    .build(),
+                ItemBuilder::new("outer", "frame")
+                    .data(json!({"title": "Out", "type": "freeform"}))
+                    .build(),
+                ItemBuilder::new("orphan", "text")
+                    .data(json!({"content": "B"}))
+                    .parent_id("missing")
+                    .build(),
+            ],
+            vec![],
+        );
+
+        let (levels, cyclic) = restore_levels(&snapshot);
+        let ids: Vec<Vec<&str>> = levels
+            .iter()
+            .map(|level| level.iter().map(|i| i.id.as_str()).collect())
+            .collect();
+
+        assert_eq!(
+            ids,
+            vec![vec!["outer", "orphan"], vec!["inner"], vec!["note"]]
+        );
+        assert!(cyclic.is_empty());
+    }
+
+    #[test]
+    fn test_restore_levels_detects_cycles() {
+        let snapshot = snapshot(
+            vec![
+                ItemBuilder::new("a", "frame")
+                    .data(json!({"title": "A", "type": "freeform"}))
+                    .parent_id("b")
+                    .build(),
+                ItemBuilder::new("b", "frame")
+                    .data(json!({"title": "B", "type": "freeform"}))
+                    .parent_id("a")
+                    .build(),
+            ],
+            vec![],
+        );
+
+        let (levels, cyclic) = restore_levels(&snapshot);
+        assert!(levels.is_empty());
+        assert_eq!(cyclic.len(), 2);
+    }
+
+    #[test]
+    fn test_create_request_remaps_parent() {
+        let snapshot = snapshot(
+            vec![ItemBuilder::new("note", "sticky_note")
+                .data(json!({"content": "A"}))
+                .parent_id("frame-old")
+                .build()],
+            vec![],
+        );
+
+        let request = create_request(&snapshot.items[0], Some("frame-new".to_string()));
+        let CreateRequest::Bulk(bulk) = request else {
+            panic!("sticky notes should be bulk-created");
+        };
+        let json = serde_json::to_value(&bulk).unwrap();
+
+        assert_eq!(json["type"], "sticky_note");
+        assert_eq!(json["parent"]["id"], "frame-new");
+        assert_eq!(json["style"]["fillColor"], "light_yellow");
+    }
+
+    #[test]
+    fn test_create_request_image_uses_type_endpoint() {
+        let snapshot = snapshot(
+            vec![ItemBuilder::new("img", "image")
+                .data(json!({"imageUrl": "https://example.com/a.png", "title": "Logo"}))
+                .build()],
+            vec![],
+        );
+
+        match create_request(&snapshot.items[0], None) {
+            CreateRequest::Single { endpoint, body } => {
+                assert_eq!(endpoint, "images");
+                assert_eq!(
+                    body["data"],
+                    json!({"url": "https://example.com/a.png", "title": "Logo"})
+                );
+                assert!(body["geometry"].get("height").is_none());
+                assert!(body.get("parent").is_none());
+            }
+            _ => panic!("images should be created individually"),
+        }
+    }
+
+    #[test]
+    fn test_create_request_skips_unknown_types() {
+        let snapshot = snapshot(
+            vec![
+                ItemBuilder::new("embed", "embed")
+                    .data(json!({"url": "https://example.com"}))
+                    .build(),
+                ItemBuilder::new("img", "image")
+                    .data(json!({"title": "No URL"}))
+                    .build(),
+            ],
+            vec![],
+        );
+
+        for item in &snapshot.items {
+            assert!(matches!(
+                create_request(item, None),
+                CreateRequest::Skipped(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_connector_body_relinks_ends() {
+        let connector = Connector {
+            id: "conn-1".to_string(),
+            start_item: Some(ConnectorEndpoint {
+                id: "a".to_string(),
+            }),
+            end_item: Some(ConnectorEndpoint {
+                id: "b".to_string(),
+            }),
+            shape: Some("curved".to_string()),
+            style: Some(json!({"strokeColor": "#000000"})),
+            captions: None,
+            created_at: None,
+            modified_at: None,
+        };
+        let mut id_map = HashMap::new();
+        id_map.insert("a".to_string(), "a2".to_string());
+
+        assert_eq!(
+            connector_body(&connector, &id_map).unwrap_err(),
+            "end item b was not recreated"
+        );
+
+        id_map.insert("b".to_string(), "b2".to_string());
+        let body = connector_body(&connector, &id_map).unwrap();
+        assert_eq!(body["startItem"]["id"], "a2");
+        assert_eq!(body["endItem"]["id"], "b2");
+        assert_eq!(body["shape"], "curved");
+    }
+}
@@ -0,0 +1,653 @@
+//! Versioned JSON board snapshots
+//!
+//! A snapshot captures everything needed to back up, diff or clone a board:
+//! board metadata, every item with typed data and style, connectors, frames with
+//! their children, and tags with the items carrying them.
+
+use crate::miro::types::{
+    Board, CardData, CardStyle, Connector, FrameData, FrameStyle, Geometry, Item, Position,
+    ShapeData, ShapeStyle, StickyNoteData, StickyNoteStyle, Tag, TextData,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use thiserror::Error;
+
+#[cfg(feature = "stdio-mcp")]
+use crate::miro::client::{MiroClient, MiroError};
+#[cfg(feature = "stdio-mcp")]
+use crate::miro::progress::ToolProgress;
+#[cfg(feature = "stdio-mcp")]
+use tracing::info;
+
+/// Current snapshot format version
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Snapshot serialization errors
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("Unsupported snapshot version {found} (this build reads up to version {supported})")]
+    UnsupportedVersion { found: u64, supported: u32 },
+
+    #[error("Invalid snapshot JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Snapshot file error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Snapshot path '{0}' is outside the snapshot directory")]
+    OutsideSnapshotDir(String),
+
+    #[error("Snapshot file '{0}' already exists; pass overwrite to replace it")]
+    AlreadyExists(String),
+}
+
+/// Item payload, typed for the item kinds this server creates
+///
+/// Fields the typed structs don't model are carried in their `extra` maps.
+/// Items whose type is unknown, or whose payload doesn't match the typed shape,
+/// are kept verbatim in [`ItemContent::Other`] so nothing is lost on export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ItemContent {
+    StickyNote {
+        data: StickyNoteData,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        style: Option<StickyNoteStyle>,
+    },
+    Shape {
+        data: ShapeData,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        style: Option<ShapeStyle>,
+    },
+    Text {
+        data: TextData,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        style: Option<Value>,
+    },
+    Frame {
+        data: FrameData,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        style: Option<FrameStyle>,
+    },
+    Card {
+        data: CardData,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        style: Option<CardStyle>,
+    },
+    Other {
+        item_type: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        data: Option<Value>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        style: Option<Value>,
+    },
+}
+
+impl ItemContent {
+    /// Convert an API item's data and style into typed content
+    pub fn from_item(item: &Item) -> Self {
+        let typed = json!({
+            "type": item.item_type,
+            "data": item.data,
+            "style": item.style,
+        });
+
+        match serde_json::from_value::<ItemContent>(typed) {
+            Ok(ItemContent::Other { .. }) | Err(_) => ItemContent::Other {
+                item_type: item.item_type.clone(),
+                data: item.data.clone(),
+                style: item.style.clone(),
+            },
+            Ok(content) => content,
+        }
+    }
+
+    /// Miro item type (e.g. "sticky_note", "frame")
+    pub fn item_type(&self) -> &str {
+        match self {
+            ItemContent::StickyNote { .. } => "sticky_note",
+            ItemContent::Shape { .. } => "shape",
+            ItemContent::Text { .. } => "text",
+            ItemContent::Frame { .. } => "frame",
+            ItemContent::Card { .. } => "card",
+            ItemContent::Other { item_type, .. } => item_type,
+        }
+    }
+
+    /// Main text of the item: content, or title for frames and cards
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            ItemContent::StickyNote { data, .. } => Some(&data.content),
+            ItemContent::Shape { data, .. } => data.content.as_deref(),
+            ItemContent::Text { data, .. } => Some(&data.content),
+            ItemContent::Frame { data, .. } => Some(&data.title),
+            ItemContent::Card { data, .. } => data.title.as_deref(),
+            ItemContent::Other { data, .. } => data.as_ref().and_then(|d| {
+                d.get("content")
+                    .or_else(|| d.get("title"))
+                    .and_then(|v| v.as_str())
+            }),
+        }
+    }
+
+    /// Style as raw JSON, for comparisons across item kinds
+    pub fn style_value(&self) -> Option<Value> {
+        let style = match self {
+            ItemContent::StickyNote { style, .. } => serde_json::to_value(style).ok(),
+            ItemContent::Shape { style, .. } => serde_json::to_value(style).ok(),
+            ItemContent::Text { style, .. } => style.clone(),
+            ItemContent::Frame { style, .. } => serde_json::to_value(style).ok(),
+            ItemContent::Card { style, .. } => serde_json::to_value(style).ok(),
+            ItemContent::Other { style, .. } => style.clone(),
+        };
+        style.filter(|s| !s.is_null())
+    }
+}
+
+/// A board item in a snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotItem {
+    pub id: String,
+    #[serde(flatten)]
+    pub content: ItemContent,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<Position>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geometry: Option<Geometry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified_at: Option<String>,
+}
+
+impl From<&Item> for SnapshotItem {
+    fn from(item: &Item) -> Self {
+        Self {
+            id: item.id.clone(),
+            content: ItemContent::from_item(item),
+            position: item.position.clone(),
+            geometry: item.geometry.clone(),
+            parent_id: item.parent.as_ref().map(|p| p.id.clone()),
+            created_at: item.created_at.clone(),
+            modified_at: item.modified_at.clone(),
+        }
+    }
+}
+
+/// A frame and the IDs of the items it contains
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotFrame {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub children: Vec<String>,
+}
+
+/// A board tag and the IDs of the items carrying it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotTag {
+    pub id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill_color: Option<String>,
+    #[serde(default)]
+    pub item_ids: Vec<String>,
+}
+
+/// Versioned JSON snapshot of a whole board
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardSnapshot {
+    pub version: u32,
+    pub exported_at: String,
+    pub board: Board,
+    pub items: Vec<SnapshotItem>,
+    pub connectors: Vec<Connector>,
+    pub frames: Vec<SnapshotFrame>,
+    pub tags: Vec<SnapshotTag>,
+}
+
+impl BoardSnapshot {
+    /// Assemble a snapshot from API listings, deriving frame membership from item parents
+    pub fn new(
+        board: Board,
+        items: &[Item],
+        connectors: Vec<Connector>,
+        tags: Vec<(Tag, Vec<String>)>,
+    ) -> Self {
+        let items: Vec<SnapshotItem> = items.iter().map(SnapshotItem::from).collect();
+
+        let mut children: HashMap<&str, Vec<String>> = HashMap::new();
+        for item in &items {
+            if let Some(parent_id) = &item.parent_id {
+                children
+                    .entry(parent_id.as_str())
+                    .or_default()
+                    .push(item.id.clone());
+            }
+        }
+
+        let frames = items
+            .iter()
+            .filter(|item| item.content.item_type() == "frame")
+            .map(|frame| SnapshotFrame {
+                id: frame.id.clone(),
+                title: frame.content.text().map(str::to_string),
+                children: children.remove(frame.id.as_str()).unwrap_or_default(),
+            })
+            .collect();
+
+        let tags = tags
+            .into_iter()
+            .map(|(tag, item_ids)| SnapshotTag {
+                id: tag.id,
+                title: tag.title,
+                fill_color: tag.fill_color,
+                item_ids,
+            })
+            .collect();
+
+        Self {
+            version: SNAPSHOT_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            board,
+            items,
+            connectors,
+            frames,
+            tags,
+        }
+    }
+
+    /// Parse a snapshot, rejecting versions newer than this build understands
+    pub fn from_json(json: &str) -> Result<Self, SnapshotError> {
+        let value: Value = serde_json::from_str(json)?;
+        let found = value.get("version").and_then(|v| v.as_u64()).unwrap_or(0);
+        let supported = u32::try_from(found)
+            .ok()
+            .is_some_and(|version| (1..=SNAPSHOT_VERSION).contains(&version));
+        if !supported {
+            return Err(SnapshotError::UnsupportedVersion {
+                found,
+                supported: SNAPSHOT_VERSION,
+            });
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Serialize as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String, SnapshotError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Read a snapshot file
+    pub fn read_from_file(path: impl AsRef<Path>) -> Result<Self, SnapshotError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Write the snapshot to a file as pretty-printed JSON
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// Look up an item by ID
+    pub fn item(&self, id: &str) -> Option<&SnapshotItem> {
+        self.items.iter().find(|item| item.id == id)
+    }
+}
+
+/// Directory that tool-supplied snapshot paths are confined to
+///
+/// Paths are taken relative to the root. `..` components are rejected, and
+/// absolute paths or symlinks are only accepted when they resolve inside it.
+#[derive(Debug, Clone)]
+pub struct SnapshotDir {
+    root: PathBuf,
+}
+
+impl SnapshotDir {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Resolve a requested path to a file inside the root, creating the root if needed
+    ///
+    /// The file itself need not exist, but its directory must.
+    pub fn resolve(&self, path: &str) -> Result<PathBuf, SnapshotError> {
+        let outside = || SnapshotError::OutsideSnapshotDir(path.to_string());
+        let requested = Path::new(path);
+        if requested
+            .components()
+            .any(|component| component == Component::ParentDir)
+        {
+            return Err(outside());
+        }
+        let file_name = requested.file_name().ok_or_else(outside)?;
+
+        std::fs::create_dir_all(&self.root)?;
+        let root = self.root.canonicalize()?;
+        let joined = root.join(requested);
+        let dir = joined.parent().unwrap_or(&root).canonicalize()?;
+        if !dir.starts_with(&root) {
+            return Err(outside());
+        }
+
+        let resolved = dir.join(file_name);
+        // An existing symlink may still point elsewhere
+        match resolved.canonicalize() {
+            Ok(target) if !target.starts_with(&root) => Err(outside()),
+            _ => Ok(resolved),
+        }
+    }
+
+    /// Read a snapshot file inside the root
+    pub fn read(&self, path: &str) -> Result<BoardSnapshot, SnapshotError> {
+        BoardSnapshot::read_from_file(self.resolve(path)?)
+    }
+
+    /// Write a snapshot file inside the root, returning where it was written
+    ///
+    /// Fails with [`SnapshotError::AlreadyExists`] rather than replace an
+    /// existing file unless `overwrite` is set.
+    pub fn write(
+        &self,
+        path: &str,
+        snapshot: &BoardSnapshot,
+        overwrite: bool,
+    ) -> Result<PathBuf, SnapshotError> {
+        let resolved = self.resolve(path)?;
+        let json = snapshot.to_json()?;
+        if overwrite {
+            std::fs::write(&resolved, json)?;
+        } else {
+            let mut file = OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&resolved)
+                .map_err(|e| match e.kind() {
+                    std::io::ErrorKind::AlreadyExists => {
+                        SnapshotError::AlreadyExists(path.to_string())
+                    }
+                    _ => SnapshotError::Io(e),
+                })?;
+            file.write_all(json.as_bytes())?;
+        }
+        Ok(resolved)
+    }
+}
+
+/// Export a board to a snapshot, walking every page of items and connectors
+///
+/// Progress is reported per fetch step and per tag. A snapshot missing parts of
+/// the board would be misleading, so cancellation fails the export with
+/// [`MiroError::Cancelled`] describing how far it got.
+#[cfg(feature = "stdio-mcp")]
+pub async fn export_board(
+    client: &MiroClient,
+    board_id: &str,
+    progress: &ToolProgress,
+) -> Result<BoardSnapshot, MiroError> {
+    let cancelled = |stage: String| {
+        info!(board_id = %board_id, stage = %stage, "Board export cancelled");
+        MiroError::Cancelled(format!(
+            "export of board {} stopped after {}",
+            board_id, stage
+        ))
+    };
+
+    let board = client.get_board(board_id).await?;
+    progress.report(1, None, "Fetched board metadata");
+    if progress.is_cancelled() {
+        return Err(cancelled("fetching board metadata".to_string()));
+    }
+
+    let items = client.list_all_items(board_id, None).await?;
+    progress.report(2, None, format!("Fetched {} items", items.len()));
+    if progress.is_cancelled() {
+        return Err(cancelled(format!("fetching {} items", items.len())));
+    }
+
+    let connectors = client.list_connectors(board_id).await?;
+    progress.report(3, None, format!("Fetched {} connectors", connectors.len()));
+
+    let board_tags = client.list_tags(board_id).await?;
+    let total = 3 + board_tags.len();
+    let mut tags = Vec::new();
+    for tag in board_tags {
+        if progress.is_cancelled() {
+            return Err(cancelled(format!(
+                "fetching {} items, {} connectors and {} tags",
+                items.len(),
+                connectors.len(),
+                tags.len()
+            )));
+        }
+
+        let item_ids = client
+            .list_items_by_tag(board_id, &tag.id)
+            .await?
+            .into_iter()
+            .map(|item| item.id)
+            .collect();
+        tags.push((tag, item_ids));
+        progress.report(
+            3 + tags.len(),
+            Some(total),
+            format!("Fetched {} of {} tags", tags.len(), total - 3),
+        );
+    }
+
+    let snapshot = BoardSnapshot::new(board, &items, connectors, tags);
+    info!(
+        board_id = %board_id,
+        items = snapshot.items.len(),
+        connectors = snapshot.connectors.len(),
+        frames = snapshot.frames.len(),
+        tags = snapshot.tags.len(),
+        "Board exported"
+    );
+    Ok(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::miro::types::{test_board, ItemBuilder};
+
+    fn sample_items() -> Vec<Item> {
+        vec![
+            ItemBuilder::new("frame-1", "frame")
+                .data(json!({"title": "Q1", "type": "freeform"}))
+                .style(json!({"fillColor": "#ffffff"}))
+                .build(),
+            ItemBuilder::new("note-1", "sticky_note")
+                .data(json!({"content": "<p>Ship it</p>", "shape": "square"}))
+                .style(json!({"fillColor": "yellow"}))
+                .parent_id("frame-1")
+                .build(),
+            ItemBuilder::new("img-1", "image")
+                .data(json!({"imageUrl": "https://example.com/a.png"}))
+                .style(json!({}))
+                .build(),
+        ]
+    }
+
+    #[test]
+    fn test_item_content_typed_and_fallback() {
+        let items = sample_items();
+
+        let note = ItemContent::from_item(&items[1]);
+        assert!(matches!(note, ItemContent::StickyNote { .. }));
+        assert_eq!(note.text(), Some("<p>Ship it</p>"));
+        assert_eq!(note.style_value(), Some(json!({"fillColor": "yellow"})));
+
+        let image = ItemContent::from_item(&items[2]);
+        assert!(matches!(image, ItemContent::Other { .. }));
+        assert_eq!(image.item_type(), "image");
+    }
+
+    #[test]
+    fn test_snapshot_frames_and_tags() {
+        let tag = Tag {
+            id: "tag-1".to_string(),
+            title: "urgent".to_string(),
+            fill_color: Some("red".to_string()),
+        };
+        let snapshot = BoardSnapshot::new(
+            test_board("Roadmap"),
+            &sample_items(),
+            vec![],
+            vec![(tag, vec!["note-1".to_string()])],
+        );
+
+        assert_eq!(snapshot.version, SNAPSHOT_VERSION);
+        assert_eq!(snapshot.items.len(), 3);
+        assert_eq!(snapshot.frames.len(), 1);
+        assert_eq!(snapshot.frames[0].title.as_deref(), Some("Q1"));
+        assert_eq!(snapshot.frames[0].children, vec!["note-1"]);
+        assert_eq!(snapshot.tags[0].item_ids, vec!["note-1"]);
+        assert_eq!(
+            snapshot.item("note-1").unwrap().parent_id.as_deref(),
+            Some("frame-1")
+        );
+    }
+
+    #[test]
+    fn test_snapshot_json_roundtrip() {
+        let snapshot = BoardSnapshot::new(test_board("Roadmap"), &sample_items(), vec![], vec![]);
+        let json = snapshot.to_json().unwrap();
+
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["items"][1]["type"], "sticky_note");
+        assert_eq!(value["items"][1]["data"]["content"], "<p>Ship it</p>");
+        assert_eq!(value["items"][2]["type"], "other");
+        assert_eq!(value["items"][2]["item_type"], "image");
+
+        let restored = BoardSnapshot::from_json(&json).unwrap();
+        assert_eq!(restored.items.len(), 3);
+        assert_eq!(restored.items[2].content.item_type(), "image");
+    }
+
+    #[test]
+    fn test_full_item_payload_roundtrip() {
+        let data = json!({"content": "<p>Ship it</p>", "shape": "square"});
+        let style = json!({
+            "fillColor": "light_yellow",
+            "textAlign": "center",
+            "textAlignVertical": "top"
+        });
+        let note = ItemBuilder::new("note-1", "sticky_note")
+            .data(data.clone())
+            .style(style.clone())
+            .build();
+        let shape_style = json!({
+            "fillColor": "#ff0000",
+            "fillOpacity": "0.5",
+            "borderColor": "#1a1a1a",
+            "borderOpacity": "1.0",
+            "borderStyle": "dashed",
+            "borderWidth": "2.0",
+            "color": "#ffffff",
+            "fontFamily": "arial",
+            "fontSize": "14",
+            "textAlign": "left",
+            "textAlignVertical": "middle"
+        });
+        let shape = ItemBuilder::new("shape-1", "shape")
+            .data(json!({"content": "Box", "shape": "round_rectangle"}))
+            .style(shape_style.clone())
+            .build();
+
+        let snapshot = BoardSnapshot::new(test_board("Roadmap"), &[note, shape], vec![], vec![]);
+        let restored = BoardSnapshot::from_json(&snapshot.to_json().unwrap()).unwrap();
+
+        assert!(matches!(
+            restored.items[1].content,
+            ItemContent::Shape { .. }
+        ));
+        let value = serde_json::to_value(&restored.items).unwrap();
+        assert_eq!(value[0]["type"], "sticky_note");
+        assert_eq!(value[0]["data"], data);
+        assert_eq!(value[0]["style"], style);
+        assert_eq!(value[1]["type"], "shape");
+        assert_eq!(value[1]["style"], shape_style);
+    }
+
+    #[test]
+    fn test_snapshot_dir_confines_paths() {
+        let root = std::env::temp_dir().join(format!("miro-snapshots-{}", uuid::Uuid::new_v4()));
+        let dir = SnapshotDir::new(&root);
+        let snapshot = BoardSnapshot::new(test_board("Roadmap"), &sample_items(), vec![], vec![]);
+
+        let written = dir.write("backup.json", &snapshot, false).unwrap();
+        assert!(written.starts_with(root.canonicalize().unwrap()));
+        assert_eq!(dir.read("backup.json").unwrap().items.len(), 3);
+
+        assert!(matches!(
+            dir.write("backup.json", &snapshot, false),
+            Err(SnapshotError::AlreadyExists(_))
+        ));
+        assert!(dir.write("backup.json", &snapshot, true).is_ok());
+
+        // Absolute paths are accepted only inside the root
+        let inside = written.display().to_string();
+        assert!(dir.read(&inside).is_ok());
+        for escape in [
+            "../escape.json",
+            "nested/../../escape.json",
+            "/etc/passwd",
+            "",
+        ] {
+            assert!(
+                matches!(
+                    dir.resolve(escape),
+                    Err(SnapshotError::OutsideSnapshotDir(_))
+                ),
+                "{} should be rejected",
+                escape
+            );
+        }
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink("/etc", root.join("link")).unwrap();
+            assert!(matches!(
+                dir.resolve("link/passwd"),
+                Err(SnapshotError::OutsideSnapshotDir(_))
+            ));
+        }
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_snapshot_rejects_unknown_version() {
+        let snapshot = BoardSnapshot::new(test_board("Roadmap"), &[], vec![], vec![]);
+        let mut value = serde_json::to_value(&snapshot).unwrap();
+        value["version"] = json!(SNAPSHOT_VERSION + 1);
+
+        let err = BoardSnapshot::from_json(&value.to_string()).unwrap_err();
+        assert!(matches!(
+            err,
+            SnapshotError::UnsupportedVersion { found, .. } if found == u64::from(SNAPSHOT_VERSION) + 1
+        ));
+    }
+
+    #[test]
+    fn test_snapshot_rejects_version_overflowing_u32() {
+        let snapshot = BoardSnapshot::new(test_board("Roadmap"), &[], vec![], vec![]);
+        let mut value = serde_json::to_value(&snapshot).unwrap();
+        value["version"] = json!(u64::from(u32::MAX) + 2);
+
+        let err = BoardSnapshot::from_json(&value.to_string()).unwrap_err();
+        assert!(matches!(
+            err,
+            SnapshotError::UnsupportedVersion { found, .. } if found == u64::from(u32::MAX) + 2
+        ));
+    }
+}
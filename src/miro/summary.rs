@@ -0,0 +1,421 @@
+//! Compact board summaries for LLM context
+//!
+//! Renders a snapshot as a hierarchical outline instead of raw item JSON: frames
+//! become sections, sticky notes are grouped by color or tag, and connectors are
+//! listed as "A → B". Item HTML is stripped, and output can be capped to an
+//! approximate token budget.
+
+use crate::miro::snapshot::{BoardSnapshot, ItemContent, SnapshotItem};
+use crate::miro::types::ConnectorEndpoint;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Rough characters-per-token ratio used for budget estimates
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Maximum characters of item text used in connector labels
+const LABEL_LIMIT: usize = 40;
+
+/// Characters reserved for the notice appended to truncated output
+const NOTICE_RESERVE: usize = 64;
+
+/// How sticky notes are grouped within a section
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryGroupBy {
+    #[default]
+    Color,
+    Tag,
+}
+
+/// Output flavor
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryFormat {
+    #[default]
+    Markdown,
+    Plain,
+}
+
+/// Rendering options for [`render_summary`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SummaryOptions {
+    #[serde(default)]
+    pub group_by: SummaryGroupBy,
+    #[serde(default)]
+    pub format: SummaryFormat,
+    /// Approximate maximum output size in tokens
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+}
+
+/// Convert item HTML content to plain text
+///
+/// Tags are dropped (block tags and `<br>` become spaces), common entities are
+/// decoded and whitespace is collapsed.
+pub fn strip_html(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                text.push(' ');
+            }
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    let decoded = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+
+    decoded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn truncate(text: &str, limit: usize) -> String {
+    if text.chars().count() <= limit {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(limit).collect();
+    format!("{}…", cut)
+}
+
+/// Plain text of an item, if it has any
+fn item_text(item: &SnapshotItem) -> Option<String> {
+    let text = match &item.content {
+        ItemContent::Card { data, .. } => match (&data.title, &data.description) {
+            (Some(title), Some(description)) => {
+                format!("{} — {}", strip_html(title), strip_html(description))
+            }
+            (Some(title), None) => strip_html(title),
+            (None, Some(description)) => strip_html(description),
+            (None, None) => String::new(),
+        },
+        content => strip_html(content.text().unwrap_or("")),
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+/// Sticky note color, or "default" when the style wasn't captured
+fn sticky_color(item: &SnapshotItem) -> String {
+    match &item.content {
+        ItemContent::StickyNote {
+            style: Some(style), ..
+        } => style.fill_color.clone(),
+        _ => "default".to_string(),
+    }
+}
+
+struct Renderer<'a> {
+    snapshot: &'a BoardSnapshot,
+    options: &'a SummaryOptions,
+    children: HashMap<&'a str, Vec<&'a SnapshotItem>>,
+    tags_by_item: HashMap<&'a str, Vec<&'a str>>,
+    lines: Vec<String>,
+}
+
+impl<'a> Renderer<'a> {
+    fn new(snapshot: &'a BoardSnapshot, options: &'a SummaryOptions) -> Self {
+        let mut children: HashMap<&str, Vec<&SnapshotItem>> = HashMap::new();
+        for item in &snapshot.items {
+            let parent = item
+                .parent_id
+                .as_deref()
+                .filter(|id| snapshot.item(id).is_some())
+                .unwrap_or("");
+            children.entry(parent).or_default().push(item);
+        }
+
+        let mut tags_by_item: HashMap<&str, Vec<&str>> = HashMap::new();
+        for tag in &snapshot.tags {
+            for item_id in &tag.item_ids {
+                tags_by_item
+                    .entry(item_id.as_str())
+                    .or_default()
+                    .push(tag.title.as_str());
+            }
+        }
+
+        Self {
+            snapshot,
+            options,
+            children,
+            tags_by_item,
+            lines: Vec::new(),
+        }
+    }
+
+    fn heading(&mut self, level: usize, text: &str) {
+        let line = match self.options.format {
+            SummaryFormat::Markdown => format!("{} {}", "#".repeat(level.min(6)), text),
+            SummaryFormat::Plain => format!("{}{}", "  ".repeat(level.saturating_sub(1)), text),
+        };
+        if !self.lines.is_empty() {
+            self.lines.push(String::new());
+        }
+        self.lines.push(line);
+    }
+
+    fn bullet(&mut self, level: usize, text: &str) {
+        let line = match self.options.format {
+            SummaryFormat::Markdown => format!("- {}", text),
+            SummaryFormat::Plain => format!("{}{}", "  ".repeat(level), text),
+        };
+        self.lines.push(line);
+    }
+
+    fn render(mut self) -> Vec<String> {
+        let board = &self.snapshot.board;
+        self.heading(1, &board.name);
+        if let Some(description) = board.description.as_deref().filter(|d| !d.is_empty()) {
+            self.lines.push(strip_html(description));
+        }
+
+        self.render_section("", 1);
+
+        let connections: Vec<String> = self
+            .snapshot
+            .connectors
+            .iter()
+            .map(|connector| {
+                let label = |end: &Option<ConnectorEndpoint>| {
+                    end.as_ref()
+                        .map(|e| self.label(&e.id))
+                        .unwrap_or_else(|| "?".to_string())
+                };
+                let caption = connector
+                    .captions
+                    .as_ref()
+                    .and_then(|c| c.first())
+                    .map(|c| format!(" ({})", strip_html(&c.content)))
+                    .unwrap_or_default();
+                format!(
+                    "{} → {}{}",
+                    label(&connector.start_item),
+                    label(&connector.end_item),
+                    caption
+                )
+            })
+            .collect();
+        if !connections.is_empty() {
+            self.heading(2, "Connections");
+            for connection in connections {
+                self.bullet(2, &connection);
+            }
+        }
+
+        self.lines
+    }
+
+    /// Short label for an item referenced by a connector
+    fn label(&self, id: &str) -> String {
+        match self.snapshot.item(id) {
+            Some(item) => match item_text(item) {
+                Some(text) => truncate(&text, LABEL_LIMIT),
+                None => format!("{} {}", item.content.item_type(), id),
+            },
+            None => id.to_string(),
+        }
+    }
+
+    /// Render the items directly under `parent` ("" for the board), then nested frames
+    fn render_section(&mut self, parent: &str, level: usize) {
+        let items = self.children.get(parent).cloned().unwrap_or_default();
+        let (frames, items): (Vec<_>, Vec<_>) = items
+            .into_iter()
+            .partition(|item| item.content.item_type() == "frame");
+        let (stickies, others): (Vec<_>, Vec<_>) = items
+            .into_iter()
+            .partition(|item| item.content.item_type() == "sticky_note");
+
+        if parent.is_empty() && !(stickies.is_empty() && others.is_empty()) {
+            self.heading(level + 1, "Board");
+        }
+
+        let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for sticky in &stickies {
+            let Some(text) = item_text(sticky) else {
+                continue;
+            };
+            let group = match self.options.group_by {
+                SummaryGroupBy::Color => sticky_color(sticky),
+                SummaryGroupBy::Tag => self
+                    .tags_by_item
+                    .get(sticky.id.as_str())
+                    .and_then(|tags| tags.first())
+                    .map(|tag| tag.to_string())
+                    .unwrap_or_else(|| "untagged".to_string()),
+            };
+            groups.entry(group).or_default().push(text);
+        }
+
+        for (group, texts) in groups {
+            let line = format!("{} ({})", group, texts.len());
+            match self.options.format {
+                SummaryFormat::Markdown => self.lines.push(format!("**{}**", line)),
+                SummaryFormat::Plain => self.bullet(level, &line),
+            }
+            for text in texts {
+                self.bullet(level + 1, &text);
+            }
+        }
+
+        for item in others {
+            if let Some(text) = item_text(item) {
+                self.bullet(level, &format!("[{}] {}", item.content.item_type(), text));
+            }
+        }
+
+        for frame in frames {
+            let title = item_text(frame).unwrap_or_else(|| format!("Frame {}", frame.id));
+            self.heading(level + 1, &title);
+            self.render_section(&frame.id, level + 1);
+        }
+    }
+}
+
+/// Render a board snapshot as a compact outline
+pub fn render_summary(snapshot: &BoardSnapshot, options: &SummaryOptions) -> String {
+    let lines = Renderer::new(snapshot, options).render();
+
+    let Some(max_tokens) = options.max_tokens else {
+        return lines.join("\n");
+    };
+
+    let budget = max_tokens.saturating_mul(CHARS_PER_TOKEN);
+    let full = lines.join("\n");
+    if full.len() <= budget {
+        return full;
+    }
+
+    // Keep whole lines, leaving room for the truncation notice
+    let available = budget.saturating_sub(NOTICE_RESERVE);
+    let mut used = 0;
+    let mut kept = 0;
+    for line in &lines {
+        if kept > 0 && used + line.len() + 1 > available {
+            break;
+        }
+        used += line.len() + 1;
+        kept += 1;
+    }
+
+    format!(
+        "{}\n… {} more line(s) omitted to fit the {}-token budget",
+        lines[..kept].join("\n"),
+        lines.len() - kept,
+        max_tokens
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::miro::types::{test_board, Connector, ItemBuilder, Tag};
+    use serde_json::json;
+
+    fn snapshot() -> BoardSnapshot {
+        let board = test_board("Retro");
+        let items = vec![
+            ItemBuilder::new("f1", "frame")
+                .data(json!({"title": "Went well", "type": "freeform"}))
+                .style(json!({"fillColor": "#ffffff"}))
+                .build(),
+            ItemBuilder::new("s1", "sticky_note")
+                .data(json!({"content": "<p>Fast <b>deploys</b></p>"}))
+                .style(json!({"fillColor": "green"}))
+                .parent_id("f1")
+                .build(),
+            ItemBuilder::new("s2", "sticky_note")
+                .data(json!({"content": "<p>Pairing &amp; reviews</p>"}))
+                .style(json!({"fillColor": "green"}))
+                .parent_id("f1")
+                .build(),
+            ItemBuilder::new("s3", "sticky_note")
+                .data(json!({"content": "Flaky CI"}))
+                .style(json!({"fillColor": "red"}))
+                .build(),
+        ];
+        let connectors = vec![Connector {
+            id: "c1".to_string(),
+            start_item: Some(ConnectorEndpoint {
+                id: "s3".to_string(),
+            }),
+            end_item: Some(ConnectorEndpoint {
+                id: "s1".to_string(),
+            }),
+            shape: None,
+            style: None,
+            captions: None,
+            created_at: None,
+            modified_at: None,
+        }];
+        let tags = vec![(
+            Tag {
+                id: "t1".to_string(),
+                title: "infra".to_string(),
+                fill_color: None,
+            },
+            vec!["s1".to_string()],
+        )];
+        BoardSnapshot::new(board, &items, connectors, tags)
+    }
+
+    #[test]
+    fn test_strip_html() {
+        assert_eq!(
+            strip_html("<p>Hello <b>world</b></p><p>&lt;3 &amp; more</p>"),
+            "Hello world <3 & more"
+        );
+        assert_eq!(strip_html("line<br/>break"), "line break");
+    }
+
+    #[test]
+    fn test_render_markdown_by_color() {
+        let summary = render_summary(&snapshot(), &SummaryOptions::default());
+
+        assert!(summary.starts_with("# Retro"));
+        assert!(summary.contains("## Board\n**red (1)**\n- Flaky CI"));
+        assert!(
+            summary.contains("## Went well\n**green (2)**\n- Fast deploys\n- Pairing & reviews")
+        );
+        assert!(summary.contains("## Connections\n- Flaky CI → Fast deploys"));
+        assert!(!summary.contains("<p>"));
+    }
+
+    #[test]
+    fn test_render_plain_by_tag() {
+        let options = SummaryOptions {
+            group_by: SummaryGroupBy::Tag,
+            format: SummaryFormat::Plain,
+            max_tokens: None,
+        };
+        let summary = render_summary(&snapshot(), &options);
+
+        assert!(!summary.contains('#'));
+        assert!(summary.contains("infra (1)"));
+        assert!(summary.contains("untagged (1)"));
+    }
+
+    #[test]
+    fn test_render_respects_token_budget() {
+        let full = render_summary(&snapshot(), &SummaryOptions::default());
+        let options = SummaryOptions {
+            max_tokens: Some(25),
+            ..Default::default()
+        };
+        let capped = render_summary(&snapshot(), &options);
+
+        assert!(capped.len() < full.len());
+        assert!(capped.starts_with("# Retro"));
+        assert!(capped.contains("omitted to fit the 25-token budget"));
+    }
+}
@@ -1,7 +1,8 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Represents a parent frame reference
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Parent {
     pub id: String,
 }
@@ -13,9 +14,21 @@ pub struct Board {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(alias = "createdAt")]
     pub created_at: String,
 }
 
+/// Board fixture for unit tests
+#[cfg(test)]
+pub(crate) fn test_board(name: &str) -> Board {
+    Board {
+        id: "board-1".to_string(),
+        name: name.to_string(),
+        description: None,
+        created_at: "2025-01-01T00:00:00Z".to_string(),
+    }
+}
+
 /// API response for list boards endpoint
 #[derive(Debug, Deserialize)]
 pub struct BoardsResponse {
@@ -43,7 +56,7 @@ pub struct CreateBoardResponse {
 }
 
 /// Position for visual elements
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Position {
     pub x: f64,
     pub y: f64,
@@ -52,7 +65,7 @@ pub struct Position {
 }
 
 /// Geometry dimensions for visual elements
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Geometry {
     pub width: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -65,6 +78,9 @@ pub struct StickyNoteData {
     pub content: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub shape: Option<String>,
+    /// Fields this server doesn't model, kept so exports and restores are lossless
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Sticky note style configuration
@@ -72,6 +88,9 @@ pub struct StickyNoteData {
 pub struct StickyNoteStyle {
     #[serde(rename = "fillColor")]
     pub fill_color: String,
+    /// Other API fields, passed through unchanged
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Request body for creating a sticky note
@@ -105,6 +124,9 @@ pub struct ShapeData {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
     pub shape: String,
+    /// Other API fields, passed through unchanged
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Shape style configuration
@@ -116,6 +138,9 @@ pub struct ShapeStyle {
     pub border_color: Option<String>,
     #[serde(rename = "borderWidth", skip_serializing_if = "Option::is_none")]
     pub border_width: Option<String>,
+    /// Other API fields, passed through unchanged
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Request body for creating a shape
@@ -147,6 +172,9 @@ pub struct ShapeResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TextData {
     pub content: String,
+    /// Other API fields, passed through unchanged
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Request body for creating text
@@ -177,6 +205,9 @@ pub struct FrameData {
     pub title: String,
     #[serde(rename = "type")]
     pub frame_type: String,
+    /// Other API fields, passed through unchanged
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Frame style configuration
@@ -184,6 +215,9 @@ pub struct FrameData {
 pub struct FrameStyle {
     #[serde(rename = "fillColor")]
     pub fill_color: String,
+    /// Other API fields, passed through unchanged
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Request body for creating a frame
@@ -211,6 +245,51 @@ pub struct FrameResponse {
     pub geometry: Option<Geometry>,
 }
 
+/// Card data payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Other API fields, passed through unchanged
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Card style configuration (theme is a hex color, e.g. "#2d9bf0")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardStyle {
+    #[serde(rename = "cardTheme", skip_serializing_if = "Option::is_none")]
+    pub card_theme: Option<String>,
+    /// Other API fields, passed through unchanged
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Board tag (attachable to sticky notes and cards)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tag {
+    pub id: String,
+    pub title: String,
+    #[serde(rename = "fillColor", skip_serializing_if = "Option::is_none")]
+    pub fill_color: Option<String>,
+}
+
+/// Request body for creating a tag
+#[derive(Debug, Serialize)]
+pub struct CreateTagRequest {
+    pub title: String,
+    #[serde(rename = "fillColor", skip_serializing_if = "Option::is_none")]
+    pub fill_color: Option<String>,
+}
+
+/// Response for list tags endpoint
+#[derive(Debug, Deserialize)]
+pub struct TagsResponse {
+    pub data: Vec<Tag>,
+}
+
 /// Connector style configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConnectorStyle {
@@ -259,8 +338,35 @@ pub struct ConnectorResponse {
     pub captions: Option<Vec<Caption>>,
 }
 
-/// Generic item response that can represent any item type
+/// Item reference at one end of a connector
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectorEndpoint {
+    pub id: String,
+}
+
+/// Connector as returned by the list connectors endpoint
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Connector {
+    pub id: String,
+    #[serde(rename = "startItem", skip_serializing_if = "Option::is_none")]
+    pub start_item: Option<ConnectorEndpoint>,
+    #[serde(rename = "endItem", skip_serializing_if = "Option::is_none")]
+    pub end_item: Option<ConnectorEndpoint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shape: Option<String>,
+    /// Raw style object (the API reports numeric style fields as strings)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub captions: Option<Vec<Caption>>,
+    #[serde(rename = "createdAt", skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(rename = "modifiedAt", skip_serializing_if = "Option::is_none")]
+    pub modified_at: Option<String>,
+}
+
+/// Generic item response that can represent any item type
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Item {
     pub id: String,
     #[serde(rename = "type")]
@@ -281,6 +387,62 @@ pub struct Item {
     pub parent: Option<Parent>,
 }
 
+/// Builder for [`Item`] fixtures in unit tests; fields not set stay `None`
+#[cfg(test)]
+pub(crate) struct ItemBuilder {
+    item: Item,
+}
+
+#[cfg(test)]
+impl ItemBuilder {
+    pub(crate) fn new(id: &str, item_type: &str) -> Self {
+        Self {
+            item: Item {
+                id: id.to_string(),
+                item_type: item_type.to_string(),
+                data: None,
+                style: None,
+                position: None,
+                geometry: None,
+                created_at: None,
+                modified_at: None,
+                parent: None,
+            },
+        }
+    }
+
+    pub(crate) fn data(mut self, data: serde_json::Value) -> Self {
+        self.item.data = Some(data);
+        self
+    }
+
+    pub(crate) fn style(mut self, style: serde_json::Value) -> Self {
+        self.item.style = Some(style);
+        self
+    }
+
+    pub(crate) fn position(mut self, x: f64, y: f64) -> Self {
+        self.item.position = Some(Position { x, y, origin: None });
+        self
+    }
+
+    pub(crate) fn modified_at(mut self, modified_at: &str) -> Self {
+        self.item.modified_at = Some(modified_at.to_string());
+        self
+    }
+
+    pub(crate) fn parent_id(mut self, parent_id: &str) -> Self {
+        self.item.parent = Some(Parent {
+            id: parent_id.to_string(),
+        });
+        self
+    }
+
+    pub(crate) fn build(self) -> Item {
+        self.item
+    }
+}
+
 /// Response for list items endpoint
 #[derive(Debug, Deserialize)]
 pub struct ItemsResponse {
@@ -351,6 +513,17 @@ pub enum BulkItemRequest {
         #[serde(skip_serializing_if = "Option::is_none")]
         parent: Option<Parent>,
     },
+    /// Card item
+    Card {
+        #[serde(rename = "type")]
+        item_type: String, // must be "card"
+        data: CardData,
+        style: CardStyle,
+        position: Position,
+        geometry: Geometry,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parent: Option<Parent>,
+    },
 }
 
 /// Request body for bulk creating items
@@ -428,9 +601,11 @@ mod tests {
             data: StickyNoteData {
                 content: "<p>Test note</p>".to_string(),
                 shape: Some("square".to_string()),
+                extra: Default::default(),
             },
             style: StickyNoteStyle {
                 fill_color: "light_yellow".to_string(),
+                extra: Default::default(),
             },
             position: Position {
                 x: 100.0,
@@ -457,11 +632,13 @@ mod tests {
             data: ShapeData {
                 content: Some("<p>Shape text</p>".to_string()),
                 shape: "rectangle".to_string(),
+                extra: Default::default(),
             },
             style: ShapeStyle {
                 fill_color: "light_blue".to_string(),
                 border_color: Some("blue".to_string()),
                 border_width: Some("2".to_string()),
+                extra: Default::default(),
             },
             position: Position {
                 x: 0.0,
@@ -487,6 +664,7 @@ mod tests {
         let request = CreateTextRequest {
             data: TextData {
                 content: "Plain text content".to_string(),
+                extra: Default::default(),
             },
             position: Position {
                 x: 50.0,
@@ -511,9 +689,11 @@ mod tests {
             data: FrameData {
                 title: "Frame Title".to_string(),
                 frame_type: "frame".to_string(),
+                extra: Default::default(),
             },
             style: FrameStyle {
                 fill_color: "light_gray".to_string(),
+                extra: Default::default(),
             },
             position: Position {
                 x: 0.0,
@@ -762,9 +942,11 @@ mod tests {
             data: StickyNoteData {
                 content: "<p>Test note</p>".to_string(),
                 shape: Some("square".to_string()),
+                extra: Default::default(),
             },
             style: StickyNoteStyle {
                 fill_color: "light_yellow".to_string(),
+                extra: Default::default(),
             },
             position: Position {
                 x: 100.0,
@@ -791,11 +973,13 @@ mod tests {
             data: ShapeData {
                 content: Some("<p>Shape</p>".to_string()),
                 shape: "rectangle".to_string(),
+                extra: Default::default(),
             },
             style: ShapeStyle {
                 fill_color: "light_blue".to_string(),
                 border_color: Some("blue".to_string()),
                 border_width: Some("2".to_string()),
+                extra: Default::default(),
             },
             position: Position {
                 x: 0.0,
@@ -821,6 +1005,7 @@ mod tests {
             item_type: "text".to_string(),
             data: TextData {
                 content: "Plain text".to_string(),
+                extra: Default::default(),
             },
             position: Position {
                 x: 50.0,
@@ -846,9 +1031,11 @@ mod tests {
             data: FrameData {
                 title: "Frame Title".to_string(),
                 frame_type: "frame".to_string(),
+                extra: Default::default(),
             },
             style: FrameStyle {
                 fill_color: "light_gray".to_string(),
+                extra: Default::default(),
             },
             position: Position {
                 x: 0.0,
@@ -868,6 +1055,37 @@ mod tests {
         assert!(json.contains("light_gray"));
     }
 
+    #[test]
+    fn test_bulk_item_request_card_serialization() {
+        let item = BulkItemRequest::Card {
+            item_type: "card".to_string(),
+            data: CardData {
+                title: Some("Card Title".to_string()),
+                description: None,
+                extra: Default::default(),
+            },
+            style: CardStyle {
+                card_theme: Some("#2d9bf0".to_string()),
+                extra: Default::default(),
+            },
+            position: Position {
+                x: 0.0,
+                y: 0.0,
+                origin: None,
+            },
+            geometry: Geometry {
+                width: 320.0,
+                height: None,
+            },
+            parent: None,
+        };
+
+        let json = serde_json::to_string(&item).unwrap();
+        assert!(json.contains("\"type\":\"card\""));
+        assert!(json.contains("\"cardTheme\":\"#2d9bf0\""));
+        assert!(!json.contains("description"));
+    }
+
     #[test]
     fn test_bulk_create_request_serialization() {
         let items = vec![
@@ -875,6 +1093,7 @@ mod tests {
                 item_type: "text".to_string(),
                 data: TextData {
                     content: "Item 1".to_string(),
+                    extra: Default::default(),
                 },
                 position: Position {
                     x: 0.0,
@@ -891,6 +1110,7 @@ mod tests {
                 item_type: "text".to_string(),
                 data: TextData {
                     content: "Item 2".to_string(),
+                    extra: Default::default(),
                 },
                 position: Position {
                     x: 100.0,
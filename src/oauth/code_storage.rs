@@ -1,7 +1,7 @@
-/// In-memory storage for pending authorization codes
+/// Storage for pending authorization codes
 ///
-/// Stores code exchanges between callback and token endpoints.
-/// Uses Arc<RwLock<HashMap>> for thread-safe access in async context.
+/// Stores code exchanges between callback and token endpoints, in the
+/// [`Storage`] backend selected by `OAUTH_STORAGE` (memory by default).
 ///
 /// # Why not cookies?
 /// Cookies don't work for OAuth Authorization Server pattern because:
@@ -13,47 +13,80 @@
 /// - Codes expire after 5 minutes (PENDING_CODE_MAX_AGE)
 /// - Cleanup runs periodically via background task
 /// - Codes are also removed immediately after successful exchange
+use super::storage::{open_storage, MemoryStorage, Storage, StorageError};
 use super::types::PendingCodeExchange;
+use crate::config::Config;
 use chrono::Utc;
-use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::{info, warn};
+use tracing::{error, info, warn};
+
+/// Storage namespace for pending code exchanges
+const CODES_NAMESPACE: &str = "codes";
 
 /// Storage for pending authorization codes awaiting token exchange
-/// Thread-safe wrapper around HashMap for async access
 #[derive(Clone)]
 pub struct CodeStorage {
-    /// Map: authorization code -> pending exchange data
-    store: Arc<RwLock<HashMap<String, PendingCodeExchange>>>,
+    /// Backend shared with the client registry
+    storage: Arc<dyn Storage>,
 }
 
 impl CodeStorage {
-    /// Create new empty code storage
+    /// Create new empty in-memory code storage
     pub fn new() -> Self {
-        Self {
-            store: Arc::new(RwLock::new(HashMap::new())),
-        }
+        Self::with_storage(Arc::new(MemoryStorage::new()))
+    }
+
+    /// Code storage backed by the given storage
+    pub fn with_storage(storage: Arc<dyn Storage>) -> Self {
+        Self { storage }
+    }
+
+    /// Open the storage backend selected in the configuration
+    pub fn from_config(config: &Config) -> Result<Self, StorageError> {
+        Ok(Self::with_storage(open_storage(
+            &config.oauth_storage,
+            &config.encryption_key,
+        )?))
+    }
+
+    /// The underlying backend, for sharing with the client registry
+    pub fn backend(&self) -> Arc<dyn Storage> {
+        Arc::clone(&self.storage)
     }
 
     /// Store a pending code exchange (called from callback handler)
-    pub async fn store(&self, code: &str, pending: PendingCodeExchange) {
-        let mut store = self.store.write().await;
+    pub async fn store(
+        &self,
+        code: &str,
+        pending: PendingCodeExchange,
+    ) -> Result<(), StorageError> {
         info!(
             code_length = code.len(),
             expires_at = %pending.expires_at,
             "Storing pending code exchange"
         );
-        store.insert(code.to_string(), pending);
+        let value = serde_json::to_string(&pending)?;
+        self.storage
+            .put(CODES_NAMESPACE, code, &value, Some(pending.expires_at))
     }
 
     /// Retrieve and remove a pending code exchange (called from token handler)
     /// Returns None if code not found or expired
     pub async fn take(&self, code: &str) -> Option<PendingCodeExchange> {
-        let mut store = self.store.write().await;
-
-        // Remove from map
-        let pending = store.remove(code)?;
+        let value = match self.storage.take(CODES_NAMESPACE, code) {
+            Ok(value) => value?,
+            Err(e) => {
+                error!(error = %e, "Failed to read pending code exchange");
+                return None;
+            }
+        };
+        let pending: PendingCodeExchange = match serde_json::from_str(&value) {
+            Ok(pending) => pending,
+            Err(e) => {
+                error!(error = %e, "Stored code exchange is invalid");
+                return None;
+            }
+        };
 
         // Check if expired
         let now = Utc::now();
@@ -66,50 +99,34 @@ impl CodeStorage {
             return None;
         }
 
-        info!(
-            code_length = code.len(),
-            "Retrieved pending code exchange"
-        );
+        info!(code_length = code.len(), "Retrieved pending code exchange");
         Some(pending)
     }
 
-    /// Remove expired codes (cleanup task)
+    /// Remove expired entries from the backend (cleanup task)
     pub async fn cleanup_expired(&self) {
-        let mut store = self.store.write().await;
-        let now = Utc::now();
-        let initial_count = store.len();
-
-        // Remove expired entries
-        store.retain(|code, pending| {
-            if now > pending.expires_at {
-                warn!(
-                    code_length = code.len(),
-                    expired_at = %pending.expires_at,
-                    "Removing expired authorization code"
-                );
-                false
-            } else {
-                true
-            }
-        });
-
-        let removed = initial_count - store.len();
-        if removed > 0 {
-            info!(
-                removed = removed,
-                remaining = store.len(),
-                "Cleaned up expired authorization codes"
-            );
+        match self.storage.remove_expired(Utc::now()) {
+            Ok(0) => {}
+            Ok(removed) => info!(removed = removed, "Cleaned up expired OAuth entries"),
+            Err(e) => error!(error = %e, "Failed to clean up expired OAuth entries"),
         }
     }
 
     /// Get current storage statistics (for monitoring/debugging)
     pub async fn stats(&self) -> (usize, usize) {
-        let store = self.store.read().await;
-        let total = store.len();
+        let entries = match self.storage.entries(CODES_NAMESPACE) {
+            Ok(entries) => entries,
+            Err(e) => {
+                error!(error = %e, "Failed to read code storage statistics");
+                return (0, 0);
+            }
+        };
         let now = Utc::now();
-        let expired = store.values().filter(|p| now > p.expires_at).count();
-        (total, expired)
+        let expired = entries
+            .iter()
+            .filter(|entry| entry.expires_at.is_some_and(|at| now > at))
+            .count();
+        (entries.len(), expired)
     }
 }
 
@@ -119,7 +136,7 @@ impl Default for CodeStorage {
     }
 }
 
-/// Start background cleanup task for expired codes
+/// Start background cleanup task for expired codes and other expired entries
 /// Runs every 60 seconds to remove expired entries
 pub fn start_cleanup_task(storage: CodeStorage) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
@@ -143,11 +160,15 @@ mod tests {
         let pending = PendingCodeExchange {
             code: code.to_string(),
             code_verifier: "verifier_abc".to_string(),
+            client_id: String::new(),
+            redirect_uri: String::new(),
+            client_pkce: None,
+            scopes: vec![],
             expires_at: Utc::now() + Duration::seconds(300),
         };
 
         // Store
-        storage.store(code, pending.clone()).await;
+        storage.store(code, pending.clone()).await.unwrap();
 
         // Retrieve
         let retrieved = storage.take(code).await.unwrap();
@@ -165,10 +186,14 @@ mod tests {
         let pending = PendingCodeExchange {
             code: code.to_string(),
             code_verifier: "verifier".to_string(),
+            client_id: String::new(),
+            redirect_uri: String::new(),
+            client_pkce: None,
+            scopes: vec![],
             expires_at: Utc::now() - Duration::seconds(60), // Expired 1 minute ago
         };
 
-        storage.store(code, pending).await;
+        storage.store(code, pending).await.unwrap();
 
         // Should return None for expired code
         assert!(storage.take(code).await.is_none());
@@ -186,10 +211,15 @@ mod tests {
                 PendingCodeExchange {
                     code: expired_code.to_string(),
                     code_verifier: "v1".to_string(),
+                    client_id: String::new(),
+                    redirect_uri: String::new(),
+                    client_pkce: None,
+                    scopes: vec![],
                     expires_at: Utc::now() - Duration::seconds(60),
                 },
             )
-            .await;
+            .await
+            .unwrap();
 
         // Add valid code
         let valid_code = "valid";
@@ -199,10 +229,15 @@ mod tests {
                 PendingCodeExchange {
                     code: valid_code.to_string(),
                     code_verifier: "v2".to_string(),
+                    client_id: String::new(),
+                    redirect_uri: String::new(),
+                    client_pkce: None,
+                    scopes: vec![],
                     expires_at: Utc::now() + Duration::seconds(300),
                 },
             )
-            .await;
+            .await
+            .unwrap();
 
         // Cleanup
         storage.cleanup_expired().await;
@@ -0,0 +1,125 @@
+//! Consent page shown by /oauth/authorize before the Miro redirect
+//!
+//! Anyone can register a client with the proxy, so a malicious client could
+//! send a user who already approved our Miro app straight through the flow.
+//! The page names the client, where the code will be sent and what it asks
+//! for; the form carries a CSRF token that must match the one in the
+//! encrypted state cookie.
+
+use serde::Deserialize;
+
+use crate::auth::scopes;
+
+/// Form posted by the consent page to /oauth/consent
+#[derive(Debug, Deserialize)]
+pub struct ConsentForm {
+    /// Must match the token in the state cookie
+    pub csrf_token: String,
+
+    /// "approve" continues to Miro; anything else denies
+    pub decision: String,
+}
+
+impl ConsentForm {
+    /// Whether the user clicked approve
+    pub fn approved(&self) -> bool {
+        self.decision == "approve"
+    }
+}
+
+/// Render the consent page for a client
+///
+/// # Arguments
+/// * `client_name` - Name the client registered with (untrusted)
+/// * `redirect_host` - Host the authorization code will be sent to
+/// * `scopes` - Miro scopes that will be requested
+/// * `csrf_token` - Token also stored in the state cookie
+pub fn render_page(
+    client_name: &str,
+    redirect_host: &str,
+    scopes: &[String],
+    csrf_token: &str,
+) -> String {
+    let permissions: String = scopes
+        .iter()
+        .map(|scope| {
+            format!(
+                "<li>{} <code>{}</code></li>",
+                scopes::describe(scope),
+                escape_html(scope)
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>Authorize {client_name}</title>
+    <style>
+        body {{ font-family: sans-serif; max-width: 32rem; margin: 4rem auto; padding: 0 1rem; }}
+        button {{ padding: 0.5rem 1.5rem; margin-right: 0.5rem; }}
+    </style>
+</head>
+<body>
+    <h1>Authorize {client_name}</h1>
+    <p><strong>{client_name}</strong> wants to access your Miro account.
+    You will be returned to <strong>{redirect_host}</strong>.</p>
+    <p>It will be able to:</p>
+    <ul>{permissions}</ul>
+    <form method="post" action="/oauth/consent">
+        <input type="hidden" name="csrf_token" value="{csrf_token}">
+        <button type="submit" name="decision" value="approve">Approve</button>
+        <button type="submit" name="decision" value="deny">Deny</button>
+    </form>
+</body>
+</html>"#,
+        client_name = escape_html(client_name),
+        redirect_host = escape_html(redirect_host),
+        permissions = permissions,
+        csrf_token = escape_html(csrf_token),
+    )
+}
+
+/// Escape text for HTML element content and quoted attribute values
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#x27;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_describes_request() {
+        let page = render_page(
+            "Claude",
+            "claude.ai",
+            &["boards:read".to_string()],
+            "csrf-123",
+        );
+        assert!(page.contains("<strong>Claude</strong> wants to access"));
+        assert!(page.contains("<strong>claude.ai</strong>"));
+        assert!(page.contains("View your boards and their content <code>boards:read</code>"));
+        assert!(!page.contains("boards:write"));
+        assert!(page.contains(r#"name="csrf_token" value="csrf-123""#));
+    }
+
+    #[test]
+    fn test_client_name_is_escaped() {
+        let page = render_page(
+            r#"<script>alert("x")</script>"#,
+            "claude.ai",
+            &[],
+            "csrf-123",
+        );
+        assert!(!page.contains("<script>"));
+        assert!(page.contains("&lt;script&gt;alert(&quot;x&quot;)&lt;/script&gt;"));
+    }
+}
@@ -1,27 +1,37 @@
-use super::types::{ClientRegistrationRequest, ClientRegistrationResponse, RegisteredClient};
+use super::storage::{token_hash, MemoryStorage, Storage};
+use super::types::{
+    ClientRegistrationRequest, ClientRegistrationResponse, ClientUpdateRequest, RegisteredClient,
+};
+use crate::config::RegistrationPolicy;
+use crate::http_server::AppStateADR002;
 use axum::{
-    extract::State,
-    http::StatusCode,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
-use chrono::Utc;
-use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
-use tracing::{info, warn};
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+/// Storage namespace for registered clients
+const CLIENTS_NAMESPACE: &str = "clients";
+
+/// Token endpoint authentication methods clients may register with
+const SUPPORTED_AUTH_METHODS: &[&str] = &["none", "client_secret_basic", "client_secret_post"];
 
-/// In-memory client registry
-/// For production, this should be persistent (database)
+/// Registry of dynamically registered clients
+///
+/// Registrations live in the configured [`Storage`] backend so clients stay
+/// valid across restarts; [`ClientRegistry::new`] keeps them in memory.
 #[derive(Clone)]
 pub struct ClientRegistry {
-    clients: Arc<RwLock<HashMap<String, RegisteredClient>>>,
+    storage: Arc<dyn Storage>,
 }
 
 impl Default for ClientRegistry {
     fn default() -> Self {
-        Self {
-            clients: Arc::new(RwLock::new(HashMap::new())),
-        }
+        Self::with_storage(Arc::new(MemoryStorage::new()))
     }
 }
 
@@ -30,89 +40,348 @@ impl ClientRegistry {
         Self::default()
     }
 
+    /// Registry backed by the given storage
+    pub fn with_storage(storage: Arc<dyn Storage>) -> Self {
+        Self { storage }
+    }
+
     /// Register a new OAuth client
+    ///
+    /// A confidential client is useless once its secret expires, so the
+    /// registration expires with it and is swept by the storage cleanup.
     pub fn register(&self, client: RegisteredClient) -> Result<(), String> {
-        let mut clients = self.clients.write().map_err(|e| e.to_string())?;
-        clients.insert(client.client_id.clone(), client);
-        Ok(())
+        let value = serde_json::to_string(&client).map_err(|e| e.to_string())?;
+        let expires_at = if client.is_public() {
+            None
+        } else {
+            client.client_secret_expires_at
+        };
+        self.storage
+            .put(CLIENTS_NAMESPACE, &client.client_id, &value, expires_at)
+            .map_err(|e| e.to_string())
     }
 
     /// Get a registered client by ID
     pub fn get(&self, client_id: &str) -> Option<RegisteredClient> {
-        let clients = self.clients.read().ok()?;
-        clients.get(client_id).cloned()
+        let value = match self.storage.get(CLIENTS_NAMESPACE, client_id) {
+            Ok(value) => value?,
+            Err(e) => {
+                error!(error = %e, "Failed to read client registration");
+                return None;
+            }
+        };
+        match serde_json::from_str(&value) {
+            Ok(client) => Some(client),
+            Err(e) => {
+                error!(error = %e, "Stored client registration is invalid");
+                None
+            }
+        }
+    }
+
+    /// Whether the client is registered as public (token_endpoint_auth_method "none")
+    pub fn is_public(&self, client_id: &str) -> bool {
+        self.get(client_id).is_some_and(|client| client.is_public())
     }
 
     /// Validate client credentials
+    ///
+    /// The secret is checked against its stored hash. Expired client secrets
+    /// are rejected.
     pub fn validate(&self, client_id: &str, client_secret: &str) -> bool {
         if let Some(client) = self.get(client_id) {
-            client.client_secret == client_secret
+            if client.secret_expired() {
+                warn!(client_id = %client_id, "Client secret has expired");
+                return false;
+            }
+            client.client_secret_hash == token_hash(client_secret)
         } else {
             false
         }
     }
+
+    /// Remove a client registration, returning it if it existed
+    pub fn remove(&self, client_id: &str) -> Result<Option<RegisteredClient>, String> {
+        let value = self
+            .storage
+            .take(CLIENTS_NAMESPACE, client_id)
+            .map_err(|e| e.to_string())?;
+        value
+            .map(|value| serde_json::from_str(&value).map_err(|e| e.to_string()))
+            .transpose()
+    }
+
+    /// Number of registered clients, not counting expired registrations
+    /// the cleanup task hasn't swept yet
+    pub fn count(&self) -> Result<usize, String> {
+        let now = Utc::now();
+        self.storage
+            .entries(CLIENTS_NAMESPACE)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter(|entry| entry.expires_at.is_none_or(|expires_at| now <= expires_at))
+                    .count()
+            })
+            .map_err(|e| e.to_string())
+    }
 }
 
-/// Handle Dynamic Client Registration (RFC 7591)
-/// POST /register
-pub async fn register_handler(
-    State(registry): State<ClientRegistry>,
-    Json(req): Json<ClientRegistrationRequest>,
-) -> Result<Json<ClientRegistrationResponse>, Response> {
-    info!(
-        client_name = %req.client_name,
-        redirect_uris = ?req.redirect_uris,
-        "Received client registration request"
-    );
+/// Whether a requested redirect URI matches a registered one
+///
+/// Matching is exact, except that loopback redirect URIs (`127.0.0.1`, `[::1]`
+/// and `localhost` over http) may use any port, since native apps pick one at
+/// runtime (RFC 8252 §7.3).
+pub fn redirect_uri_matches(registered: &str, requested: &str) -> bool {
+    if registered == requested {
+        return true;
+    }
+    let (Ok(registered), Ok(requested)) = (url::Url::parse(registered), url::Url::parse(requested))
+    else {
+        return false;
+    };
+    is_loopback(&registered)
+        && registered.host() == requested.host()
+        && registered.scheme() == requested.scheme()
+        && registered.path() == requested.path()
+        && registered.query() == requested.query()
+        && registered.fragment().is_none()
+        && requested.fragment().is_none()
+}
 
-    // Validate request
-    if req.client_name.is_empty() {
-        warn!("Registration rejected: empty client_name");
-        return Err((
-            StatusCode::BAD_REQUEST,
+/// Whether a URI is an http redirect to this machine: `localhost`, or a
+/// loopback address such as `127.0.0.1` or `[::1]`
+fn is_loopback(uri: &url::Url) -> bool {
+    uri.scheme() == "http"
+        && match uri.host() {
+            Some(url::Host::Ipv4(ip)) => ip.is_loopback(),
+            Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+            Some(url::Host::Domain(domain)) => domain == "localhost",
+            None => false,
+        }
+}
+
+/// Error from the registration endpoints, sent as JSON (RFC 7591 §3.2.2)
+#[derive(Debug)]
+pub struct RegistrationError {
+    status: StatusCode,
+    error: &'static str,
+    description: &'static str,
+}
+
+impl RegistrationError {
+    fn new(status: StatusCode, error: &'static str, description: &'static str) -> Self {
+        Self {
+            status,
+            error,
+            description,
+        }
+    }
+}
+
+impl IntoResponse for RegistrationError {
+    fn into_response(self) -> Response {
+        (
+            self.status,
             Json(serde_json::json!({
-                "error": "invalid_client_metadata",
-                "error_description": "client_name is required"
+                "error": self.error,
+                "error_description": self.description
             })),
         )
-            .into_response());
+            .into_response()
+    }
+}
+
+/// Validate client metadata for registration or update
+fn validate_client_metadata(
+    req: &ClientRegistrationRequest,
+    policy: &RegistrationPolicy,
+) -> Result<(), RegistrationError> {
+    if req.client_name.is_empty() {
+        warn!("Registration rejected: empty client_name");
+        return Err(RegistrationError::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_client_metadata",
+            "client_name is required",
+        ));
     }
 
     if req.redirect_uris.is_empty() {
         warn!("Registration rejected: no redirect_uris");
-        return Err((
+        return Err(RegistrationError::new(
             StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-                "error": "invalid_redirect_uri",
-                "error_description": "At least one redirect_uri is required"
-            })),
-        )
-            .into_response());
+            "invalid_redirect_uri",
+            "At least one redirect_uri is required",
+        ));
     }
 
-    // Validate redirect URIs are HTTPS (except localhost for development)
+    if let Some(method) = &req.token_endpoint_auth_method {
+        if !SUPPORTED_AUTH_METHODS.contains(&method.as_str()) {
+            warn!(method = %method, "Registration rejected: unsupported token_endpoint_auth_method");
+            return Err(RegistrationError::new(
+                StatusCode::BAD_REQUEST,
+                "invalid_client_metadata",
+                "token_endpoint_auth_method must be none, client_secret_basic or client_secret_post",
+            ));
+        }
+    }
+
+    // Validate redirect URIs are HTTPS (except loopback for native apps and development)
     for uri in &req.redirect_uris {
-        if !uri.starts_with("https://") && !uri.starts_with("http://localhost") {
+        let Ok(url) = url::Url::parse(uri) else {
+            warn!(uri = %uri, "Registration rejected: malformed redirect_uri");
+            return Err(RegistrationError::new(
+                StatusCode::BAD_REQUEST,
+                "invalid_redirect_uri",
+                "redirect_uri must be an absolute URI",
+            ));
+        };
+        if url.scheme() != "https" && !is_loopback(&url) {
             warn!(uri = %uri, "Registration rejected: non-HTTPS redirect_uri");
-            return Err((
+            return Err(RegistrationError::new(
                 StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({
-                    "error": "invalid_redirect_uri",
-                    "error_description": "redirect_uri must use HTTPS (or http://localhost for development)"
-                })),
+                "invalid_redirect_uri",
+                "redirect_uri must use HTTPS (or http to a loopback host for development)",
+            ));
+        }
+
+        if !url
+            .host_str()
+            .is_some_and(|host| policy.allows_redirect_host(host))
+        {
+            warn!(uri = %uri, "Registration rejected: redirect_uri host not allowed");
+            return Err(RegistrationError::new(
+                StatusCode::BAD_REQUEST,
+                "invalid_redirect_uri",
+                "redirect_uri host is not allowed on this server",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Registration response for a client, as returned by every RFC 7591/7592 endpoint
+///
+/// The client secret and registration access token are only known when
+/// they're issued; reads return everything else.
+fn registration_response(
+    client: &RegisteredClient,
+    base_url: &str,
+    client_secret: Option<String>,
+    registration_access_token: Option<String>,
+) -> ClientRegistrationResponse {
+    ClientRegistrationResponse {
+        client_id: client.client_id.clone(),
+        client_secret,
+        registration_access_token,
+        registration_client_uri: Some(format!("{}/register/{}", base_url, client.client_id)),
+        client_name: client.client_name.clone(),
+        redirect_uris: client.redirect_uris.clone(),
+        grant_types: client.grant_types.clone(),
+        response_types: client.response_types.clone(),
+        token_endpoint_auth_method: client.token_endpoint_auth_method.clone(),
+        client_id_issued_at: client.created_at.timestamp(),
+        // Only meaningful with a secret; 0 = never
+        client_secret_expires_at: (!client.is_public()).then(|| {
+            client
+                .client_secret_expires_at
+                .map_or(0, |expires_at| expires_at.timestamp())
+        }),
+    }
+}
+
+fn base_url(state: &AppStateADR002) -> &str {
+    state
+        .config
+        .base_url
+        .as_deref()
+        .unwrap_or("http://localhost:3000")
+}
+
+/// Bearer token from the Authorization header, if any
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(axum::http::header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+}
+
+/// Handle Dynamic Client Registration (RFC 7591)
+/// POST /register
+///
+/// Registrations are subject to the configured [`RegistrationPolicy`]: an
+/// initial access token, a cap on registered clients and allowed redirect URI
+/// hosts. The response includes a registration access token and URI for
+/// managing the registration (RFC 7592).
+pub async fn register_handler(
+    State(state): State<AppStateADR002>,
+    headers: HeaderMap,
+    Json(req): Json<ClientRegistrationRequest>,
+) -> Result<Json<ClientRegistrationResponse>, RegistrationError> {
+    let registry = &state.client_registry;
+    let policy = &state.config.registration_policy;
+    info!(
+        client_name = %req.client_name,
+        redirect_uris = ?req.redirect_uris,
+        "Received client registration request"
+    );
+
+    if let Some(initial_access_token) = &policy.initial_access_token {
+        // Compared by hash, so the comparison time says nothing about the token
+        if bearer_token(&headers).map(token_hash) != Some(token_hash(initial_access_token)) {
+            warn!("Registration rejected: missing or wrong initial access token");
+            return Err(RegistrationError::new(
+                StatusCode::UNAUTHORIZED,
+                "invalid_token",
+                "A valid initial access token is required to register clients",
+            ));
+        }
+    }
+
+    validate_client_metadata(&req, policy)?;
+
+    if let Some(max_clients) = policy.max_clients {
+        let count = registry.count().map_err(|e| {
+            warn!(error = %e, "Failed to count registered clients");
+            RegistrationError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "server_error",
+                "Failed to register client",
             )
-                .into_response());
+        })?;
+        if count >= max_clients {
+            warn!(
+                max_clients = max_clients,
+                "Registration rejected: client limit reached"
+            );
+            return Err(RegistrationError::new(
+                StatusCode::FORBIDDEN,
+                "access_denied",
+                "This server does not accept more client registrations",
+            ));
         }
     }
 
-    // Generate client credentials
+    // Default token endpoint auth method
+    let token_endpoint_auth_method = req
+        .token_endpoint_auth_method
+        .unwrap_or_else(|| "client_secret_basic".to_string());
+    let public_client = token_endpoint_auth_method == "none";
+
+    // Generate client credentials; public clients get no secret
     let client_id = uuid::Uuid::new_v4().to_string();
-    let client_secret = uuid::Uuid::new_v4().to_string();
+    let client_secret = (!public_client).then(|| uuid::Uuid::new_v4().to_string());
+    let registration_access_token = uuid::Uuid::new_v4().to_string();
     let now = Utc::now();
 
     // Default grant types if not specified
     let grant_types = if req.grant_types.is_empty() {
-        vec!["authorization_code".to_string()]
+        vec![
+            "authorization_code".to_string(),
+            "refresh_token".to_string(),
+        ]
     } else {
         req.grant_types
     };
@@ -124,32 +393,31 @@ pub async fn register_handler(
         req.response_types
     };
 
-    // Default token endpoint auth method
-    let token_endpoint_auth_method = req
-        .token_endpoint_auth_method
-        .unwrap_or_else(|| "client_secret_basic".to_string());
-
     // Create registered client
     let client = RegisteredClient {
         client_id: client_id.clone(),
-        client_secret: client_secret.clone(),
+        client_secret_hash: client_secret.as_deref().map(token_hash).unwrap_or_default(),
         client_name: req.client_name.clone(),
-        redirect_uris: req.redirect_uris.clone(),
-        grant_types: grant_types.clone(),
+        redirect_uris: req.redirect_uris,
+        grant_types,
+        token_endpoint_auth_method,
+        response_types,
         created_at: now,
+        client_secret_expires_at: policy
+            .client_secret_ttl_secs
+            .filter(|_| !public_client)
+            .map(|ttl| now + Duration::seconds(ttl as i64)),
+        registration_access_token_hash: Some(token_hash(&registration_access_token)),
     };
 
     // Store client
-    if let Err(e) = registry.register(client) {
+    if let Err(e) = registry.register(client.clone()) {
         warn!(error = %e, "Failed to register client");
-        return Err((
+        return Err(RegistrationError::new(
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({
-                "error": "server_error",
-                "error_description": "Failed to register client"
-            })),
-        )
-            .into_response());
+            "server_error",
+            "Failed to register client",
+        ));
     }
 
     info!(
@@ -159,17 +427,208 @@ pub async fn register_handler(
     );
 
     // Return registration response
-    Ok(Json(ClientRegistrationResponse {
-        client_id,
+    Ok(Json(registration_response(
+        &client,
+        base_url(&state),
         client_secret,
-        registration_access_token: None,
-        registration_client_uri: None,
-        client_name: req.client_name,
-        redirect_uris: req.redirect_uris,
-        grant_types,
-        response_types,
-        token_endpoint_auth_method,
-        client_id_issued_at: now.timestamp(),
-        client_secret_expires_at: None, // Never expires
-    }))
+        Some(registration_access_token),
+    )))
+}
+
+/// Look up the client named in a management request and check its registration
+/// access token (RFC 7592 §2)
+///
+/// Unknown clients and wrong tokens get the same 401, so client IDs can't be probed.
+fn authorize_management(
+    state: &AppStateADR002,
+    headers: &HeaderMap,
+    client_id: &str,
+) -> Result<RegisteredClient, RegistrationError> {
+    let client = state.client_registry.get(client_id).filter(|client| {
+        let presented = bearer_token(headers).map(token_hash);
+        presented.is_some() && client.registration_access_token_hash == presented
+    });
+    client.ok_or_else(|| {
+        warn!(client_id = %client_id, "Client management request not authorized");
+        RegistrationError::new(
+            StatusCode::UNAUTHORIZED,
+            "invalid_token",
+            "Invalid registration access token",
+        )
+    })
+}
+
+/// Read a client registration (RFC 7592 §2.1)
+/// GET /register/{client_id}
+pub async fn read_client_handler(
+    State(state): State<AppStateADR002>,
+    Path(client_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<ClientRegistrationResponse>, RegistrationError> {
+    let client = authorize_management(&state, &headers, &client_id)?;
+    Ok(Json(registration_response(
+        &client,
+        base_url(&state),
+        None,
+        None,
+    )))
+}
+
+/// Replace a client's metadata (RFC 7592 §2.2)
+/// PUT /register/{client_id}
+///
+/// The client keeps its ID, secret and registration access token.
+pub async fn update_client_handler(
+    State(state): State<AppStateADR002>,
+    Path(client_id): Path<String>,
+    headers: HeaderMap,
+    Json(req): Json<ClientUpdateRequest>,
+) -> Result<Json<ClientRegistrationResponse>, RegistrationError> {
+    let client = authorize_management(&state, &headers, &client_id)?;
+
+    if req.client_id != client.client_id {
+        return Err(RegistrationError::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_client_metadata",
+            "client_id does not match the registration",
+        ));
+    }
+    if req
+        .client_secret
+        .is_some_and(|secret| token_hash(&secret) != client.client_secret_hash)
+    {
+        return Err(RegistrationError::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_client_metadata",
+            "client_secret does not match the registration",
+        ));
+    }
+    let metadata = req.metadata;
+    validate_client_metadata(&metadata, &state.config.registration_policy)?;
+
+    let updated = RegisteredClient {
+        client_name: metadata.client_name,
+        redirect_uris: metadata.redirect_uris,
+        grant_types: if metadata.grant_types.is_empty() {
+            client.grant_types.clone()
+        } else {
+            metadata.grant_types
+        },
+        response_types: if metadata.response_types.is_empty() {
+            client.response_types.clone()
+        } else {
+            metadata.response_types
+        },
+        token_endpoint_auth_method: metadata
+            .token_endpoint_auth_method
+            .unwrap_or_else(|| client.token_endpoint_auth_method.clone()),
+        ..client
+    };
+
+    if let Err(e) = state.client_registry.register(updated.clone()) {
+        warn!(error = %e, "Failed to update client");
+        return Err(RegistrationError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "server_error",
+            "Failed to update client",
+        ));
+    }
+
+    info!(client_id = %client_id, "Client registration updated");
+    Ok(Json(registration_response(
+        &updated,
+        base_url(&state),
+        None,
+        None,
+    )))
+}
+
+/// Delete a client registration (RFC 7592 §2.3)
+/// DELETE /register/{client_id}
+///
+/// Revokes everything issued to the client: with proxy-owned tokens, its grants
+/// are dropped and their Miro tokens revoked.
+pub async fn delete_client_handler(
+    State(state): State<AppStateADR002>,
+    Path(client_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<StatusCode, RegistrationError> {
+    authorize_management(&state, &headers, &client_id)?;
+
+    let server_error = |e: String| {
+        warn!(error = %e, "Failed to delete client");
+        RegistrationError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "server_error",
+            "Failed to delete client",
+        )
+    };
+    state
+        .client_registry
+        .remove(&client_id)
+        .map_err(server_error)?;
+
+    if let Some(proxy_tokens) = &state.proxy_tokens {
+        let revoked = proxy_tokens
+            .revoke_client(&client_id)
+            .map_err(|e| server_error(e.to_string()))?;
+        for miro_tokens in revoked {
+            state.token_validator.evict(&miro_tokens.access_token);
+            if let Err(e) = state
+                .oauth_provider
+                .revoke_token(&miro_tokens.refresh_token)
+                .await
+            {
+                warn!(client_id = %client_id, error = %e, "Revoking Miro tokens failed");
+            }
+        }
+    }
+
+    info!(client_id = %client_id, "Client registration deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redirect_uri_matches_exactly() {
+        let registered = "https://claude.ai/api/mcp/auth_callback";
+        assert!(redirect_uri_matches(registered, registered));
+        assert!(!redirect_uri_matches(
+            registered,
+            "https://claude.ai/api/mcp/auth_callback/evil"
+        ));
+        assert!(!redirect_uri_matches(
+            registered,
+            "https://claude.ai:8443/api/mcp/auth_callback"
+        ));
+        assert!(!redirect_uri_matches(registered, "https://evil.example/cb"));
+    }
+
+    #[test]
+    fn test_loopback_redirect_uri_port_is_flexible() {
+        assert!(redirect_uri_matches(
+            "http://127.0.0.1/callback",
+            "http://127.0.0.1:53682/callback"
+        ));
+        assert!(redirect_uri_matches(
+            "http://[::1]:8080/callback",
+            "http://[::1]:9090/callback"
+        ));
+        assert!(redirect_uri_matches(
+            "http://localhost:3000/callback",
+            "http://localhost:4000/callback"
+        ));
+        // Path and host must still match
+        assert!(!redirect_uri_matches(
+            "http://127.0.0.1/callback",
+            "http://127.0.0.1:53682/other"
+        ));
+        assert!(!redirect_uri_matches(
+            "http://127.0.0.1/callback",
+            "http://localhost:53682/callback"
+        ));
+    }
 }
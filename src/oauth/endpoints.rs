@@ -4,13 +4,25 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
-use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine as _,
+};
 use chrono::Utc;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use tracing::{error, info, warn};
 
-use super::{pkce::generate_pkce_pair, types::OAuthState};
+use crate::auth::scopes;
+
+use super::{
+    consent::{self, ConsentForm},
+    pkce::{self, generate_pkce_pair},
+    proxy_provider::MiroOAuthError,
+    proxy_tokens::{IssuedTokens, ProxyTokenError, ProxyTokenStore},
+    storage::token_hash,
+    types::{ClientPkce, CookieData, OAuthState},
+};
 
 /// Cookie name for OAuth state during authorization flow
 const STATE_COOKIE_NAME: &str = "miro_oauth_state";
@@ -26,6 +38,7 @@ const PENDING_CODE_MAX_AGE: i64 = 300; // 5 minutes in seconds
 #[derive(Debug, Deserialize)]
 pub struct AuthorizeParams {
     /// OAuth response type (should be "code")
+    #[serde(default)]
     response_type: String,
 
     /// Client ID from Claude.ai
@@ -41,6 +54,14 @@ pub struct AuthorizeParams {
     /// Requested OAuth scopes
     #[serde(default)]
     scope: Option<String>,
+
+    /// Client's PKCE challenge (RFC 7636), required for public clients
+    #[serde(default)]
+    code_challenge: Option<String>,
+
+    /// PKCE challenge method; only S256 is accepted
+    #[serde(default)]
+    code_challenge_method: Option<String>,
 }
 
 /// Query parameters for OAuth callback
@@ -76,19 +97,28 @@ pub struct TokenResponseRfc6749 {
 /// Receives authorization request from Claude.ai, generates PKCE pair,
 /// stores state in encrypted cookie, then redirects to Miro.
 ///
+/// An unknown client_id or a redirect_uri that isn't registered for the client
+/// is reported to the user directly; other errors are sent back to the client's
+/// redirect_uri (RFC 6749 §4.1.2.1).
+///
 /// # Flow
-/// 1. Extract and validate authorization request parameters from Claude.ai
-/// 2. Generate PKCE code verifier and challenge
-/// 3. Generate random state nonce (CSRF protection)
-/// 4. Store state, PKCE verifier, and Claude's redirect_uri in encrypted cookie
-/// 5. Redirect user to Miro authorization URL with PKCE challenge
+/// 1. Look up the client in the registry and match the redirect_uri against
+///    its registered redirect URIs
+/// 2. Validate the remaining authorization request parameters, including the
+///    client's own PKCE challenge (S256 only, required for public clients)
+/// 3. Map the requested scope to the Miro scopes to ask for
+/// 4. Generate PKCE code verifier and challenge for Miro
+/// 5. Generate random state nonce (CSRF protection)
+/// 6. Store state, PKCE verifier, client challenge, scopes and Claude's redirect_uri
+///    in encrypted cookie
+/// 7. Redirect user to Miro authorization URL with PKCE challenge and scopes
+///
+/// With `consent_policy.required`, clients that aren't trusted get a consent
+/// page instead of step 7; [`consent_handler`] continues once the user approves.
 pub async fn authorize_handler(
     State(state): State<crate::http_server::AppStateADR002>,
     Query(params): Query<AuthorizeParams>,
 ) -> Result<Response, OAuthEndpointError> {
-    let provider = &state.oauth_provider;
-    let cookie_manager = &state.cookie_manager;
-
     info!(
         client_id = %params.client_id,
         redirect_uri = %params.redirect_uri,
@@ -98,39 +128,220 @@ pub async fn authorize_handler(
         "Starting OAuth authorization flow from Claude.ai"
     );
 
+    // Never redirect to a URI we can't tie to a registered client
+    let client = state
+        .client_registry
+        .get(&params.client_id)
+        .ok_or_else(|| {
+            warn!(client_id = %params.client_id, "Authorization rejected: unknown client");
+            OAuthEndpointError::InvalidClient(format!("Unknown client_id: {}", params.client_id))
+        })?;
+    if !client.allows_redirect_uri(&params.redirect_uri) {
+        warn!(
+            client_id = %params.client_id,
+            redirect_uri = %params.redirect_uri,
+            "Authorization rejected: redirect_uri not registered"
+        );
+        return Err(OAuthEndpointError::InvalidRequest(
+            "redirect_uri is not registered for this client".to_string(),
+        ));
+    }
+
+    // From here on, errors go back to the client's redirect_uri
+    let redirect_error = |error: &'static str, description: String| {
+        OAuthEndpointError::Redirect(Box::new(AuthorizationErrorRedirect {
+            redirect_uri: params.redirect_uri.clone(),
+            error,
+            description,
+            state: params.state.clone(),
+        }))
+    };
+
     // Validate response_type (must be "code" for authorization code flow)
     if params.response_type != "code" {
         warn!(response_type = %params.response_type, "Invalid response_type");
-        return Err(OAuthEndpointError::InvalidRequest(format!(
-            "Unsupported response_type: {}. Only 'code' is supported.",
-            params.response_type
-        )));
+        let error = if params.response_type.is_empty() {
+            "invalid_request"
+        } else {
+            "unsupported_response_type"
+        };
+        return Err(redirect_error(
+            error,
+            format!(
+                "Unsupported response_type: {}. Only 'code' is supported.",
+                params.response_type
+            ),
+        ));
     }
 
+    // Validate the client's PKCE challenge; it's checked against the verifier at /token
+    let client_pkce = match params.code_challenge.as_deref() {
+        Some(challenge) => {
+            pkce::validate_challenge(challenge, params.code_challenge_method.as_deref()).map_err(
+                |e| {
+                    warn!(client_id = %params.client_id, error = %e, "Invalid PKCE challenge");
+                    redirect_error("invalid_request", e.to_string())
+                },
+            )?;
+            Some(ClientPkce {
+                code_challenge: challenge.to_string(),
+                code_challenge_method: pkce::S256.to_string(),
+            })
+        }
+        None if client.is_public() => {
+            warn!(client_id = %params.client_id, "Public client sent no PKCE challenge");
+            return Err(redirect_error(
+                "invalid_request",
+                "code_challenge is required for public clients".to_string(),
+            ));
+        }
+        None => None,
+    };
+
+    // Only ask Miro for scopes the client requested and the server supports
+    let scopes = scopes::map_requested_scope(params.scope.as_deref()).map_err(|e| {
+        warn!(client_id = %params.client_id, error = %e, "Invalid scope requested");
+        redirect_error("invalid_scope", e.to_string())
+    })?;
+
     // Generate PKCE pair
     let pkce = generate_pkce_pair();
     info!("Generated PKCE pair");
 
-    // Generate random state nonce (32 bytes = 43 chars base64url)
-    let mut rng = rand::thread_rng();
-    let state_bytes: [u8; 32] = rng.gen();
-    let state = URL_SAFE_NO_PAD.encode(state_bytes);
-
     // Create OAuth state for cookie storage (use redirect_uri from Claude.ai's request)
-    let oauth_state = OAuthState {
-        state: state.clone(),
+    let mut oauth_state = OAuthState {
+        state: random_token(),
         code_verifier: pkce.verifier,
         redirect_uri: params.redirect_uri.clone(), // From Claude.ai's authorization request
+        client_id: params.client_id.clone(),
+        client_state: params.state.clone(),
+        client_pkce,
+        scopes,
+        csrf_token: None,
     };
 
-    // Encrypt and store state in cookie
-    let encrypted_state = cookie_manager
-        .encrypt(&oauth_state)
+    if !state
+        .config
+        .consent_policy
+        .requires_consent(&client.client_id)
+    {
+        return redirect_to_miro(&state, &oauth_state);
+    }
+
+    // Ask the user first; the state cookie carries the form's CSRF token
+    let csrf_token = random_token();
+    oauth_state.csrf_token = Some(csrf_token.clone());
+    let redirect_host = url::Url::parse(&params.redirect_uri)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_default();
+    let page = consent::render_page(
+        &client.client_name,
+        &redirect_host,
+        &oauth_state.scopes,
+        &csrf_token,
+    );
+
+    info!(client_id = %params.client_id, "Showing consent page");
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::SET_COOKIE, state_cookie(&state, &oauth_state)?),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+            (header::X_FRAME_OPTIONS, "DENY".to_string()),
+            (
+                header::CONTENT_SECURITY_POLICY,
+                "default-src 'none'; style-src 'unsafe-inline'; frame-ancestors 'none'".to_string(),
+            ),
+        ],
+        axum::response::Html(page),
+    )
+        .into_response())
+}
+
+/// Handle POST /oauth/consent - The user's answer on the consent page
+///
+/// The form's CSRF token must match the one in the state cookie. Approving
+/// continues to Miro exactly as [`authorize_handler`] would have; denying sends
+/// `access_denied` to the client's redirect_uri.
+pub async fn consent_handler(
+    State(state): State<crate::http_server::AppStateADR002>,
+    headers: HeaderMap,
+    axum::extract::Form(form): axum::extract::Form<ConsentForm>,
+) -> Result<Response, OAuthEndpointError> {
+    let state_cookie = extract_cookie(&headers, STATE_COOKIE_NAME)
+        .ok_or_else(|| OAuthEndpointError::InvalidState("State cookie not found".to_string()))?;
+    let mut oauth_state: OAuthState = state
+        .cookie_manager
+        .decrypt(&state_cookie)
+        .map_err(|e| OAuthEndpointError::InvalidState(format!("Failed to decrypt state: {}", e)))?;
+
+    // Hashed so the comparison doesn't leak how much of the token matched
+    let expected = oauth_state
+        .csrf_token
+        .take()
+        .ok_or_else(|| OAuthEndpointError::InvalidState("No consent pending".to_string()))?;
+    if token_hash(&expected) != token_hash(&form.csrf_token) {
+        warn!(client_id = %oauth_state.client_id, "Consent CSRF token mismatch");
+        return Err(OAuthEndpointError::InvalidState(
+            "CSRF token mismatch".to_string(),
+        ));
+    }
+
+    if !form.approved() {
+        info!(client_id = %oauth_state.client_id, "User denied consent");
+        return Err(OAuthEndpointError::Redirect(Box::new(
+            AuthorizationErrorRedirect {
+                redirect_uri: oauth_state.redirect_uri,
+                error: "access_denied",
+                description: "The user denied the authorization request".to_string(),
+                state: oauth_state.client_state,
+            },
+        )));
+    }
+
+    info!(client_id = %oauth_state.client_id, "User approved consent");
+    redirect_to_miro(&state, &oauth_state)
+}
+
+/// Random 32 bytes, base64url (43 chars); used for state nonces and CSRF tokens
+fn random_token() -> String {
+    let bytes: [u8; 32] = rand::thread_rng().gen();
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// `Set-Cookie` value storing the encrypted OAuth state
+fn state_cookie(
+    state: &crate::http_server::AppStateADR002,
+    oauth_state: &OAuthState,
+) -> Result<String, OAuthEndpointError> {
+    let encrypted_state = state
+        .cookie_manager
+        .encrypt(oauth_state)
         .map_err(|e| OAuthEndpointError::CookieError(format!("Failed to encrypt state: {}", e)))?;
 
+    Ok(format!(
+        "{}={}; HttpOnly; Secure; SameSite=Lax; Max-Age={}; Path=/",
+        STATE_COOKIE_NAME, encrypted_state, STATE_COOKIE_MAX_AGE
+    ))
+}
+
+/// Store the OAuth state in its cookie and redirect the user to Miro
+fn redirect_to_miro(
+    state: &crate::http_server::AppStateADR002,
+    oauth_state: &OAuthState,
+) -> Result<Response, OAuthEndpointError> {
+    let cookie_header = state_cookie(state, oauth_state)?;
+
     // Build Miro authorization URL
-    let auth_url = provider
-        .build_authorization_url(&state, &pkce.challenge)
+    let auth_url = state
+        .oauth_provider
+        .build_authorization_url(
+            &oauth_state.state,
+            &pkce::s256_challenge(&oauth_state.code_verifier),
+            &oauth_state.scopes,
+        )
         .map_err(|e| OAuthEndpointError::OAuthError(format!("Failed to build auth URL: {}", e)))?;
 
     info!(
@@ -138,12 +349,6 @@ pub async fn authorize_handler(
         "Redirecting to Miro authorization endpoint"
     );
 
-    // Build response with state cookie and redirect
-    let cookie_header = format!(
-        "{}={}; HttpOnly; Secure; SameSite=Lax; Max-Age={}; Path=/",
-        STATE_COOKIE_NAME, encrypted_state, STATE_COOKIE_MAX_AGE
-    );
-
     Ok((
         StatusCode::FOUND,
         [
@@ -166,8 +371,9 @@ pub async fn authorize_handler(
 /// # Flow
 /// 1. Extract and validate state from cookie (CSRF protection)
 /// 2. Verify state parameter matches cookie
-/// 3. Store authorization code + PKCE verifier in encrypted cookie (temporary)
-/// 4. Redirect to Claude.ai WITH code in URL: redirect_uri?code=XXX&state=YYY
+/// 3. Forward a Miro error (e.g. the user denied access) to the client's redirect_uri
+/// 4. Store authorization code + PKCE verifier server-side, bound to the client and redirect_uri
+/// 5. Redirect to Claude.ai WITH code in URL: redirect_uri?code=XXX&state=<client state>
 pub async fn callback_handler(
     State(state): State<crate::http_server::AppStateADR002>,
     Query(params): Query<CallbackParams>,
@@ -176,9 +382,10 @@ pub async fn callback_handler(
     let cookie_manager = &state.cookie_manager;
     info!("Handling OAuth callback from Miro");
 
-    // Check for OAuth error from Miro
-    if let Some(error) = params.error {
-        let description = params.error_description.unwrap_or_default();
+    // Without the state cookie we don't know where to send Miro's error
+    let state_cookie = extract_cookie(&headers, STATE_COOKIE_NAME);
+    if let (Some(error), None) = (&params.error, &state_cookie) {
+        let description = params.error_description.clone().unwrap_or_default();
         error!(error = %error, description = %description, "OAuth error from Miro");
         return Err(OAuthEndpointError::OAuthError(format!(
             "Miro OAuth error: {} - {}",
@@ -187,7 +394,7 @@ pub async fn callback_handler(
     }
 
     // Extract state from cookie
-    let state_cookie = extract_cookie(&headers, STATE_COOKIE_NAME)
+    let state_cookie = state_cookie
         .ok_or_else(|| OAuthEndpointError::InvalidState("State cookie not found".to_string()))?;
 
     let oauth_state: OAuthState = cookie_manager
@@ -207,8 +414,34 @@ pub async fn callback_handler(
         ));
     }
 
+    // The Miro redirect only happens after consent, so this can't be a genuine callback
+    if oauth_state.csrf_token.is_some() {
+        warn!(client_id = %oauth_state.client_id, "Callback before consent was given");
+        return Err(OAuthEndpointError::InvalidState(
+            "Consent was not given".to_string(),
+        ));
+    }
+
     info!("State validated successfully");
 
+    // Forward OAuth error from Miro to the client (RFC 6749 §4.1.2.1)
+    if let Some(error) = params.error {
+        let description = params.error_description.unwrap_or_default();
+        error!(error = %error, description = %description, "OAuth error from Miro");
+        return Err(OAuthEndpointError::Redirect(Box::new(
+            AuthorizationErrorRedirect {
+                redirect_uri: oauth_state.redirect_uri,
+                error: if error == "access_denied" {
+                    "access_denied"
+                } else {
+                    "server_error"
+                },
+                description: format!("Miro authorization failed: {}", description),
+                state: oauth_state.client_state,
+            },
+        )));
+    }
+
     // Extract authorization code
     let code = params.code.as_ref().ok_or_else(|| {
         OAuthEndpointError::InvalidRequest("Authorization code missing".to_string())
@@ -223,10 +456,18 @@ pub async fn callback_handler(
     let pending_exchange = super::types::PendingCodeExchange {
         code: code.clone(),
         code_verifier: oauth_state.code_verifier.clone(),
+        client_id: oauth_state.client_id.clone(),
+        redirect_uri: oauth_state.redirect_uri.clone(),
+        client_pkce: oauth_state.client_pkce.clone(),
+        scopes: oauth_state.scopes.clone(),
         expires_at: Utc::now() + chrono::Duration::seconds(PENDING_CODE_MAX_AGE),
     };
 
-    state.code_storage.store(code, pending_exchange).await;
+    state
+        .code_storage
+        .store(code, pending_exchange)
+        .await
+        .map_err(|e| OAuthEndpointError::StorageError(e.to_string()))?;
 
     // Clear state cookie (no longer needed)
     let clear_state_cookie = format!(
@@ -234,11 +475,12 @@ pub async fn callback_handler(
         STATE_COOKIE_NAME
     );
 
-    // Redirect to Claude.ai WITH the authorization code in URL (standard OAuth2 flow)
-    let redirect_url = format!(
-        "{}?code={}&state={}",
-        oauth_state.redirect_uri, code, state_param
-    );
+    // Redirect to Claude.ai WITH the authorization code and its own state (standard OAuth2 flow)
+    let mut redirect_params = vec![("code", code.as_str())];
+    if let Some(client_state) = &oauth_state.client_state {
+        redirect_params.push(("state", client_state.as_str()));
+    }
+    let redirect_url = redirect_with_params(&oauth_state.redirect_uri, &redirect_params);
 
     info!(
         redirect_url = %redirect_url,
@@ -255,22 +497,45 @@ pub async fn callback_handler(
         .into_response())
 }
 
-/// Handle POST /oauth/token - Exchange authorization code for access token
+/// Grant types accepted by the token endpoint
+const SUPPORTED_GRANT_TYPES: [&str; 2] = ["authorization_code", "refresh_token"];
+
+/// Storage namespace binding Miro refresh tokens to the client they were issued to
+const REFRESH_BINDINGS_NAMESPACE: &str = "refresh_bindings";
+
+/// How long a refresh token binding is kept (Miro refresh tokens last 60 days)
+const REFRESH_BINDING_TTL_DAYS: i64 = 60;
+
+/// Storage namespace binding Miro access tokens to the client they were issued to
+const ACCESS_BINDINGS_NAMESPACE: &str = "access_bindings";
+
+/// Handle POST /oauth/token - Issue tokens for an authorization code or refresh token
+///
+/// Standard OAuth2 token endpoint that Claude.ai calls to get an access token.
+/// After authenticating the client, this endpoint:
+/// - `authorization_code`: exchanges the code (with the PKCE verifier stored
+///   during callback) with Miro
+/// - `refresh_token`: forwards the refresh token to Miro, which returns a new
+///   access token and, when it rotates them, a new refresh token. Only the
+///   client the refresh token was issued to may redeem it.
 ///
-/// Standard OAuth2 token endpoint that Claude.ai calls to exchange the authorization code
-/// for an access token. This endpoint:
-/// 1. Extracts authorization code from request (from Claude.ai)
-/// 2. Retrieves PKCE verifier from encrypted cookie (stored during callback)
-/// 3. Exchanges code with Miro API for access token
-/// 4. Returns token in RFC 6749 format
+/// Either way the token pair is returned in RFC 6749 format, with the scopes
+/// Miro actually granted out of those requested at /authorize. With
+/// `issue_proxy_tokens` enabled, Miro's tokens stay server-side and the client
+/// gets proxy-owned tokens instead; refreshing those never calls Miro.
 ///
-/// # Request Format (application/x-www-form-urlencoded or JSON)
+/// # Request Format (application/x-www-form-urlencoded)
 /// ```text
 /// grant_type=authorization_code
 /// code=<authorization_code>
 /// redirect_uri=<redirect_uri>
 /// client_id=<client_id>
-/// code_verifier=<pkce_verifier> (optional, we have it in cookie)
+/// code_verifier=<pkce_verifier> (optional, we have it server-side)
+/// ```
+/// ```text
+/// grant_type=refresh_token
+/// refresh_token=<refresh_token>
+/// client_id=<client_id>
 /// ```
 ///
 /// # Response Format (RFC 6749)
@@ -288,10 +553,6 @@ pub async fn token_handler(
     headers: HeaderMap,
     axum::extract::Form(token_request): axum::extract::Form<super::types::TokenRequest>,
 ) -> Result<Json<TokenResponseRfc6749>, OAuthEndpointError> {
-    let provider = &state.oauth_provider;
-    let config = &state.config;
-    let client_registry = &state.client_registry;
-
     info!(
         grant_type = %token_request.grant_type,
         client_id = %token_request.client_id,
@@ -299,26 +560,114 @@ pub async fn token_handler(
     );
 
     // Validate grant_type
-    if token_request.grant_type != "authorization_code" {
-        return Err(OAuthEndpointError::InvalidRequest(format!(
+    if !SUPPORTED_GRANT_TYPES.contains(&token_request.grant_type.as_str()) {
+        return Err(OAuthEndpointError::UnsupportedGrantType(format!(
             "Unsupported grant_type: {}",
             token_request.grant_type
         )));
     }
 
+    let public_client = authenticate_client(&state, &headers, &token_request)?;
+
+    if let Some(proxy_tokens) = &state.proxy_tokens {
+        let issued = if token_request.grant_type == "refresh_token" {
+            refresh_proxy_tokens(proxy_tokens, &token_request).await?
+        } else {
+            let cookie_data =
+                exchange_authorization_code(&state, &token_request, public_client).await?;
+            proxy_tokens
+                .issue(
+                    &token_request.client_id,
+                    &granted_scope(&cookie_data),
+                    &cookie_data,
+                )
+                .map_err(|e| OAuthEndpointError::StorageError(e.to_string()))?
+        };
+
+        info!(
+            client_id = %token_request.client_id,
+            grant_type = %token_request.grant_type,
+            expires_in = %issued.expires_in,
+            "Issued proxy access token"
+        );
+
+        return Ok(Json(TokenResponseRfc6749 {
+            access_token: issued.access_token,
+            token_type: "Bearer".to_string(),
+            expires_in: issued.expires_in,
+            refresh_token: Some(issued.refresh_token),
+            scope: Some(issued.scope),
+        }));
+    }
+
+    let cookie_data = if token_request.grant_type == "refresh_token" {
+        refresh_access_token(&state, &token_request).await?
+    } else {
+        exchange_authorization_code(&state, &token_request, public_client).await?
+    };
+    bind_tokens(&state, &token_request.client_id, &cookie_data)?;
+
+    // Calculate token expiration
+    let now = Utc::now();
+    let expires_in = (cookie_data.expires_at - now).num_seconds().max(0);
+    let scope = granted_scope(&cookie_data);
+
+    info!(
+        user_id = %cookie_data.user_info.user_id,
+        grant_type = %token_request.grant_type,
+        expires_in = %expires_in,
+        scope = %scope,
+        "Issued access token"
+    );
+
+    // Return token in RFC 6749 format to Claude.ai
+    Ok(Json(TokenResponseRfc6749 {
+        access_token: cookie_data.access_token,
+        token_type: "Bearer".to_string(),
+        expires_in,
+        refresh_token: Some(cookie_data.refresh_token),
+        scope: Some(scope),
+    }))
+}
+
+/// Scope to report for Miro's tokens
+///
+/// Miro normally reports the scopes it granted; tokens it didn't report them
+/// for are assumed to carry the defaults.
+fn granted_scope(cookie_data: &CookieData) -> String {
+    cookie_data
+        .scope
+        .clone()
+        .unwrap_or_else(|| scopes::DEFAULT_SCOPES.join(" "))
+}
+
+/// Authenticate the client calling the token endpoint
+///
+/// DCR registered clients authenticate with their secret (client_secret_basic or
+/// client_secret_post) and may only use grant types they registered for. Public
+/// clients (registered with token_endpoint_auth_method "none") are accepted
+/// without a secret and must prove possession with PKCE instead.
+///
+/// Returns whether the client authenticated without a secret.
+fn authenticate_client(
+    state: &crate::http_server::AppStateADR002,
+    headers: &HeaderMap,
+    token_request: &super::types::TokenRequest,
+) -> Result<bool, OAuthEndpointError> {
     // Extract client_secret from either Authorization header (client_secret_basic) or form body (client_secret_post)
-    let client_secret = extract_client_secret(&headers, &token_request);
+    let client_secret = extract_client_secret(headers, token_request);
 
-    // Validate client credentials
-    // Priority: 1) DCR registered clients, 2) Manual config client_id (backwards compatibility)
+    // Validate client credentials against the DCR registry
     let is_valid_client = if let Some(ref secret) = client_secret {
         // Client provided secret - validate against registry (DCR)
         info!(client_id = %token_request.client_id, "Validating DCR registered client");
-        client_registry.validate(&token_request.client_id, secret)
+        state
+            .client_registry
+            .validate(&token_request.client_id, secret)
     } else {
-        // No secret provided - check if it's our manual config client (backwards compatibility)
-        info!(client_id = %token_request.client_id, "Checking manual config client");
-        token_request.client_id == config.client_id
+        // No secret provided - must be a public DCR client
+        info!(client_id = %token_request.client_id, "Checking public client");
+        state.client_registry.is_public(&token_request.client_id)
     };
 
     if !is_valid_client {
@@ -332,7 +681,36 @@ pub async fn token_handler(
         ));
     }
 
+    if let Some(client) = state.client_registry.get(&token_request.client_id) {
+        if !client.grant_types.contains(&token_request.grant_type) {
+            warn!(
+                client_id = %token_request.client_id,
+                grant_type = %token_request.grant_type,
+                "Client not registered for grant type"
+            );
+            return Err(OAuthEndpointError::UnauthorizedClient(format!(
+                "Client is not registered for the {} grant",
+                token_request.grant_type
+            )));
+        }
+    }
+
     info!(client_id = %token_request.client_id, "Client authenticated successfully");
+    Ok(client_secret.is_none())
+}
+
+/// authorization_code grant: check the client's PKCE verifier, then exchange the
+/// stored code and our own PKCE verifier with Miro
+async fn exchange_authorization_code(
+    state: &crate::http_server::AppStateADR002,
+    token_request: &super::types::TokenRequest,
+    public_client: bool,
+) -> Result<super::types::CookieData, OAuthEndpointError> {
+    if token_request.code.is_empty() {
+        return Err(OAuthEndpointError::InvalidRequest(
+            "code is required for the authorization_code grant".to_string(),
+        ));
+    }
 
     // Retrieve pending code exchange from server-side storage
     let pending_exchange = state
@@ -344,7 +722,7 @@ pub async fn token_handler(
                 code_length = token_request.code.len(),
                 "Authorization code not found or expired"
             );
-            OAuthEndpointError::Unauthorized(
+            OAuthEndpointError::InvalidGrant(
                 "Authorization code not found or expired - may have been used already or flow not completed".to_string(),
             )
         })?;
@@ -352,41 +730,410 @@ pub async fn token_handler(
     // Validate code matches (redundant but keeps consistency)
     if token_request.code != pending_exchange.code {
         warn!("Authorization code mismatch");
-        return Err(OAuthEndpointError::Unauthorized(
+        return Err(OAuthEndpointError::InvalidGrant(
             "Authorization code mismatch".to_string(),
         ));
     }
 
+    // The code is bound to the client and redirect_uri of the authorization request
+    if token_request.client_id != pending_exchange.client_id {
+        warn!(client_id = %token_request.client_id, "Authorization code issued to another client");
+        return Err(OAuthEndpointError::InvalidGrant(
+            "Authorization code was issued to another client".to_string(),
+        ));
+    }
+    if token_request.redirect_uri != pending_exchange.redirect_uri {
+        warn!(
+            client_id = %token_request.client_id,
+            redirect_uri = %token_request.redirect_uri,
+            "redirect_uri differs from authorization request"
+        );
+        return Err(OAuthEndpointError::InvalidGrant(
+            "redirect_uri does not match the authorization request".to_string(),
+        ));
+    }
+
+    // The code has been consumed either way, so a failed check can't be retried
+    match &pending_exchange.client_pkce {
+        Some(client_pkce) => pkce::verify_challenge(
+            token_request.code_verifier.as_deref(),
+            &client_pkce.code_challenge,
+            &client_pkce.code_challenge_method,
+        )
+        .map_err(|e| {
+            warn!(client_id = %token_request.client_id, error = %e, "PKCE verification failed");
+            OAuthEndpointError::InvalidGrant(e.to_string())
+        })?,
+        None if public_client => {
+            warn!(client_id = %token_request.client_id, "Public client code issued without PKCE");
+            return Err(OAuthEndpointError::InvalidGrant(
+                "PKCE is required for public clients".to_string(),
+            ));
+        }
+        None => {}
+    }
+
     info!("Exchanging authorization code with Miro API");
 
     // Exchange code for access token with Miro
-    let cookie_data = provider
+    let mut cookie_data = state
+        .oauth_provider
         .exchange_code_for_token(&pending_exchange.code, &pending_exchange.code_verifier)
         .await
         .map_err(|e| {
             OAuthEndpointError::OAuthError(format!("Token exchange with Miro failed: {}", e))
         })?;
 
-    // Calculate token expiration
-    let now = Utc::now();
-    let expires_in = (cookie_data.expires_at - now).num_seconds().max(0);
+    // The grant is what was requested, less anything Miro declined
+    let granted = scopes::intersect(&pending_exchange.scopes, cookie_data.scope.as_deref());
+    info!(
+        client_id = %token_request.client_id,
+        requested = ?pending_exchange.scopes,
+        granted = ?granted,
+        "Authorization code exchanged"
+    );
+    cookie_data.scope = Some(granted.join(" "));
+    Ok(cookie_data)
+}
+
+/// refresh_token grant: forward the refresh token to Miro for a new token pair
+///
+/// Miro refresh tokens are redeemed with this server's Miro credentials, so a
+/// token is only forwarded for the client it was issued to. Its binding is
+/// dropped once Miro accepts it; the caller binds the new refresh token.
+async fn refresh_access_token(
+    state: &crate::http_server::AppStateADR002,
+    token_request: &super::types::TokenRequest,
+) -> Result<super::types::CookieData, OAuthEndpointError> {
+    let refresh_token = token_request
+        .refresh_token
+        .as_deref()
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| {
+            OAuthEndpointError::InvalidRequest(
+                "refresh_token is required for the refresh_token grant".to_string(),
+            )
+        })?;
+
+    if let Some(scope) = &token_request.scope {
+        // Miro can't narrow a refresh, so the original grant's scopes are kept
+        info!(scope = %scope, "Ignoring scope on refresh request");
+    }
+
+    let storage = state.code_storage.backend();
+    let binding = token_hash(refresh_token);
+    let bound_client = storage
+        .get(REFRESH_BINDINGS_NAMESPACE, &binding)
+        .map_err(|e| OAuthEndpointError::StorageError(e.to_string()))?;
+    if bound_client.as_deref() != Some(token_request.client_id.as_str()) {
+        warn!(client_id = %token_request.client_id, "Refresh token not issued to this client");
+        return Err(OAuthEndpointError::InvalidGrant(
+            "Refresh token is invalid, expired or revoked".to_string(),
+        ));
+    }
+
+    info!("Refreshing access token with Miro API");
+
+    let refreshed = state
+        .oauth_provider
+        .refresh_token(refresh_token)
+        .await
+        .map_err(|e| match e {
+            MiroOAuthError::OAuthError { error, .. } if error == "invalid_grant" => {
+                warn!("Miro rejected refresh token");
+                OAuthEndpointError::InvalidGrant(
+                    "Refresh token is invalid, expired or revoked".to_string(),
+                )
+            }
+            e => OAuthEndpointError::OAuthError(format!("Token refresh with Miro failed: {}", e)),
+        })?;
+    storage
+        .take(REFRESH_BINDINGS_NAMESPACE, &binding)
+        .map_err(|e| OAuthEndpointError::StorageError(e.to_string()))?;
+    Ok(refreshed)
+}
+
+/// Record which client a Miro token pair was issued to
+fn bind_tokens(
+    state: &crate::http_server::AppStateADR002,
+    client_id: &str,
+    tokens: &super::types::CookieData,
+) -> Result<(), OAuthEndpointError> {
+    let storage = state.code_storage.backend();
+    storage
+        .put(
+            ACCESS_BINDINGS_NAMESPACE,
+            &token_hash(&tokens.access_token),
+            client_id,
+            Some(tokens.expires_at),
+        )
+        .map_err(|e| OAuthEndpointError::StorageError(e.to_string()))?;
+    if tokens.refresh_token.is_empty() {
+        return Ok(());
+    }
+    let expires_at = Utc::now() + chrono::Duration::days(REFRESH_BINDING_TTL_DAYS);
+    storage
+        .put(
+            REFRESH_BINDINGS_NAMESPACE,
+            &token_hash(&tokens.refresh_token),
+            client_id,
+            Some(expires_at),
+        )
+        .map_err(|e| OAuthEndpointError::StorageError(e.to_string()))
+}
+
+/// Whether a Miro token was issued to `client_id`, as an access or refresh token
+fn is_bound_to(
+    state: &crate::http_server::AppStateADR002,
+    client_id: &str,
+    token: &str,
+) -> Result<bool, OAuthEndpointError> {
+    let storage = state.code_storage.backend();
+    let binding = token_hash(token);
+    for namespace in [ACCESS_BINDINGS_NAMESPACE, REFRESH_BINDINGS_NAMESPACE] {
+        let bound_client = storage
+            .get(namespace, &binding)
+            .map_err(|e| OAuthEndpointError::StorageError(e.to_string()))?;
+        if bound_client.as_deref() == Some(client_id) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Drop a revoked Miro token's client binding
+fn unbind_token(
+    state: &crate::http_server::AppStateADR002,
+    token: &str,
+) -> Result<(), OAuthEndpointError> {
+    let storage = state.code_storage.backend();
+    let binding = token_hash(token);
+    for namespace in [ACCESS_BINDINGS_NAMESPACE, REFRESH_BINDINGS_NAMESPACE] {
+        storage
+            .take(namespace, &binding)
+            .map_err(|e| OAuthEndpointError::StorageError(e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// refresh_token grant with proxy-owned tokens: rotate the client's refresh token
+async fn refresh_proxy_tokens(
+    proxy_tokens: &ProxyTokenStore,
+    token_request: &super::types::TokenRequest,
+) -> Result<IssuedTokens, OAuthEndpointError> {
+    let refresh_token = token_request
+        .refresh_token
+        .as_deref()
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| {
+            OAuthEndpointError::InvalidRequest(
+                "refresh_token is required for the refresh_token grant".to_string(),
+            )
+        })?;
+
+    proxy_tokens
+        .refresh(&token_request.client_id, refresh_token)
+        .await
+        .map_err(|e| match e {
+            ProxyTokenError::InvalidToken => {
+                warn!(client_id = %token_request.client_id, "Proxy refresh token rejected");
+                OAuthEndpointError::InvalidGrant(
+                    "Refresh token is invalid, expired or revoked".to_string(),
+                )
+            }
+            e => OAuthEndpointError::StorageError(e.to_string()),
+        })
+}
 
+/// Handle POST /oauth/revoke - Revoke an access or refresh token (RFC 7009)
+///
+/// Called when a user disconnects the server in Claude.ai. The token is revoked
+/// with Miro, which ends the whole token pair, and evicted from the
+/// `TokenValidator` cache so it stops working here immediately rather than when
+/// its cache entry expires. Only tokens issued to the requesting client are
+/// revoked (RFC 7009 §2.1). With proxy-owned tokens the grant behind the token
+/// is dropped and its Miro tokens are revoked instead.
+///
+/// Unknown, expired and already revoked tokens get the same 200 response as
+/// revoked ones (RFC 7009 §2.2).
+pub async fn revoke_handler(
+    State(state): State<crate::http_server::AppStateADR002>,
+    headers: HeaderMap,
+    axum::extract::Form(request): axum::extract::Form<super::types::TokenOperationRequest>,
+) -> Result<StatusCode, OAuthEndpointError> {
+    let (client_id, _) = authenticate_token_client(&state, &headers, &request)?;
+    if request.token.is_empty() {
+        return Err(OAuthEndpointError::InvalidRequest(
+            "token is required".to_string(),
+        ));
+    }
     info!(
-        user_id = %cookie_data.user_info.user_id,
-        expires_in = %expires_in,
-        "Successfully exchanged code for access token"
+        client_id = %client_id,
+        token_type_hint = ?request.token_type_hint,
+        "Revocation requested"
     );
 
-    // Return token in RFC 6749 format to Claude.ai
-    Ok(Json(TokenResponseRfc6749 {
-        access_token: cookie_data.access_token,
-        token_type: "Bearer".to_string(),
-        expires_in,
-        refresh_token: Some(cookie_data.refresh_token),
-        scope: Some("boards:read boards:write".to_string()),
+    let Some(proxy_tokens) = &state.proxy_tokens else {
+        if !is_bound_to(&state, &client_id, &request.token)? {
+            info!(client_id = %client_id, "Token not issued to this client, nothing revoked");
+            return Ok(StatusCode::OK);
+        }
+        state.token_validator.evict(&request.token);
+        state
+            .oauth_provider
+            .revoke_token(&request.token)
+            .await
+            .map_err(|e| {
+                OAuthEndpointError::Unavailable(format!("Token revocation with Miro failed: {}", e))
+            })?;
+        unbind_token(&state, &request.token)?;
+        info!(client_id = %client_id, "Token revoked");
+        return Ok(StatusCode::OK);
+    };
+
+    let revoked = proxy_tokens
+        .revoke_token(&client_id, &request.token)
+        .map_err(|e| OAuthEndpointError::StorageError(e.to_string()))?;
+    if let Some(miro_tokens) = revoked {
+        state.token_validator.evict(&miro_tokens.access_token);
+        // The grant is gone, so the proxy tokens are dead even if Miro can't be reached
+        if let Err(e) = state
+            .oauth_provider
+            .revoke_token(&miro_tokens.refresh_token)
+            .await
+        {
+            warn!(client_id = %client_id, error = %e, "Revoking Miro tokens failed");
+        }
+        info!(client_id = %client_id, "Proxy token grant revoked");
+    }
+    Ok(StatusCode::OK)
+}
+
+/// Token introspection response (RFC 7662 §2.2)
+#[derive(Debug, Default, Serialize)]
+pub struct IntrospectionResponse {
+    active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exp: Option<i64>,
+    /// Miro user ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sub: Option<String>,
+    /// Miro team ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    team_id: Option<String>,
+}
+
+/// Handle POST /oauth/introspect - Describe a token (RFC 7662)
+///
+/// Only confidential clients (registered with a secret) may introspect. Any
+/// token that doesn't validate, including ones Miro rejects, is reported as
+/// `{"active": false}` without further detail.
+pub async fn introspect_handler(
+    State(state): State<crate::http_server::AppStateADR002>,
+    headers: HeaderMap,
+    axum::extract::Form(request): axum::extract::Form<super::types::TokenOperationRequest>,
+) -> Result<Json<IntrospectionResponse>, OAuthEndpointError> {
+    let (client_id, public_client) = authenticate_token_client(&state, &headers, &request)?;
+    if public_client {
+        warn!(client_id = %client_id, "Public client attempted token introspection");
+        return Err(OAuthEndpointError::Unauthorized(
+            "Token introspection requires client authentication".to_string(),
+        ));
+    }
+
+    let (miro_token, proxy_access) = match &state.proxy_tokens {
+        Some(proxy_tokens) => {
+            match proxy_tokens
+                .resolve(&request.token, &state.oauth_provider)
+                .await
+            {
+                Ok(access) => (access.miro_access_token.clone(), Some(access)),
+                Err(e) => {
+                    info!(client_id = %client_id, error = %e, "Introspected token is inactive");
+                    return Ok(Json(IntrospectionResponse::default()));
+                }
+            }
+        }
+        None => (request.token.clone(), None),
+    };
+
+    let user_info = match state.token_validator.validate_token(&miro_token).await {
+        Ok(user_info) => user_info,
+        Err(e) => {
+            info!(client_id = %client_id, error = %e, "Introspected token is inactive");
+            return Ok(Json(IntrospectionResponse::default()));
+        }
+    };
+
+    info!(client_id = %client_id, user_id = %user_info.user_id, "Introspected active token");
+    Ok(Json(IntrospectionResponse {
+        active: true,
+        scope: Some(match &proxy_access {
+            Some(access) => access.scope.clone(),
+            None => user_info.scopes.join(" "),
+        }),
+        client_id: proxy_access.as_ref().map(|access| access.client_id.clone()),
+        token_type: Some("Bearer".to_string()),
+        exp: proxy_access.map(|access| access.expires_at.timestamp()),
+        sub: Some(user_info.user_id),
+        team_id: Some(user_info.team_id),
     }))
 }
 
+/// Authenticate the client calling the revocation or introspection endpoint
+///
+/// Same rules as the token endpoint: DCR clients authenticate with their secret
+/// (basic or post), public clients just name themselves. Returns the client_id
+/// and whether it authenticated without a secret.
+fn authenticate_token_client(
+    state: &crate::http_server::AppStateADR002,
+    headers: &HeaderMap,
+    request: &super::types::TokenOperationRequest,
+) -> Result<(String, bool), OAuthEndpointError> {
+    let (client_id, client_secret) = match extract_basic_credentials(headers) {
+        Some((client_id, client_secret)) => (Some(client_id), Some(client_secret)),
+        None => (request.client_id.clone(), request.client_secret.clone()),
+    };
+    let client_id = client_id.filter(|id| !id.is_empty()).ok_or_else(|| {
+        OAuthEndpointError::Unauthorized("Client authentication required".to_string())
+    })?;
+
+    let is_valid_client = match &client_secret {
+        Some(secret) => state.client_registry.validate(&client_id, secret),
+        None => state.client_registry.is_public(&client_id),
+    };
+    if !is_valid_client {
+        warn!(client_id = %client_id, "Client authentication failed");
+        return Err(OAuthEndpointError::Unauthorized(
+            "Invalid client credentials".to_string(),
+        ));
+    }
+    Ok((client_id, client_secret.is_none()))
+}
+
+/// Append query parameters to a client redirect URI, keeping any query it already has
+fn redirect_with_params(redirect_uri: &str, params: &[(&str, &str)]) -> String {
+    match url::Url::parse(redirect_uri) {
+        Ok(mut url) => {
+            url.query_pairs_mut().extend_pairs(params);
+            url.to_string()
+        }
+        Err(_) => {
+            let query = url::form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(params)
+                .finish();
+            let separator = if redirect_uri.contains('?') { '&' } else { '?' };
+            format!("{}{}{}", redirect_uri, separator, query)
+        }
+    }
+}
+
 /// Extract cookie value from request headers
 fn extract_cookie(headers: &HeaderMap, cookie_name: &str) -> Option<String> {
     headers
@@ -410,60 +1157,152 @@ fn extract_client_secret(
     headers: &HeaderMap,
     token_request: &super::types::TokenRequest,
 ) -> Option<String> {
-    // Try client_secret_basic (Authorization: Basic base64(client_id:client_secret))
-    if let Some(auth_header) = headers.get(header::AUTHORIZATION) {
-        if let Ok(auth_str) = auth_header.to_str() {
-            if let Some(basic_token) = auth_str.strip_prefix("Basic ") {
-                // Decode base64
-                if let Ok(decoded_bytes) = URL_SAFE_NO_PAD.decode(basic_token.as_bytes()) {
-                    if let Ok(decoded_str) = String::from_utf8(decoded_bytes) {
-                        // Split by : to get client_id:client_secret
-                        let (_client_id, client_secret) = decoded_str.split_once(':')?;
-                        return Some(client_secret.to_string());
-                    }
-                }
-            }
+    // Try client_secret_basic, then client_secret_post (included in form body)
+    extract_basic_credentials(headers)
+        .map(|(_client_id, client_secret)| client_secret)
+        .or_else(|| token_request.client_secret.clone())
+}
+
+/// Extract (client_id, client_secret) from `Authorization: Basic base64(client_id:client_secret)`
+fn extract_basic_credentials(headers: &HeaderMap) -> Option<(String, String)> {
+    let auth_str = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let basic_token = auth_str.strip_prefix("Basic ")?;
+    // Decode base64 (RFC 7617 uses the standard alphabet; accept unpadded base64url too)
+    let decoded_bytes = STANDARD
+        .decode(basic_token.trim().as_bytes())
+        .or_else(|_| URL_SAFE_NO_PAD.decode(basic_token.trim().as_bytes()))
+        .ok()?;
+    let decoded_str = String::from_utf8(decoded_bytes).ok()?;
+    // Split by : to get client_id:client_secret
+    let (client_id, client_secret) = decoded_str.split_once(':')?;
+    Some((client_id.to_string(), client_secret.to_string()))
+}
+
+/// Authorization error sent back to the client's redirect_uri (RFC 6749 §4.1.2.1)
+#[derive(Debug)]
+pub struct AuthorizationErrorRedirect {
+    pub redirect_uri: String,
+    /// RFC 6749 error code, e.g. "invalid_request" or "access_denied"
+    pub error: &'static str,
+    pub description: String,
+    /// The client's state parameter, echoed back unchanged
+    pub state: Option<String>,
+}
+
+impl IntoResponse for AuthorizationErrorRedirect {
+    fn into_response(self) -> Response {
+        warn!(
+            error = self.error,
+            description = %self.description,
+            "Returning authorization error to client"
+        );
+        let mut params = vec![
+            ("error", self.error),
+            ("error_description", self.description.as_str()),
+        ];
+        if let Some(state) = &self.state {
+            params.push(("state", state.as_str()));
         }
+        let location = redirect_with_params(&self.redirect_uri, &params);
+        let clear_state_cookie = format!(
+            "{}=; HttpOnly; Secure; SameSite=Lax; Max-Age=0; Path=/",
+            STATE_COOKIE_NAME
+        );
+        (
+            StatusCode::FOUND,
+            [
+                (header::SET_COOKIE, clear_state_cookie),
+                (header::LOCATION, location),
+            ],
+        )
+            .into_response()
     }
-
-    // Try client_secret_post (included in form body)
-    token_request.client_secret.clone()
 }
 
 /// Errors from OAuth endpoint handlers
+///
+/// Rendered as RFC 6749 §5.2 error responses: a JSON body with `error` and
+/// `error_description`, so clients can tell a dead refresh token from bad
+/// credentials.
 #[derive(Debug)]
 pub enum OAuthEndpointError {
     InvalidState(String),
     InvalidRequest(String),
+    InvalidClient(String),
+    Redirect(Box<AuthorizationErrorRedirect>),
+    InvalidGrant(String),
+    UnsupportedGrantType(String),
+    /// Authenticated client that isn't registered for the requested grant
+    UnauthorizedClient(String),
     OAuthError(String),
     CookieError(String),
+    StorageError(String),
+    Unavailable(String),
+    /// Client authentication failed
     Unauthorized(String),
 }
 
 impl IntoResponse for OAuthEndpointError {
     fn into_response(self) -> Response {
-        let (status, message) = match self {
-            OAuthEndpointError::InvalidState(msg) => {
-                (StatusCode::BAD_REQUEST, format!("Invalid state: {}", msg))
-            }
+        let (status, error_code, description) = match self {
+            OAuthEndpointError::InvalidState(msg) => (
+                StatusCode::BAD_REQUEST,
+                "invalid_request",
+                format!("Invalid state: {}", msg),
+            ),
             OAuthEndpointError::InvalidRequest(msg) => {
-                (StatusCode::BAD_REQUEST, format!("Invalid request: {}", msg))
+                (StatusCode::BAD_REQUEST, "invalid_request", msg)
             }
-            OAuthEndpointError::OAuthError(msg) => {
-                (StatusCode::BAD_REQUEST, format!("OAuth error: {}", msg))
+            OAuthEndpointError::InvalidClient(msg) => {
+                (StatusCode::BAD_REQUEST, "invalid_client", msg)
             }
+            OAuthEndpointError::Redirect(redirect) => return redirect.into_response(),
+            OAuthEndpointError::InvalidGrant(msg) => {
+                (StatusCode::BAD_REQUEST, "invalid_grant", msg)
+            }
+            OAuthEndpointError::UnsupportedGrantType(msg) => {
+                (StatusCode::BAD_REQUEST, "unsupported_grant_type", msg)
+            }
+            OAuthEndpointError::UnauthorizedClient(msg) => {
+                (StatusCode::BAD_REQUEST, "unauthorized_client", msg)
+            }
+            OAuthEndpointError::OAuthError(msg) => (StatusCode::BAD_GATEWAY, "server_error", msg),
             OAuthEndpointError::CookieError(msg) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
+                "server_error",
                 format!("Cookie error: {}", msg),
             ),
+            OAuthEndpointError::StorageError(msg) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "server_error",
+                format!("Storage error: {}", msg),
+            ),
+            OAuthEndpointError::Unavailable(msg) => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "temporarily_unavailable",
+                msg,
+            ),
             OAuthEndpointError::Unauthorized(msg) => {
-                (StatusCode::UNAUTHORIZED, format!("Unauthorized: {}", msg))
+                (StatusCode::UNAUTHORIZED, "invalid_client", msg)
             }
         };
 
-        error!(status = %status, message = %message, "OAuth endpoint error");
-
-        (status, message).into_response()
+        error!(status = %status, error = error_code, description = %description, "OAuth endpoint error");
+
+        let body = Json(serde_json::json!({
+            "error": error_code,
+            "error_description": description,
+        }));
+        if status == StatusCode::UNAUTHORIZED {
+            // RFC 6749 §5.2: a 401 names the authentication scheme the client should use
+            return (
+                status,
+                [(header::WWW_AUTHENTICATE, "Basic realm=\"oauth\"")],
+                body,
+            )
+                .into_response();
+        }
+        (status, body).into_response()
     }
 }
 
@@ -492,6 +1331,53 @@ mod tests {
         assert_eq!(extract_cookie(&headers, "nonexistent"), None);
     }
 
+    fn token_request(value: serde_json::Value) -> super::super::types::TokenRequest {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_refresh_token_request_needs_no_code() {
+        let request = token_request(serde_json::json!({
+            "grant_type": "refresh_token",
+            "refresh_token": "miro-refresh",
+            "client_id": "client-1"
+        }));
+        assert_eq!(request.refresh_token.as_deref(), Some("miro-refresh"));
+        assert!(request.code.is_empty());
+        assert!(SUPPORTED_GRANT_TYPES.contains(&request.grant_type.as_str()));
+    }
+
+    #[test]
+    fn test_extract_client_secret_basic() {
+        let request = token_request(serde_json::json!({
+            "grant_type": "refresh_token",
+            "client_id": "client-1"
+        }));
+        let mut headers = HeaderMap::new();
+        let credentials = STANDARD.encode("client-1:s3cr?t>");
+        headers.insert(
+            header::AUTHORIZATION,
+            format!("Basic {}", credentials).parse().unwrap(),
+        );
+        assert_eq!(
+            extract_client_secret(&headers, &request),
+            Some("s3cr?t>".to_string())
+        );
+        assert_eq!(extract_client_secret(&HeaderMap::new(), &request), None);
+    }
+
+    #[test]
+    fn test_redirect_with_params_keeps_existing_query() {
+        assert_eq!(
+            redirect_with_params("https://app.example/cb?tenant=a", &[("code", "x y")]),
+            "https://app.example/cb?tenant=a&code=x+y"
+        );
+        assert_eq!(
+            redirect_with_params("https://app.example/cb", &[("error", "access_denied")]),
+            "https://app.example/cb?error=access_denied"
+        );
+    }
+
     #[test]
     fn test_extract_cookie_with_spaces() {
         let mut headers = HeaderMap::new();
@@ -1,11 +1,14 @@
 //! OAuth2 state management and PKCE utilities for Miro authentication
 
 pub mod code_storage;
+pub mod consent;
 pub mod cookie_manager;
 pub mod dcr;
 pub mod endpoints;
 pub mod pkce;
 pub mod proxy_provider;
+pub mod proxy_tokens;
+pub mod storage;
 pub mod types;
 
 pub use code_storage::*;
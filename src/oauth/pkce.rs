@@ -4,6 +4,28 @@ use sha2::{Digest, Sha256};
 
 use super::types::PkcePair;
 
+/// The only code_challenge_method we accept; "plain" offers no protection
+pub const S256: &str = "S256";
+
+/// Why a PKCE check failed
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum PkceError {
+    #[error("Unsupported code_challenge_method: {0}. Only S256 is supported.")]
+    UnsupportedMethod(String),
+
+    #[error("code_challenge must be a base64url-encoded SHA-256 hash")]
+    MalformedChallenge,
+
+    #[error("code_verifier is required")]
+    MissingVerifier,
+
+    #[error("code_verifier must be 43-128 unreserved characters")]
+    MalformedVerifier,
+
+    #[error("code_verifier does not match code_challenge")]
+    Mismatch,
+}
+
 /// Generate PKCE code verifier and challenge pair (RFC 7636)
 ///
 /// The code verifier is a cryptographically random string used to bind the authorization
@@ -26,19 +48,61 @@ pub fn generate_pkce_pair() -> PkcePair {
 
     // Base64url encode verifier (no padding)
     let verifier = URL_SAFE_NO_PAD.encode(verifier_bytes);
+    let challenge = s256_challenge(&verifier);
+
+    PkcePair {
+        verifier,
+        challenge,
+    }
+}
 
-    // Compute SHA-256 hash of verifier
+/// Compute the S256 code challenge for a verifier: BASE64URL(SHA256(verifier))
+pub fn s256_challenge(verifier: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(verifier.as_bytes());
-    let challenge_bytes = hasher.finalize();
+    URL_SAFE_NO_PAD.encode(hasher.finalize())
+}
 
-    // Base64url encode challenge (no padding)
-    let challenge = URL_SAFE_NO_PAD.encode(challenge_bytes);
+/// Validate a client's code_challenge and method at the authorization endpoint
+///
+/// A missing method means "plain" (RFC 7636 §4.3), which we reject.
+pub fn validate_challenge(challenge: &str, method: Option<&str>) -> Result<(), PkceError> {
+    let method = method.unwrap_or("plain");
+    if method != S256 {
+        return Err(PkceError::UnsupportedMethod(method.to_string()));
+    }
+    let decodes_to_hash = URL_SAFE_NO_PAD
+        .decode(challenge.as_bytes())
+        .is_ok_and(|hash| hash.len() == 32);
+    if !decodes_to_hash {
+        return Err(PkceError::MalformedChallenge);
+    }
+    Ok(())
+}
 
-    PkcePair {
-        verifier,
-        challenge,
+/// Verify a client's code_verifier against the challenge it sent to /authorize
+pub fn verify_challenge(
+    verifier: Option<&str>,
+    challenge: &str,
+    method: &str,
+) -> Result<(), PkceError> {
+    if method != S256 {
+        return Err(PkceError::UnsupportedMethod(method.to_string()));
+    }
+    let verifier = verifier
+        .filter(|verifier| !verifier.is_empty())
+        .ok_or(PkceError::MissingVerifier)?;
+    let well_formed = (43..=128).contains(&verifier.len())
+        && verifier
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~'));
+    if !well_formed {
+        return Err(PkceError::MalformedVerifier);
     }
+    if s256_challenge(verifier) != challenge {
+        return Err(PkceError::Mismatch);
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -82,6 +146,47 @@ mod tests {
         assert_eq!(expected_challenge, actual_challenge);
     }
 
+    #[test]
+    fn test_verify_client_challenge() {
+        let pkce = generate_pkce_pair();
+        assert_eq!(validate_challenge(&pkce.challenge, Some(S256)), Ok(()));
+        assert_eq!(
+            verify_challenge(Some(&pkce.verifier), &pkce.challenge, S256),
+            Ok(())
+        );
+
+        let other = generate_pkce_pair();
+        assert_eq!(
+            verify_challenge(Some(&other.verifier), &pkce.challenge, S256),
+            Err(PkceError::Mismatch)
+        );
+        assert_eq!(
+            verify_challenge(None, &pkce.challenge, S256),
+            Err(PkceError::MissingVerifier)
+        );
+        assert_eq!(
+            verify_challenge(Some("short"), &pkce.challenge, S256),
+            Err(PkceError::MalformedVerifier)
+        );
+    }
+
+    #[test]
+    fn test_validate_challenge_rejects_plain() {
+        let pkce = generate_pkce_pair();
+        assert_eq!(
+            validate_challenge(&pkce.challenge, None),
+            Err(PkceError::UnsupportedMethod("plain".to_string()))
+        );
+        assert_eq!(
+            validate_challenge(&pkce.verifier, Some("plain")),
+            Err(PkceError::UnsupportedMethod("plain".to_string()))
+        );
+        assert_eq!(
+            validate_challenge("not-a-hash", Some(S256)),
+            Err(PkceError::MalformedChallenge)
+        );
+    }
+
     #[test]
     fn test_pkce_uniqueness() {
         // Each generation should produce unique verifier
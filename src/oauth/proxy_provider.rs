@@ -9,9 +9,7 @@ use super::types::{CookieData, TokenResponse, UserInfo};
 /// Miro OAuth endpoints
 const MIRO_AUTH_ENDPOINT: &str = "https://miro.com/oauth/authorize";
 const MIRO_TOKEN_ENDPOINT: &str = "https://api.miro.com/v1/oauth/token";
-
-/// Miro OAuth scopes
-const MIRO_SCOPES: &[&str] = &["boards:read", "boards:write"];
+const MIRO_REVOKE_ENDPOINT: &str = "https://api.miro.com/v2/oauth/revoke";
 
 /// Errors from Miro OAuth operations
 #[derive(Error, Debug)]
@@ -68,19 +66,21 @@ impl MiroOAuthProvider {
     /// # Arguments
     /// * `state` - CSRF protection nonce
     /// * `pkce_challenge` - PKCE code challenge (SHA-256 hash of verifier)
+    /// * `scopes` - Miro scopes to request (see `auth::scopes::map_requested_scope`)
     ///
     /// # Returns
     /// URL to redirect user to for Miro authorization
     ///
     /// # Example
     /// ```ignore
-    /// let url = provider.build_authorization_url("random_state", "pkce_challenge")?;
+    /// let url = provider.build_authorization_url("random_state", "pkce_challenge", &scopes)?;
     /// // Redirect user to: https://miro.com/oauth/authorize?client_id=...&response_type=code&...
     /// ```
     pub fn build_authorization_url(
         &self,
         state: &str,
         pkce_challenge: &str,
+        scopes: &[String],
     ) -> Result<Url, MiroOAuthError> {
         let mut url = Url::parse(MIRO_AUTH_ENDPOINT)?;
 
@@ -88,7 +88,7 @@ impl MiroOAuthProvider {
             .append_pair("client_id", &self.client_id)
             .append_pair("response_type", "code")
             .append_pair("redirect_uri", &self.redirect_uri)
-            .append_pair("scope", &MIRO_SCOPES.join(" "))
+            .append_pair("scope", &scopes.join(" "))
             .append_pair("state", state)
             .append_pair("code_challenge", pkce_challenge)
             .append_pair("code_challenge_method", "S256");
@@ -141,7 +141,7 @@ impl MiroOAuthProvider {
             .send()
             .await?;
 
-        self.parse_token_response(response).await
+        self.parse_token_response(response, None).await
     }
 
     /// Refresh access token using refresh token
@@ -180,13 +180,55 @@ impl MiroOAuthProvider {
             .send()
             .await?;
 
-        self.parse_token_response(response).await
+        // Miro only rotates refresh tokens when they're close to expiring
+        self.parse_token_response(response, Some(refresh_token))
+            .await
+    }
+
+    /// Revoke an access or refresh token with Miro
+    ///
+    /// Miro revokes the whole token pair. Tokens Miro rejects as unknown or
+    /// already revoked count as revoked (RFC 7009 §2.2).
+    ///
+    /// # Errors
+    /// Returns error if the HTTP request fails or Miro can't process the revocation
+    pub async fn revoke_token(&self, token: &str) -> Result<(), MiroOAuthError> {
+        #[derive(Serialize)]
+        struct RevokeRequest<'a> {
+            client_id: &'a str,
+            client_secret: &'a str,
+            token: &'a str,
+        }
+
+        let request_body = RevokeRequest {
+            client_id: &self.client_id,
+            client_secret: &self.client_secret,
+            token,
+        };
+
+        let response = self
+            .http_client
+            .post(MIRO_REVOKE_ENDPOINT)
+            .form(&request_body)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status.is_success() || status.is_client_error() {
+            Ok(())
+        } else {
+            Err(MiroOAuthError::InvalidResponse(format!(
+                "Miro revoke endpoint returned status {}",
+                status
+            )))
+        }
     }
 
     /// Parse token response from Miro and convert to CookieData
     ///
     /// # Arguments
     /// * `response` - HTTP response from Miro token endpoint
+    /// * `previous_refresh_token` - Refresh token to keep when Miro doesn't issue a new one
     ///
     /// # Returns
     /// `CookieData` with tokens and expiration
@@ -196,6 +238,7 @@ impl MiroOAuthProvider {
     async fn parse_token_response(
         &self,
         response: reqwest::Response,
+        previous_refresh_token: Option<&str>,
     ) -> Result<CookieData, MiroOAuthError> {
         let status = response.status();
 
@@ -237,6 +280,7 @@ impl MiroOAuthProvider {
         // Refresh token should be present in initial authorization, might be missing in refresh
         let refresh_token = token_response
             .refresh_token
+            .or_else(|| previous_refresh_token.map(str::to_string))
             .ok_or_else(|| MiroOAuthError::MissingField("refresh_token".to_string()))?;
 
         Ok(CookieData {
@@ -244,6 +288,7 @@ impl MiroOAuthProvider {
             refresh_token,
             expires_at,
             user_info,
+            scope: token_response.scope,
         })
     }
 }
@@ -264,7 +309,11 @@ mod tests {
     fn test_build_authorization_url() {
         let provider = get_test_provider();
         let url = provider
-            .build_authorization_url("test_state", "test_challenge")
+            .build_authorization_url(
+                "test_state",
+                "test_challenge",
+                &["boards:read".to_string(), "team:read".to_string()],
+            )
             .unwrap();
 
         assert_eq!(url.scheme(), "https");
@@ -289,9 +338,10 @@ mod tests {
             Some(&"http://localhost:3000/oauth/callback".to_string())
         );
 
-        // Verify scopes
-        let scopes = params.get("scope").unwrap();
-        assert!(scopes.contains("boards:read"));
-        assert!(scopes.contains("boards:write"));
+        // Only the requested scopes are asked for
+        assert_eq!(
+            params.get("scope"),
+            Some(&"boards:read team:read".to_string())
+        );
     }
 }
@@ -0,0 +1,602 @@
+//! Proxy-owned access tokens
+//!
+//! With `issue_proxy_tokens` enabled the token endpoint no longer hands Miro's
+//! tokens to MCP clients. Each successful authorization creates a *grant* that
+//! keeps the Miro token pair server-side, encrypted with the configured
+//! encryption key, and the client receives opaque tokens bound to its client_id
+//! and scope instead:
+//!
+//! - access tokens (`mcp_at_…`) live for an hour and resolve to the grant's Miro
+//!   access token in the bearer middleware, which refreshes it with Miro when
+//!   it's about to expire
+//! - refresh tokens (`mcp_rt_…`) are rotated on every use and never reach Miro
+//!
+//! Only SHA-256 hashes of issued tokens are stored, and revoking a client drops
+//! its grants so every token issued to it stops working.
+
+use super::cookie_manager::{CookieError, CookieManager};
+use super::proxy_provider::{MiroOAuthError, MiroOAuthProvider};
+use super::storage::{token_hash, Storage, StorageError};
+use super::types::{CookieData, UserInfo};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::{info, warn};
+
+/// Lifetime of proxy access tokens
+pub const PROXY_ACCESS_TOKEN_TTL_SECS: i64 = 60 * 60;
+
+/// Lifetime of proxy refresh tokens; each refresh issues a new one
+pub const PROXY_REFRESH_TOKEN_TTL_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// Refresh the Miro access token when it expires within this many seconds
+const MIRO_REFRESH_MARGIN_SECS: i64 = 60;
+
+const ACCESS_TOKEN_PREFIX: &str = "mcp_at_";
+const REFRESH_TOKEN_PREFIX: &str = "mcp_rt_";
+
+const GRANTS_NAMESPACE: &str = "grants";
+const ACCESS_TOKENS_NAMESPACE: &str = "access_tokens";
+const REFRESH_TOKENS_NAMESPACE: &str = "refresh_tokens";
+
+/// Errors from the proxy token store
+#[derive(Error, Debug)]
+pub enum ProxyTokenError {
+    #[error("Token is invalid, expired or revoked")]
+    InvalidToken,
+
+    #[error("Token storage failed: {0}")]
+    Storage(#[from] StorageError),
+
+    #[error("Miro token encryption failed: {0}")]
+    Encryption(#[from] CookieError),
+
+    #[error("Refreshing the Miro token failed: {0}")]
+    MiroRefresh(#[from] MiroOAuthError),
+}
+
+impl From<serde_json::Error> for ProxyTokenError {
+    fn from(e: serde_json::Error) -> Self {
+        ProxyTokenError::Storage(StorageError::Serialization(e))
+    }
+}
+
+/// One authorization of a client, holding the encrypted Miro token pair
+#[derive(Debug, Serialize, Deserialize)]
+struct Grant {
+    grant_id: String,
+    client_id: String,
+    scope: String,
+    /// `CookieData` encrypted with the configured encryption key
+    miro_tokens: String,
+    created_at: DateTime<Utc>,
+    /// Expiry of the newest refresh token; the grant is dropped after it
+    expires_at: DateTime<Utc>,
+}
+
+/// What an issued token hash points to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TokenRecord {
+    grant_id: String,
+    client_id: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// Tokens returned to the client by the token endpoint
+#[derive(Debug, Clone)]
+pub struct IssuedTokens {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_in: i64,
+    pub scope: String,
+}
+
+/// A proxy access token resolved to the grant behind it
+#[derive(Debug, Clone)]
+pub struct ProxyAccess {
+    pub client_id: String,
+    pub scope: String,
+    /// When the proxy access token expires
+    pub expires_at: DateTime<Utc>,
+    /// Current Miro access token for calls to the Miro API
+    pub miro_access_token: String,
+    /// User the grant was issued for, as reported by Miro's token response
+    pub user: UserInfo,
+}
+
+/// Issues, resolves and revokes proxy-owned tokens
+#[derive(Clone)]
+pub struct ProxyTokenStore {
+    storage: Arc<dyn Storage>,
+    cipher: Arc<CookieManager>,
+    /// Serializes grant updates so concurrent requests don't race on a rotated refresh token
+    refresh_lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl ProxyTokenStore {
+    pub fn new(storage: Arc<dyn Storage>, cipher: Arc<CookieManager>) -> Self {
+        Self {
+            storage,
+            cipher,
+            refresh_lock: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+
+    /// Create a grant for a completed authorization and issue its first tokens
+    pub fn issue(
+        &self,
+        client_id: &str,
+        scope: &str,
+        miro_tokens: &CookieData,
+    ) -> Result<IssuedTokens, ProxyTokenError> {
+        let now = Utc::now();
+        let grant = Grant {
+            grant_id: uuid::Uuid::new_v4().to_string(),
+            client_id: client_id.to_string(),
+            scope: scope.to_string(),
+            miro_tokens: self.cipher.encrypt(miro_tokens)?,
+            created_at: now,
+            expires_at: now,
+        };
+        info!(client_id = %client_id, grant_id = %grant.grant_id, "Created proxy token grant");
+        self.issue_tokens(grant)
+    }
+
+    /// Exchange a refresh token for new tokens, rotating the refresh token
+    ///
+    /// Holds the refresh lock so a concurrent Miro refresh can't be overwritten
+    /// by the grant read here.
+    pub async fn refresh(
+        &self,
+        client_id: &str,
+        refresh_token: &str,
+    ) -> Result<IssuedTokens, ProxyTokenError> {
+        let _guard = self.refresh_lock.lock().await;
+        let record: TokenRecord = self
+            .take_json(REFRESH_TOKENS_NAMESPACE, &token_hash(refresh_token))?
+            .filter(|record: &TokenRecord| Utc::now() <= record.expires_at)
+            .ok_or(ProxyTokenError::InvalidToken)?;
+        if record.client_id != client_id {
+            warn!(client_id = %client_id, "Refresh token issued to another client");
+            return Err(ProxyTokenError::InvalidToken);
+        }
+        let grant = self.grant(&record.grant_id)?;
+        self.issue_tokens(grant)
+    }
+
+    /// Resolve an access token to its grant and a current Miro access token
+    ///
+    /// Refreshes the Miro token pair first when Miro's access token is about to expire.
+    pub async fn resolve(
+        &self,
+        access_token: &str,
+        provider: &MiroOAuthProvider,
+    ) -> Result<ProxyAccess, ProxyTokenError> {
+        if !access_token.starts_with(ACCESS_TOKEN_PREFIX) {
+            return Err(ProxyTokenError::InvalidToken);
+        }
+        let record: TokenRecord = self
+            .get_json(ACCESS_TOKENS_NAMESPACE, &token_hash(access_token))?
+            .filter(|record: &TokenRecord| Utc::now() <= record.expires_at)
+            .ok_or(ProxyTokenError::InvalidToken)?;
+
+        let grant = self.grant(&record.grant_id)?;
+        let mut miro_tokens: CookieData = self.cipher.decrypt(&grant.miro_tokens)?;
+        if needs_refresh(&miro_tokens) {
+            miro_tokens = self.refresh_miro_tokens(&grant.grant_id, provider).await?;
+        }
+
+        Ok(ProxyAccess {
+            client_id: grant.client_id,
+            scope: grant.scope,
+            expires_at: record.expires_at,
+            miro_access_token: miro_tokens.access_token,
+            user: miro_tokens.user_info,
+        })
+    }
+
+    /// Revoke the grant behind an access or refresh token issued to `client_id`
+    ///
+    /// Returns the grant's Miro token pair so the caller can revoke it with
+    /// Miro too, or `None` if the token is unknown or belongs to another client.
+    pub fn revoke_token(
+        &self,
+        client_id: &str,
+        token: &str,
+    ) -> Result<Option<CookieData>, ProxyTokenError> {
+        let hash = token_hash(token);
+        let record: Option<TokenRecord> = match self.get_json(ACCESS_TOKENS_NAMESPACE, &hash)? {
+            Some(record) => Some(record),
+            None => self.get_json(REFRESH_TOKENS_NAMESPACE, &hash)?,
+        };
+        let Some(record) = record else {
+            return Ok(None);
+        };
+        if record.client_id != client_id {
+            warn!(client_id = %client_id, "Refusing to revoke a token issued to another client");
+            return Ok(None);
+        }
+
+        let mut revoked = self.remove_grants(|grant| grant.grant_id == record.grant_id)?;
+        info!(client_id = %client_id, grant_id = %record.grant_id, "Revoked proxy token grant");
+        Ok(revoked.pop())
+    }
+
+    /// Revoke every grant of a client
+    ///
+    /// Tokens issued to the client stop resolving immediately and are swept
+    /// from storage along with the grants. Returns the grants' Miro token pairs
+    /// so the caller can revoke them with Miro too.
+    pub fn revoke_client(&self, client_id: &str) -> Result<Vec<CookieData>, ProxyTokenError> {
+        let revoked = self.remove_grants(|grant| grant.client_id == client_id)?;
+        info!(client_id = %client_id, revoked = revoked.len(), "Revoked proxy token grants");
+        Ok(revoked)
+    }
+
+    /// Remove matching grants and every token issued for them
+    fn remove_grants(
+        &self,
+        matches: impl Fn(&Grant) -> bool,
+    ) -> Result<Vec<CookieData>, ProxyTokenError> {
+        let mut removed = Vec::new();
+        for entry in self.storage.entries(GRANTS_NAMESPACE)? {
+            let grant: Grant = serde_json::from_str(&entry.value)?;
+            if matches(&grant) {
+                self.storage.take(GRANTS_NAMESPACE, &entry.key)?;
+                removed.push(grant);
+            }
+        }
+        if removed.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        for namespace in [ACCESS_TOKENS_NAMESPACE, REFRESH_TOKENS_NAMESPACE] {
+            for entry in self.storage.entries(namespace)? {
+                let record: TokenRecord = serde_json::from_str(&entry.value)?;
+                if removed
+                    .iter()
+                    .any(|grant| grant.grant_id == record.grant_id)
+                {
+                    self.storage.take(namespace, &entry.key)?;
+                }
+            }
+        }
+        removed
+            .iter()
+            .map(|grant| Ok(self.cipher.decrypt(&grant.miro_tokens)?))
+            .collect()
+    }
+
+    /// Refresh a grant's Miro tokens, unless another request already did
+    async fn refresh_miro_tokens(
+        &self,
+        grant_id: &str,
+        provider: &MiroOAuthProvider,
+    ) -> Result<CookieData, ProxyTokenError> {
+        let _guard = self.refresh_lock.lock().await;
+        let mut grant = self.grant(grant_id)?;
+        let current: CookieData = self.cipher.decrypt(&grant.miro_tokens)?;
+        if !needs_refresh(&current) {
+            return Ok(current);
+        }
+
+        info!(grant_id = %grant_id, "Refreshing Miro tokens for proxy grant");
+        let refreshed = provider.refresh_token(&current.refresh_token).await?;
+        grant.miro_tokens = self.cipher.encrypt(&refreshed)?;
+        self.put_json(GRANTS_NAMESPACE, grant_id, &grant, Some(grant.expires_at))?;
+        Ok(refreshed)
+    }
+
+    /// Issue a new access and refresh token for a grant and extend the grant's lifetime
+    fn issue_tokens(&self, mut grant: Grant) -> Result<IssuedTokens, ProxyTokenError> {
+        let now = Utc::now();
+        let access_token = generate_token(ACCESS_TOKEN_PREFIX);
+        let refresh_token = generate_token(REFRESH_TOKEN_PREFIX);
+        grant.expires_at = now + Duration::seconds(PROXY_REFRESH_TOKEN_TTL_SECS);
+        let access_record = TokenRecord {
+            grant_id: grant.grant_id.clone(),
+            client_id: grant.client_id.clone(),
+            expires_at: now + Duration::seconds(PROXY_ACCESS_TOKEN_TTL_SECS),
+        };
+        let refresh_record = TokenRecord {
+            expires_at: grant.expires_at,
+            ..access_record.clone()
+        };
+
+        self.put_json(
+            GRANTS_NAMESPACE,
+            &grant.grant_id,
+            &grant,
+            Some(grant.expires_at),
+        )?;
+        self.put_json(
+            ACCESS_TOKENS_NAMESPACE,
+            &token_hash(&access_token),
+            &access_record,
+            Some(access_record.expires_at),
+        )?;
+        self.put_json(
+            REFRESH_TOKENS_NAMESPACE,
+            &token_hash(&refresh_token),
+            &refresh_record,
+            Some(refresh_record.expires_at),
+        )?;
+
+        Ok(IssuedTokens {
+            access_token,
+            refresh_token,
+            expires_in: PROXY_ACCESS_TOKEN_TTL_SECS,
+            scope: grant.scope,
+        })
+    }
+
+    fn grant(&self, grant_id: &str) -> Result<Grant, ProxyTokenError> {
+        self.get_json(GRANTS_NAMESPACE, grant_id)?
+            .filter(|grant: &Grant| Utc::now() <= grant.expires_at)
+            .ok_or(ProxyTokenError::InvalidToken)
+    }
+
+    fn get_json<T: DeserializeOwned>(
+        &self,
+        namespace: &str,
+        key: &str,
+    ) -> Result<Option<T>, ProxyTokenError> {
+        match self.storage.get(namespace, key)? {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn take_json<T: DeserializeOwned>(
+        &self,
+        namespace: &str,
+        key: &str,
+    ) -> Result<Option<T>, ProxyTokenError> {
+        match self.storage.take(namespace, key)? {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn put_json<T: Serialize>(
+        &self,
+        namespace: &str,
+        key: &str,
+        value: &T,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(), ProxyTokenError> {
+        let value = serde_json::to_string(value)?;
+        Ok(self.storage.put(namespace, key, &value, expires_at)?)
+    }
+}
+
+fn needs_refresh(miro_tokens: &CookieData) -> bool {
+    miro_tokens.expires_at - Utc::now() < Duration::seconds(MIRO_REFRESH_MARGIN_SECS)
+}
+
+/// A prefixed, 256-bit random token
+fn generate_token(prefix: &str) -> String {
+    let bytes: [u8; 32] = rand::thread_rng().gen();
+    format!("{}{}", prefix, URL_SAFE_NO_PAD.encode(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oauth::storage::MemoryStorage;
+    use crate::oauth::types::UserInfo;
+
+    fn store() -> (ProxyTokenStore, Arc<dyn Storage>) {
+        let storage: Arc<dyn Storage> = Arc::new(MemoryStorage::new());
+        let store = ProxyTokenStore::new(
+            Arc::clone(&storage),
+            Arc::new(CookieManager::new_for_testing()),
+        );
+        (store, storage)
+    }
+
+    fn miro_tokens() -> CookieData {
+        CookieData {
+            access_token: "miro-access-token".to_string(),
+            refresh_token: "miro-refresh-token".to_string(),
+            expires_at: Utc::now() + Duration::hours(1),
+            user_info: UserInfo {
+                user_id: "user-1".to_string(),
+                email: None,
+                name: None,
+            },
+            scope: Some("boards:read boards:write".to_string()),
+        }
+    }
+
+    fn provider() -> MiroOAuthProvider {
+        MiroOAuthProvider::new(
+            "id".to_string(),
+            "secret".to_string(),
+            "http://localhost/callback".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_issued_token_resolves_to_miro_token() {
+        let (store, storage) = store();
+        let issued = store
+            .issue("client-a", "boards:read", &miro_tokens())
+            .unwrap();
+        assert!(issued.access_token.starts_with(ACCESS_TOKEN_PREFIX));
+        assert_eq!(issued.expires_in, PROXY_ACCESS_TOKEN_TTL_SECS);
+
+        let access = store
+            .resolve(&issued.access_token, &provider())
+            .await
+            .unwrap();
+        assert_eq!(access.client_id, "client-a");
+        assert_eq!(access.scope, "boards:read");
+        assert_eq!(access.miro_access_token, "miro-access-token");
+
+        // Neither Miro's tokens nor ours are stored in the clear
+        for namespace in [
+            GRANTS_NAMESPACE,
+            ACCESS_TOKENS_NAMESPACE,
+            REFRESH_TOKENS_NAMESPACE,
+        ] {
+            for entry in storage.entries(namespace).unwrap() {
+                assert!(!entry.value.contains("miro-access-token"));
+                assert!(!entry.value.contains("miro-refresh-token"));
+                assert_ne!(entry.key, issued.access_token);
+                assert_ne!(entry.key, issued.refresh_token);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unknown_token_is_rejected() {
+        let (store, _) = store();
+        assert!(matches!(
+            store.resolve("miro-access-token", &provider()).await,
+            Err(ProxyTokenError::InvalidToken)
+        ));
+        assert!(matches!(
+            store
+                .resolve(&generate_token(ACCESS_TOKEN_PREFIX), &provider())
+                .await,
+            Err(ProxyTokenError::InvalidToken)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_rotates_refresh_token() {
+        let (store, _) = store();
+        let issued = store
+            .issue("client-a", "boards:read", &miro_tokens())
+            .unwrap();
+
+        // Bound to the client it was issued to
+        assert!(matches!(
+            store.refresh("client-b", &issued.refresh_token).await,
+            Err(ProxyTokenError::InvalidToken)
+        ));
+
+        let issued = store
+            .issue("client-a", "boards:read", &miro_tokens())
+            .unwrap();
+        let refreshed = store
+            .refresh("client-a", &issued.refresh_token)
+            .await
+            .unwrap();
+        assert_ne!(refreshed.refresh_token, issued.refresh_token);
+        assert!(store
+            .resolve(&refreshed.access_token, &provider())
+            .await
+            .is_ok());
+
+        // The old refresh token was used up
+        assert!(matches!(
+            store.refresh("client-a", &issued.refresh_token).await,
+            Err(ProxyTokenError::InvalidToken)
+        ));
+    }
+
+    /// A proxy refresh waits for an in-flight Miro refresh and keeps its new tokens
+    #[tokio::test]
+    async fn test_refresh_keeps_concurrently_rotated_miro_tokens() {
+        let (store, _) = store();
+        let issued = store
+            .issue("client-a", "boards:read", &miro_tokens())
+            .unwrap();
+        let grant_id = store
+            .get_json::<TokenRecord>(ACCESS_TOKENS_NAMESPACE, &token_hash(&issued.access_token))
+            .unwrap()
+            .unwrap()
+            .grant_id;
+
+        // A Miro refresh is in flight while the client refreshes its proxy tokens
+        let guard = store.refresh_lock.lock().await;
+        let refresh = tokio::spawn({
+            let store = store.clone();
+            let refresh_token = issued.refresh_token.clone();
+            async move { store.refresh("client-a", &refresh_token).await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!refresh.is_finished());
+
+        let mut grant = store.grant(&grant_id).unwrap();
+        grant.miro_tokens = store
+            .cipher
+            .encrypt(&CookieData {
+                access_token: "rotated-access-token".to_string(),
+                refresh_token: "rotated-refresh-token".to_string(),
+                ..miro_tokens()
+            })
+            .unwrap();
+        store
+            .put_json(GRANTS_NAMESPACE, &grant_id, &grant, Some(grant.expires_at))
+            .unwrap();
+        drop(guard);
+
+        let refreshed = refresh.await.unwrap().unwrap();
+        let access = store
+            .resolve(&refreshed.access_token, &provider())
+            .await
+            .unwrap();
+        assert_eq!(access.miro_access_token, "rotated-access-token");
+    }
+
+    #[tokio::test]
+    async fn test_revoke_client_invalidates_its_tokens() {
+        let (store, _) = store();
+        let revoked = store
+            .issue("client-a", "boards:read", &miro_tokens())
+            .unwrap();
+        let kept = store
+            .issue("client-b", "boards:read", &miro_tokens())
+            .unwrap();
+
+        let miro_tokens = store.revoke_client("client-a").unwrap();
+        assert_eq!(miro_tokens.len(), 1);
+        assert_eq!(miro_tokens[0].refresh_token, "miro-refresh-token");
+        assert!(store
+            .resolve(&revoked.access_token, &provider())
+            .await
+            .is_err());
+        assert!(store
+            .refresh("client-a", &revoked.refresh_token)
+            .await
+            .is_err());
+        assert!(store.resolve(&kept.access_token, &provider()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_revoke_token_only_for_its_client() {
+        let (store, _) = store();
+        let issued = store
+            .issue("client-a", "boards:read", &miro_tokens())
+            .unwrap();
+
+        assert!(store
+            .revoke_token("client-b", &issued.refresh_token)
+            .unwrap()
+            .is_none());
+        assert!(store
+            .resolve(&issued.access_token, &provider())
+            .await
+            .is_ok());
+
+        // Revoking the refresh token ends the whole grant
+        let revoked = store
+            .revoke_token("client-a", &issued.refresh_token)
+            .unwrap();
+        assert_eq!(revoked.unwrap().access_token, "miro-access-token");
+        assert!(store
+            .resolve(&issued.access_token, &provider())
+            .await
+            .is_err());
+        assert!(store
+            .revoke_token("client-a", &issued.access_token)
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,471 @@
+//! Storage backends for OAuth proxy state
+//!
+//! DCR registrations and pending authorization codes must survive restarts and
+//! be shared between replicas, otherwise Claude.ai fails with "invalid client"
+//! after every deploy. [`ClientRegistry`](super::dcr::ClientRegistry) and
+//! [`CodeStorage`](super::code_storage::CodeStorage) keep their data in a
+//! [`Storage`] backend chosen by [`StorageBackend`] in the configuration:
+//!
+//! - [`MemoryStorage`]: in-process, lost on restart (default, tests)
+//! - [`EncryptedFileStorage`]: one file encrypted with AES-256-GCM via [`CookieManager`]
+//! - [`SqliteStorage`]: a SQLite database, for volumes shared by several replicas
+//!
+//! Backends are plain key-value stores split into namespaces. Values are JSON
+//! documents owned by the caller; an optional expiry only matters to
+//! [`Storage::remove_expired`], so callers still check expiry themselves.
+//! All backends are local and fast, so the trait is synchronous.
+
+use super::cookie_manager::{CookieError, CookieManager};
+use crate::config::StorageBackend;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use tracing::info;
+
+/// Errors from storage backends
+#[derive(Error, Debug)]
+pub enum StorageError {
+    #[error("Storage I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("Storage encryption failed: {0}")]
+    Encryption(#[from] CookieError),
+
+    #[error("Stored value is not valid JSON: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Storage lock poisoned")]
+    Poisoned,
+}
+
+/// One stored value with its key and optional expiry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredEntry {
+    pub key: String,
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl StoredEntry {
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| now > expires_at)
+    }
+}
+
+/// Namespaced key-value storage for OAuth proxy state
+pub trait Storage: Send + Sync {
+    /// Value stored under `key`, even if it has expired
+    fn get(&self, namespace: &str, key: &str) -> Result<Option<String>, StorageError>;
+
+    /// Insert or replace the value under `key`
+    fn put(
+        &self,
+        namespace: &str,
+        key: &str,
+        value: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(), StorageError>;
+
+    /// Remove and return the value under `key`, even if it has expired
+    fn take(&self, namespace: &str, key: &str) -> Result<Option<String>, StorageError>;
+
+    /// All entries in a namespace, including expired ones
+    fn entries(&self, namespace: &str) -> Result<Vec<StoredEntry>, StorageError>;
+
+    /// Remove entries in every namespace that expired before `now`; returns how many
+    fn remove_expired(&self, now: DateTime<Utc>) -> Result<usize, StorageError>;
+}
+
+/// Open the backend selected in the configuration
+///
+/// `encryption_key` encrypts the file backend; it's the same key that protects
+/// the OAuth state cookie.
+pub fn open_storage(
+    backend: &StorageBackend,
+    encryption_key: &[u8; 32],
+) -> Result<Arc<dyn Storage>, StorageError> {
+    let storage: Arc<dyn Storage> = match backend {
+        StorageBackend::Memory => Arc::new(MemoryStorage::new()),
+        StorageBackend::EncryptedFile(path) => Arc::new(EncryptedFileStorage::open(
+            path,
+            CookieManager::new(encryption_key),
+        )?),
+        StorageBackend::Sqlite(path) => Arc::new(SqliteStorage::open(path)?),
+    };
+    info!(backend = ?backend, "OAuth storage opened");
+    Ok(storage)
+}
+
+/// SHA-256 of a bearer secret, for storing it without keeping the secret itself
+pub fn token_hash(token: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Entries by namespace, then key
+type Namespaces = BTreeMap<String, BTreeMap<String, StoredEntry>>;
+
+fn get_entry(namespaces: &Namespaces, namespace: &str, key: &str) -> Option<String> {
+    namespaces
+        .get(namespace)
+        .and_then(|entries| entries.get(key))
+        .map(|entry| entry.value.clone())
+}
+
+fn put_entry(
+    namespaces: &mut Namespaces,
+    namespace: &str,
+    key: &str,
+    value: &str,
+    expires_at: Option<DateTime<Utc>>,
+) {
+    namespaces.entry(namespace.to_string()).or_default().insert(
+        key.to_string(),
+        StoredEntry {
+            key: key.to_string(),
+            value: value.to_string(),
+            expires_at,
+        },
+    );
+}
+
+fn take_entry(namespaces: &mut Namespaces, namespace: &str, key: &str) -> Option<String> {
+    namespaces
+        .get_mut(namespace)
+        .and_then(|entries| entries.remove(key))
+        .map(|entry| entry.value)
+}
+
+fn remove_expired_entries(namespaces: &mut Namespaces, now: DateTime<Utc>) -> usize {
+    let mut removed = 0;
+    for entries in namespaces.values_mut() {
+        let before = entries.len();
+        entries.retain(|_, entry| !entry.is_expired(now));
+        removed += before - entries.len();
+    }
+    removed
+}
+
+/// In-memory storage; everything is lost when the process exits
+#[derive(Default)]
+pub struct MemoryStorage {
+    namespaces: Mutex<Namespaces>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Namespaces>, StorageError> {
+        self.namespaces.lock().map_err(|_| StorageError::Poisoned)
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn get(&self, namespace: &str, key: &str) -> Result<Option<String>, StorageError> {
+        Ok(get_entry(&*self.lock()?, namespace, key))
+    }
+
+    fn put(
+        &self,
+        namespace: &str,
+        key: &str,
+        value: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(), StorageError> {
+        put_entry(&mut *self.lock()?, namespace, key, value, expires_at);
+        Ok(())
+    }
+
+    fn take(&self, namespace: &str, key: &str) -> Result<Option<String>, StorageError> {
+        Ok(take_entry(&mut *self.lock()?, namespace, key))
+    }
+
+    fn entries(&self, namespace: &str) -> Result<Vec<StoredEntry>, StorageError> {
+        Ok(self
+            .lock()?
+            .get(namespace)
+            .map(|entries| entries.values().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    fn remove_expired(&self, now: DateTime<Utc>) -> Result<usize, StorageError> {
+        Ok(remove_expired_entries(&mut *self.lock()?, now))
+    }
+}
+
+/// Storage in a single file encrypted with AES-256-GCM
+///
+/// The whole store is kept in memory and the file is rewritten after every
+/// change (write to a temporary file, then rename), so a crash never leaves a
+/// half-written store. Suited to a single instance with a persistent volume.
+pub struct EncryptedFileStorage {
+    path: PathBuf,
+    cipher: CookieManager,
+    namespaces: Mutex<Namespaces>,
+}
+
+impl EncryptedFileStorage {
+    /// Open the store at `path`, creating it on first write
+    pub fn open(path: &Path, cipher: CookieManager) -> Result<Self, StorageError> {
+        let namespaces = match std::fs::read_to_string(path) {
+            Ok(contents) => cipher.decrypt(contents.trim())?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Namespaces::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            cipher,
+            namespaces: Mutex::new(namespaces),
+        })
+    }
+
+    /// Apply `change` and write the store back to disk
+    fn update<T>(&self, change: impl FnOnce(&mut Namespaces) -> T) -> Result<T, StorageError> {
+        let mut namespaces = self.namespaces.lock().map_err(|_| StorageError::Poisoned)?;
+        let result = change(&mut namespaces);
+        self.persist(&namespaces)?;
+        Ok(result)
+    }
+
+    fn persist(&self, namespaces: &Namespaces) -> Result<(), StorageError> {
+        let encrypted = self.cipher.encrypt(namespaces)?;
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp_path = self.path.with_extension("tmp");
+        std::fs::write(&tmp_path, encrypted)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&tmp_path, std::fs::Permissions::from_mode(0o600))?;
+        }
+        std::fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+impl Storage for EncryptedFileStorage {
+    fn get(&self, namespace: &str, key: &str) -> Result<Option<String>, StorageError> {
+        let namespaces = self.namespaces.lock().map_err(|_| StorageError::Poisoned)?;
+        Ok(get_entry(&namespaces, namespace, key))
+    }
+
+    fn put(
+        &self,
+        namespace: &str,
+        key: &str,
+        value: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(), StorageError> {
+        self.update(|namespaces| put_entry(namespaces, namespace, key, value, expires_at))
+    }
+
+    fn take(&self, namespace: &str, key: &str) -> Result<Option<String>, StorageError> {
+        self.update(|namespaces| take_entry(namespaces, namespace, key))
+    }
+
+    fn entries(&self, namespace: &str) -> Result<Vec<StoredEntry>, StorageError> {
+        let namespaces = self.namespaces.lock().map_err(|_| StorageError::Poisoned)?;
+        Ok(namespaces
+            .get(namespace)
+            .map(|entries| entries.values().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    fn remove_expired(&self, now: DateTime<Utc>) -> Result<usize, StorageError> {
+        let mut namespaces = self.namespaces.lock().map_err(|_| StorageError::Poisoned)?;
+        let removed = remove_expired_entries(&mut namespaces, now);
+        if removed > 0 {
+            self.persist(&namespaces)?;
+        }
+        Ok(removed)
+    }
+}
+
+/// Storage in a SQLite database
+///
+/// Values are stored as-is, so protect the database file like any other
+/// credential store. Several processes can share one database file.
+pub struct SqliteStorage {
+    connection: Mutex<rusqlite::Connection>,
+}
+
+impl SqliteStorage {
+    /// Open (or create) the database at `path`
+    pub fn open(path: &Path) -> Result<Self, StorageError> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        Self::with_connection(rusqlite::Connection::open(path)?)
+    }
+
+    /// A private in-memory database (for tests)
+    pub fn open_in_memory() -> Result<Self, StorageError> {
+        Self::with_connection(rusqlite::Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: rusqlite::Connection) -> Result<Self, StorageError> {
+        connection.busy_timeout(std::time::Duration::from_secs(5))?;
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS oauth_entries (
+                namespace TEXT NOT NULL,
+                key TEXT NOT NULL,
+                value TEXT NOT NULL,
+                expires_at INTEGER,
+                PRIMARY KEY (namespace, key)
+            );
+            CREATE INDEX IF NOT EXISTS oauth_entries_expires_at
+                ON oauth_entries (expires_at);",
+        )?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn connection(&self) -> Result<std::sync::MutexGuard<'_, rusqlite::Connection>, StorageError> {
+        self.connection.lock().map_err(|_| StorageError::Poisoned)
+    }
+}
+
+impl Storage for SqliteStorage {
+    fn get(&self, namespace: &str, key: &str) -> Result<Option<String>, StorageError> {
+        use rusqlite::OptionalExtension;
+        Ok(self
+            .connection()?
+            .query_row(
+                "SELECT value FROM oauth_entries WHERE namespace = ?1 AND key = ?2",
+                (namespace, key),
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    fn put(
+        &self,
+        namespace: &str,
+        key: &str,
+        value: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(), StorageError> {
+        self.connection()?.execute(
+            "INSERT OR REPLACE INTO oauth_entries (namespace, key, value, expires_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            (
+                namespace,
+                key,
+                value,
+                expires_at.map(|at| at.timestamp_millis()),
+            ),
+        )?;
+        Ok(())
+    }
+
+    fn take(&self, namespace: &str, key: &str) -> Result<Option<String>, StorageError> {
+        use rusqlite::OptionalExtension;
+        // DELETE ... RETURNING keeps take atomic across processes sharing the file
+        Ok(self
+            .connection()?
+            .query_row(
+                "DELETE FROM oauth_entries WHERE namespace = ?1 AND key = ?2 RETURNING value",
+                (namespace, key),
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    fn entries(&self, namespace: &str) -> Result<Vec<StoredEntry>, StorageError> {
+        let connection = self.connection()?;
+        let mut statement = connection.prepare(
+            "SELECT key, value, expires_at FROM oauth_entries WHERE namespace = ?1 ORDER BY key",
+        )?;
+        let entries = statement
+            .query_map([namespace], |row| {
+                let expires_at: Option<i64> = row.get(2)?;
+                Ok(StoredEntry {
+                    key: row.get(0)?,
+                    value: row.get(1)?,
+                    expires_at: expires_at
+                        .and_then(|millis| Utc.timestamp_millis_opt(millis).single()),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }
+
+    fn remove_expired(&self, now: DateTime<Utc>) -> Result<usize, StorageError> {
+        Ok(self.connection()?.execute(
+            "DELETE FROM oauth_entries WHERE expires_at IS NOT NULL AND expires_at < ?1",
+            [now.timestamp_millis()],
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn exercise(storage: &dyn Storage) {
+        let now = Utc::now();
+        storage.put("clients", "a", r#"{"id":"a"}"#, None).unwrap();
+        storage
+            .put("codes", "old", "1", Some(now - Duration::seconds(60)))
+            .unwrap();
+        storage
+            .put("codes", "new", "2", Some(now + Duration::seconds(60)))
+            .unwrap();
+
+        assert_eq!(
+            storage.get("clients", "a").unwrap().as_deref(),
+            Some(r#"{"id":"a"}"#)
+        );
+        assert_eq!(storage.get("codes", "a").unwrap(), None);
+        assert_eq!(storage.entries("codes").unwrap().len(), 2);
+
+        assert_eq!(storage.remove_expired(now).unwrap(), 1);
+        assert_eq!(storage.get("codes", "old").unwrap(), None);
+
+        assert_eq!(storage.take("codes", "new").unwrap().as_deref(), Some("2"));
+        assert_eq!(storage.take("codes", "new").unwrap(), None);
+        assert_eq!(storage.entries("clients").unwrap()[0].key, "a");
+    }
+
+    #[test]
+    fn test_memory_storage() {
+        exercise(&MemoryStorage::new());
+    }
+
+    #[test]
+    fn test_sqlite_storage() {
+        exercise(&SqliteStorage::open_in_memory().unwrap());
+    }
+
+    #[test]
+    fn test_encrypted_file_storage_persists_across_reopen() {
+        let path = std::env::temp_dir().join(format!("oauth-store-{}.enc", uuid::Uuid::new_v4()));
+        let storage = EncryptedFileStorage::open(&path, CookieManager::new_for_testing()).unwrap();
+        exercise(&storage);
+        drop(storage);
+
+        // Contents are encrypted at rest
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("clients"));
+
+        let reopened = EncryptedFileStorage::open(&path, CookieManager::new_for_testing()).unwrap();
+        assert!(reopened.get("clients", "a").unwrap().is_some());
+
+        // A different key can't read the store
+        assert!(EncryptedFileStorage::open(&path, CookieManager::new(&[7u8; 32])).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -15,6 +15,10 @@ pub struct CookieData {
 
     /// User information from Miro
     pub user_info: UserInfo,
+
+    /// Scopes Miro granted, space-separated, when it reported them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
 }
 
 /// User information from Miro
@@ -43,6 +47,35 @@ pub struct OAuthState {
 
     /// Redirect URI after OAuth completion
     pub redirect_uri: String,
+
+    /// MCP client that started the flow
+    #[serde(default)]
+    pub client_id: String,
+
+    /// The MCP client's own state parameter, returned with the code
+    #[serde(default)]
+    pub client_state: Option<String>,
+
+    /// PKCE challenge sent by the MCP client (not the one we send to Miro)
+    #[serde(default)]
+    pub client_pkce: Option<ClientPkce>,
+
+    /// Miro scopes requested on the client's behalf
+    #[serde(default = "crate::auth::scopes::default_scopes")]
+    pub scopes: Vec<String>,
+    /// CSRF token of the consent form; set until the user approves
+    #[serde(default)]
+    pub csrf_token: Option<String>,
+}
+
+/// PKCE challenge an MCP client sent to /authorize, checked at /token (RFC 7636)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClientPkce {
+    /// BASE64URL(SHA256(code_verifier))
+    pub code_challenge: String,
+
+    /// Challenge method; only "S256" is accepted
+    pub code_challenge_method: String,
 }
 
 /// Pending authorization code waiting for token exchange
@@ -55,6 +88,22 @@ pub struct PendingCodeExchange {
     /// PKCE code verifier (needed for token exchange)
     pub code_verifier: String,
 
+    /// MCP client the code was issued to
+    #[serde(default)]
+    pub client_id: String,
+
+    /// Redirect URI from the authorization request; /token must send the same one
+    #[serde(default)]
+    pub redirect_uri: String,
+
+    /// MCP client's PKCE challenge, verified against its code_verifier at /token
+    #[serde(default)]
+    pub client_pkce: Option<ClientPkce>,
+
+    /// Miro scopes requested on the client's behalf
+    #[serde(default = "crate::auth::scopes::default_scopes")]
+    pub scopes: Vec<String>,
+
     /// Expiration timestamp (short-lived, ~5 minutes)
     pub expires_at: DateTime<Utc>,
 }
@@ -95,13 +144,15 @@ pub struct MiroUser {
 /// POST /oauth/token with these parameters
 #[derive(Debug, Deserialize)]
 pub struct TokenRequest {
-    /// Must be "authorization_code"
+    /// "authorization_code" or "refresh_token"
     pub grant_type: String,
 
-    /// Authorization code from callback
+    /// Authorization code from callback (authorization_code grant)
+    #[serde(default)]
     pub code: String,
 
-    /// Must match the redirect_uri from authorize request
+    /// Must match the redirect_uri from authorize request (authorization_code grant)
+    #[serde(default)]
     pub redirect_uri: String,
 
     /// Client ID (for validation)
@@ -113,6 +164,35 @@ pub struct TokenRequest {
 
     /// PKCE code verifier (if PKCE was used)
     pub code_verifier: Option<String>,
+
+    /// Refresh token from a previous token response (refresh_token grant)
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+
+    /// Requested scope (refresh_token grant); Miro keeps the original grant's scopes
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+/// Request to the revocation (RFC 7009) or introspection (RFC 7662) endpoint
+/// POST /oauth/revoke or /oauth/introspect with these parameters
+#[derive(Debug, Deserialize)]
+pub struct TokenOperationRequest {
+    /// The access or refresh token to revoke or introspect
+    #[serde(default)]
+    pub token: String,
+
+    /// "access_token" or "refresh_token"; only a hint, every kind is looked up
+    #[serde(default)]
+    pub token_type_hint: Option<String>,
+
+    /// Client ID (client_secret_post, or public clients)
+    #[serde(default)]
+    pub client_id: Option<String>,
+
+    /// Client secret (for client_secret_post authentication method)
+    #[serde(default)]
+    pub client_secret: Option<String>,
 }
 
 impl From<MiroUser> for UserInfo {
@@ -157,8 +237,9 @@ pub struct ClientRegistrationResponse {
     /// Unique client identifier
     pub client_id: String,
 
-    /// Client secret
-    pub client_secret: String,
+    /// Client secret, only returned when it's issued
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
 
     /// Registration access token (for future updates)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -191,13 +272,70 @@ pub struct ClientRegistrationResponse {
     pub client_secret_expires_at: Option<i64>,
 }
 
+/// Client update request (RFC 7592 §2.2)
+/// PUT /register/{client_id} with the full client metadata
+#[derive(Debug, Deserialize)]
+pub struct ClientUpdateRequest {
+    /// Must match the client being updated
+    pub client_id: String,
+
+    /// If present, must match the current secret; secrets can't be chosen by clients
+    #[serde(default)]
+    pub client_secret: Option<String>,
+
+    /// The replacement metadata
+    #[serde(flatten)]
+    pub metadata: ClientRegistrationRequest,
+}
+
 /// Registered OAuth client
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegisteredClient {
     pub client_id: String,
-    pub client_secret: String,
+    /// SHA-256 of the client secret; the secret itself is never stored.
+    /// Empty for public clients, which are issued no secret
+    pub client_secret_hash: String,
     pub client_name: String,
     pub redirect_uris: Vec<String>,
     pub grant_types: Vec<String>,
+    /// "none" for public clients, which must use PKCE
+    #[serde(default = "default_token_endpoint_auth_method")]
+    pub token_endpoint_auth_method: String,
+    #[serde(default = "default_response_types")]
+    pub response_types: Vec<String>,
     pub created_at: DateTime<Utc>,
+    /// When the client secret stops being accepted; `None` never expires
+    #[serde(default)]
+    pub client_secret_expires_at: Option<DateTime<Utc>>,
+    /// SHA-256 of the registration access token for RFC 7592 management
+    #[serde(default)]
+    pub registration_access_token_hash: Option<String>,
+}
+
+impl RegisteredClient {
+    /// Public clients can't keep a secret and authenticate with PKCE alone
+    pub fn is_public(&self) -> bool {
+        self.token_endpoint_auth_method == "none"
+    }
+
+    /// Whether the client secret has passed its expiry
+    pub fn secret_expired(&self) -> bool {
+        self.client_secret_expires_at
+            .is_some_and(|expires_at| Utc::now() > expires_at)
+    }
+
+    /// Whether `redirect_uri` is one of this client's registered redirect URIs
+    pub fn allows_redirect_uri(&self, redirect_uri: &str) -> bool {
+        self.redirect_uris
+            .iter()
+            .any(|registered| super::dcr::redirect_uri_matches(registered, redirect_uri))
+    }
+}
+
+fn default_token_endpoint_auth_method() -> String {
+    "client_secret_basic".to_string()
+}
+
+fn default_response_types() -> Vec<String> {
+    vec!["code".to_string()]
 }
@@ -19,6 +19,15 @@ fn get_test_config() -> Config {
         encryption_key: [0u8; 32],
         port: 3010,
         base_url: Some("http://localhost:3010".to_string()),
+        resource_poll_interval_secs: 30,
+        max_resource_subscriptions: 10,
+        confirm_import_threshold: 100,
+        oauth_storage: Default::default(),
+        issue_proxy_tokens: false,
+        registration_policy: Default::default(),
+        consent_policy: Default::default(),
+        log_pii: false,
+        snapshot_dir: "snapshots".into(),
     }
 }
 
@@ -36,7 +45,13 @@ fn create_test_app() -> Router {
         let cookie_manager = Arc::new(CookieManager::new(&config.encryption_key));
         let code_storage = CodeStorage::new();
 
-        create_app_adr002(token_validator, config, oauth_provider, cookie_manager, code_storage)
+        create_app_adr002(
+            token_validator,
+            config,
+            oauth_provider,
+            cookie_manager,
+            code_storage,
+        )
     }
 
     #[cfg(not(feature = "oauth-proxy"))]
@@ -83,11 +98,36 @@ async fn test_oauth_metadata_no_auth_required() {
 async fn test_oauth_authorize_no_auth_required() {
     let app = create_test_app();
 
+    // Register a client (DCR is public too)
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/register")
+                .header("content-type", "application/json")
+                .body(axum::body::Body::from(
+                    r#"{"client_name":"Claude","redirect_uris":["https://claude.ai/api/mcp/auth_callback"]}"#,
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let registration: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let client_id = registration["client_id"].as_str().unwrap();
+
     // OAuth authorization request with required query parameters
     let response = app
         .oneshot(
             Request::builder()
-                .uri("/oauth/authorize?response_type=code&client_id=test_client&redirect_uri=https://claude.ai/api/mcp/auth_callback")
+                .uri(format!(
+                    "/oauth/authorize?response_type=code&client_id={}&redirect_uri=https://claude.ai/api/mcp/auth_callback",
+                    client_id
+                ))
                 .body(axum::body::Body::empty())
                 .unwrap(),
         )
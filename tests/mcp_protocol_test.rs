@@ -2,11 +2,26 @@
 //!
 //! Tests the JSON-RPC 2.0 MCP protocol implementation over HTTP POST
 
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use axum::Router;
 use miro_mcp_server::{
+    http_server::create_app_adr002,
     mcp::{JsonRpcError, JsonRpcRequest, JsonRpcResponse},
-    TokenValidator,
+    Config, TokenValidator,
 };
 use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[cfg(feature = "oauth-proxy")]
+use miro_mcp_server::oauth::code_storage::CodeStorage;
+#[cfg(feature = "oauth-proxy")]
+use miro_mcp_server::oauth::cookie_manager::CookieManager;
+#[cfg(feature = "oauth-proxy")]
+use miro_mcp_server::oauth::proxy_provider::MiroOAuthProvider;
 
 #[test]
 fn test_jsonrpc_request_serialization() {
@@ -174,3 +189,292 @@ fn test_multiple_tools_in_list() {
         assert_eq!(tools.len(), 2);
     }
 }
+
+fn get_test_config() -> Config {
+    Config {
+        client_id: "test_client_id".to_string(),
+        client_secret: "test_client_secret".to_string(),
+        redirect_uri: "http://localhost:3010/oauth/callback".to_string(),
+        encryption_key: [0u8; 32],
+        port: 3010,
+        base_url: Some("http://localhost:3010".to_string()),
+        resource_poll_interval_secs: 30,
+        max_resource_subscriptions: 10,
+        confirm_import_threshold: 100,
+        oauth_storage: Default::default(),
+        issue_proxy_tokens: false,
+        registration_policy: Default::default(),
+        consent_policy: Default::default(),
+        log_pii: false,
+        snapshot_dir: "snapshots".into(),
+    }
+}
+
+/// App whose token validator accepts any Bearer token via a mocked Miro endpoint
+async fn create_test_app(mock_server: &MockServer) -> Router {
+    Mock::given(method("GET"))
+        .and(path("/v1/oauth-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "user_id": "user123",
+            "team_id": "team456",
+            "scopes": "boards:read boards:write"
+        })))
+        .mount(mock_server)
+        .await;
+
+    let config = Arc::new(get_test_config());
+    let token_validator = Arc::new(TokenValidator::new_with_endpoint(format!(
+        "{}/v1/oauth-token",
+        mock_server.uri()
+    )));
+
+    #[cfg(feature = "oauth-proxy")]
+    {
+        let oauth_provider = Arc::new(MiroOAuthProvider::new(
+            config.client_id.clone(),
+            config.client_secret.clone(),
+            config.redirect_uri.clone(),
+        ));
+        let cookie_manager = Arc::new(CookieManager::new(&config.encryption_key));
+        let code_storage = CodeStorage::new();
+
+        create_app_adr002(
+            token_validator,
+            config,
+            oauth_provider,
+            cookie_manager,
+            code_storage,
+        )
+    }
+
+    #[cfg(not(feature = "oauth-proxy"))]
+    {
+        create_app_adr002(token_validator, config)
+    }
+}
+
+/// POST a raw body to /mcp, returning the status and the parsed body (if any)
+async fn post_mcp(body: &str) -> (StatusCode, Option<Value>) {
+    let mock_server = MockServer::start().await;
+    let app = create_test_app(&mock_server).await;
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/mcp")
+                .header(header::AUTHORIZATION, "Bearer valid_token_123")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = (!bytes.is_empty()).then(|| serde_json::from_slice(&bytes).unwrap());
+    (status, body)
+}
+
+#[tokio::test]
+async fn test_http_parse_error_returns_jsonrpc_error() {
+    let (status, body) = post_mcp("{not json").await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let body = body.unwrap();
+    assert_eq!(body["jsonrpc"], "2.0");
+    assert_eq!(body["error"]["code"], -32700);
+}
+
+#[tokio::test]
+async fn test_http_invalid_request_returns_32600() {
+    let (status, body) = post_mcp(r#"{"jsonrpc":"2.0","id":1}"#).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let body = body.unwrap();
+    assert_eq!(body["error"]["code"], -32600);
+    assert_eq!(body["id"], 1);
+}
+
+#[tokio::test]
+async fn test_http_ping() {
+    let (status, body) = post_mcp(r#"{"jsonrpc":"2.0","id":7,"method":"ping"}"#).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let body = body.unwrap();
+    assert_eq!(body["id"], 7);
+    assert_eq!(body["result"], json!({}));
+}
+
+#[tokio::test]
+async fn test_http_notification_returns_202_without_body() {
+    let (status, body) =
+        post_mcp(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#).await;
+
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert!(body.is_none());
+}
+
+#[tokio::test]
+async fn test_http_batch_returns_array_of_responses() {
+    let (status, body) = post_mcp(
+        r#"[
+            {"jsonrpc":"2.0","id":1,"method":"ping"},
+            {"jsonrpc":"2.0","method":"notifications/initialized"},
+            {"jsonrpc":"2.0","id":2,"method":"no/such/method"},
+            {"jsonrpc":"2.0","id":3},
+            {"jsonrpc":"2.0","id":4,"method":"tools/list"}
+        ]"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let body = body.unwrap();
+    let responses = body.as_array().unwrap();
+
+    // The notification gets no entry
+    assert_eq!(responses.len(), 4);
+    assert_eq!(responses[0]["id"], 1);
+    assert_eq!(responses[0]["result"], json!({}));
+    assert_eq!(responses[1]["error"]["code"], -32601);
+    assert_eq!(responses[2]["id"], 3);
+    assert_eq!(responses[2]["error"]["code"], -32600);
+    assert!(responses[3]["result"]["tools"].is_array());
+}
+
+#[tokio::test]
+async fn test_http_batch_of_notifications_returns_202() {
+    let (status, body) = post_mcp(
+        r#"[{"jsonrpc":"2.0","method":"notifications/initialized"},
+            {"jsonrpc":"2.0","method":"notifications/cancelled","params":{"requestId":1}}]"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert!(body.is_none());
+}
+
+#[tokio::test]
+async fn test_http_empty_batch_is_invalid() {
+    let (status, body) = post_mcp("[]").await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body.unwrap()["error"]["code"], -32600);
+}
+
+/// Protocol version 2025-06-18 removed JSON-RPC batching
+#[tokio::test]
+async fn test_http_batch_rejected_for_2025_06_18() {
+    let mock_server = MockServer::start().await;
+    let app = create_test_app(&mock_server).await;
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/mcp")
+                .header(header::AUTHORIZATION, "Bearer valid_token_123")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header("MCP-Protocol-Version", "2025-06-18")
+                .body(Body::from(r#"[{"jsonrpc":"2.0","id":1,"method":"ping"}]"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["error"]["code"], -32600);
+    assert_eq!(
+        body["error"]["message"],
+        "batching is not supported in this protocol version"
+    );
+}
+
+#[tokio::test]
+async fn test_http_initialize_rejected_inside_batch() {
+    let (status, body) = post_mcp(r#"[{"jsonrpc":"2.0","id":1,"method":"initialize"}]"#).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.unwrap()[0]["error"]["code"], -32600);
+}
+
+#[tokio::test]
+async fn test_http_initialize_unsupported_version_is_rejected() {
+    let (status, body) = post_mcp(
+        r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2023-01-01"}}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let body = body.unwrap();
+    assert_eq!(body["error"]["code"], -32602);
+    assert_eq!(body["error"]["data"]["requested"], "2023-01-01");
+}
+
+#[tokio::test]
+async fn test_http_unsupported_protocol_version_header() {
+    let mock_server = MockServer::start().await;
+    let app = create_test_app(&mock_server).await;
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/mcp")
+                .header(header::AUTHORIZATION, "Bearer valid_token_123")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header("MCP-Protocol-Version", "1999-01-01")
+                .body(Body::from(r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+/// /oauth/userinfo needs a Bearer token and returns the token's claims
+#[tokio::test]
+async fn test_userinfo_endpoint() {
+    let mock_server = MockServer::start().await;
+    let app = create_test_app(&mock_server).await;
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/oauth/userinfo")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/oauth/userinfo")
+                .header(header::AUTHORIZATION, "Bearer valid_token_123")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let claims: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(claims["sub"], "user123");
+    assert_eq!(claims["team_id"], "team456");
+    assert_eq!(claims["scope"], "boards:read boards:write");
+    assert!(claims.get("email").is_none());
+}
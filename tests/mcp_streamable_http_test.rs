@@ -0,0 +1,322 @@
+//! Integration tests for the streamable HTTP transport on /mcp
+//!
+//! Covers session issuance on initialize, SSE responses to POST, the GET event
+//! stream, request cancellation, and session termination via DELETE.
+
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use axum::Router;
+use miro_mcp_server::{http_server::create_app_adr002, Config, TokenValidator};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[cfg(feature = "oauth-proxy")]
+use miro_mcp_server::oauth::code_storage::CodeStorage;
+#[cfg(feature = "oauth-proxy")]
+use miro_mcp_server::oauth::cookie_manager::CookieManager;
+#[cfg(feature = "oauth-proxy")]
+use miro_mcp_server::oauth::proxy_provider::MiroOAuthProvider;
+
+const TOKEN: &str = "valid_token_123";
+
+fn get_test_config() -> Config {
+    Config {
+        client_id: "test_client_id".to_string(),
+        client_secret: "test_client_secret".to_string(),
+        redirect_uri: "http://localhost:3010/oauth/callback".to_string(),
+        encryption_key: [0u8; 32],
+        port: 3010,
+        base_url: Some("http://localhost:3010".to_string()),
+        resource_poll_interval_secs: 30,
+        max_resource_subscriptions: 10,
+        confirm_import_threshold: 100,
+        oauth_storage: Default::default(),
+        issue_proxy_tokens: false,
+        registration_policy: Default::default(),
+        consent_policy: Default::default(),
+        log_pii: false,
+        snapshot_dir: "snapshots".into(),
+    }
+}
+
+/// App whose token validator accepts `TOKEN` via a mocked Miro endpoint
+async fn create_test_app(mock_server: &MockServer) -> Router {
+    Mock::given(method("GET"))
+        .and(path("/v1/oauth-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "user_id": "user123",
+            "team_id": "team456",
+            "scopes": "boards:read boards:write"
+        })))
+        .mount(mock_server)
+        .await;
+
+    let config = Arc::new(get_test_config());
+    let token_validator = Arc::new(TokenValidator::new_with_endpoint(format!(
+        "{}/v1/oauth-token",
+        mock_server.uri()
+    )));
+
+    #[cfg(feature = "oauth-proxy")]
+    {
+        let oauth_provider = Arc::new(MiroOAuthProvider::new(
+            config.client_id.clone(),
+            config.client_secret.clone(),
+            config.redirect_uri.clone(),
+        ));
+        let cookie_manager = Arc::new(CookieManager::new(&config.encryption_key));
+        let code_storage = CodeStorage::new();
+
+        create_app_adr002(
+            token_validator,
+            config,
+            oauth_provider,
+            cookie_manager,
+            code_storage,
+        )
+    }
+
+    #[cfg(not(feature = "oauth-proxy"))]
+    {
+        create_app_adr002(token_validator, config)
+    }
+}
+
+fn mcp_request(
+    http_method: &str,
+    session: Option<&str>,
+    accept: &str,
+    body: Value,
+) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method(http_method)
+        .uri("/mcp")
+        .header(header::AUTHORIZATION, format!("Bearer {}", TOKEN))
+        .header(header::ACCEPT, accept)
+        .header(header::CONTENT_TYPE, "application/json");
+    if let Some(session) = session {
+        builder = builder.header("Mcp-Session-Id", session);
+    }
+    let body = if body.is_null() {
+        Body::empty()
+    } else {
+        Body::from(body.to_string())
+    };
+    builder.body(body).unwrap()
+}
+
+async fn initialize(app: &Router) -> String {
+    let response = app
+        .clone()
+        .oneshot(mcp_request(
+            "POST",
+            None,
+            "application/json, text/event-stream",
+            json!({"jsonrpc": "2.0", "id": 1, "method": "initialize"}),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    response
+        .headers()
+        .get("mcp-session-id")
+        .expect("initialize should issue a session ID")
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn test_initialize_issues_session_id() {
+    let mock_server = MockServer::start().await;
+    let app = create_test_app(&mock_server).await;
+
+    let first = initialize(&app).await;
+    let second = initialize(&app).await;
+
+    assert!(!first.is_empty());
+    assert_ne!(first, second);
+}
+
+#[tokio::test]
+async fn test_unknown_session_returns_404() {
+    let mock_server = MockServer::start().await;
+    let app = create_test_app(&mock_server).await;
+
+    let response = app
+        .oneshot(mcp_request(
+            "POST",
+            Some("not-a-session"),
+            "application/json",
+            json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_tools_call_streams_when_client_accepts_sse() {
+    let mock_server = MockServer::start().await;
+    let app = create_test_app(&mock_server).await;
+    let session = initialize(&app).await;
+
+    let response = app
+        .oneshot(mcp_request(
+            "POST",
+            Some(&session),
+            "application/json, text/event-stream",
+            json!({
+                "jsonrpc": "2.0",
+                "id": 3,
+                "method": "tools/call",
+                "params": {"name": "no_such_tool", "arguments": {}}
+            }),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()[header::CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .starts_with("text/event-stream"));
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    let data = body
+        .lines()
+        .find_map(|line| line.strip_prefix("data: "))
+        .expect("SSE body should contain a data line");
+    let message: Value = serde_json::from_str(data).unwrap();
+    assert_eq!(message["id"], 3);
+    assert!(message["error"].is_object());
+}
+
+#[tokio::test]
+async fn test_get_opens_event_stream_for_session() {
+    let mock_server = MockServer::start().await;
+    let app = create_test_app(&mock_server).await;
+    let session = initialize(&app).await;
+
+    let response = app
+        .clone()
+        .oneshot(mcp_request(
+            "GET",
+            Some(&session),
+            "application/json",
+            Value::Null,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+
+    let response = app
+        .clone()
+        .oneshot(mcp_request("GET", None, "text/event-stream", Value::Null))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .oneshot(mcp_request(
+            "GET",
+            Some(&session),
+            "text/event-stream",
+            Value::Null,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()[header::CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .starts_with("text/event-stream"));
+}
+
+#[tokio::test]
+async fn test_delete_terminates_session() {
+    let mock_server = MockServer::start().await;
+    let app = create_test_app(&mock_server).await;
+    let session = initialize(&app).await;
+
+    let response = app
+        .clone()
+        .oneshot(mcp_request(
+            "DELETE",
+            Some(&session),
+            "application/json",
+            Value::Null,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = app
+        .oneshot(mcp_request(
+            "GET",
+            Some(&session),
+            "text/event-stream",
+            Value::Null,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_subscribe_requires_session() {
+    let mock_server = MockServer::start().await;
+    let app = create_test_app(&mock_server).await;
+
+    let response = app
+        .oneshot(mcp_request(
+            "POST",
+            None,
+            "application/json",
+            json!({
+                "jsonrpc": "2.0",
+                "id": 4,
+                "method": "resources/subscribe",
+                "params": {"uri": "miro://boards/uXjVO"}
+            }),
+        ))
+        .await
+        .unwrap();
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let message: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(message["error"]["code"], -32600);
+}
+
+#[tokio::test]
+async fn test_cancelled_notification_is_accepted() {
+    let mock_server = MockServer::start().await;
+    let app = create_test_app(&mock_server).await;
+    let session = initialize(&app).await;
+
+    let response = app
+        .oneshot(mcp_request(
+            "POST",
+            Some(&session),
+            "application/json",
+            json!({
+                "jsonrpc": "2.0",
+                "method": "notifications/cancelled",
+                "params": {"requestId": 42, "reason": "user aborted"}
+            }),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+}
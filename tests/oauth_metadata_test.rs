@@ -21,6 +21,15 @@ async fn test_authorization_server_metadata_endpoint() {
         encryption_key: [0u8; 32],
         port: 3010,
         base_url: Some("http://localhost:3010".to_string()),
+        resource_poll_interval_secs: 30,
+        max_resource_subscriptions: 10,
+        confirm_import_threshold: 100,
+        oauth_storage: Default::default(),
+        issue_proxy_tokens: false,
+        registration_policy: Default::default(),
+        consent_policy: Default::default(),
+        log_pii: false,
+        snapshot_dir: "snapshots".into(),
     });
 
     let app = Router::new()
@@ -91,6 +100,34 @@ async fn test_authorization_server_metadata_endpoint() {
         "Registration endpoint should be at /register"
     );
 
+    // Revocation (RFC 7009) and introspection (RFC 7662)
+    let revocation_endpoint = metadata["revocation_endpoint"].as_str().unwrap();
+    assert_eq!(revocation_endpoint, "http://localhost:3010/oauth/revoke");
+    let introspection_endpoint = metadata["introspection_endpoint"].as_str().unwrap();
+    assert_eq!(
+        introspection_endpoint,
+        "http://localhost:3010/oauth/introspect"
+    );
+    assert!(
+        !metadata["introspection_endpoint_auth_methods_supported"]
+            .as_array()
+            .unwrap()
+            .contains(&Value::String("none".to_string())),
+        "Introspection is for confidential clients only"
+    );
+
+    assert_eq!(
+        metadata["userinfo_endpoint"].as_str().unwrap(),
+        "http://localhost:3010/oauth/userinfo"
+    );
+
+    // Scopes a client may request
+    let scopes = metadata["scopes_supported"]
+        .as_array()
+        .expect("scopes_supported should be array");
+    assert!(scopes.contains(&Value::String("boards:read".to_string())));
+    assert!(scopes.contains(&Value::String("team:read".to_string())));
+
     // Grant and response types
     let grant_types = metadata["grant_types_supported"]
         .as_array()
@@ -99,6 +136,19 @@ async fn test_authorization_server_metadata_endpoint() {
         grant_types.contains(&Value::String("authorization_code".to_string())),
         "Should support authorization_code grant type"
     );
+    assert!(
+        grant_types.contains(&Value::String("refresh_token".to_string())),
+        "Should support refresh_token grant type"
+    );
+
+    let pkce_methods = metadata["code_challenge_methods_supported"]
+        .as_array()
+        .expect("code_challenge_methods_supported should be array");
+    assert_eq!(
+        pkce_methods,
+        &vec![Value::String("S256".to_string())],
+        "Only S256 PKCE should be advertised"
+    );
 
     let response_types = metadata["response_types_supported"]
         .as_array()
@@ -136,6 +186,15 @@ async fn test_protected_resource_metadata_endpoint() {
         encryption_key: [0u8; 32],
         port: 3010,
         base_url: Some("http://localhost:3010".to_string()),
+        resource_poll_interval_secs: 30,
+        max_resource_subscriptions: 10,
+        confirm_import_threshold: 100,
+        oauth_storage: Default::default(),
+        issue_proxy_tokens: false,
+        registration_policy: Default::default(),
+        consent_policy: Default::default(),
+        log_pii: false,
+        snapshot_dir: "snapshots".into(),
     });
 
     let app = Router::new()
@@ -201,6 +260,10 @@ async fn test_protected_resource_metadata_endpoint() {
         grant_types.contains(&Value::String("authorization_code".to_string())),
         "Should support authorization_code grant type"
     );
+    assert!(
+        grant_types.contains(&Value::String("refresh_token".to_string())),
+        "Should support refresh_token grant type"
+    );
 
     let response_types = metadata["response_types_supported"]
         .as_array()
@@ -227,6 +290,15 @@ async fn test_bearer_auth_returns_401_with_www_authenticate() {
         encryption_key: [0u8; 32],
         port: 3010,
         base_url: Some("http://localhost:3010".to_string()),
+        resource_poll_interval_secs: 30,
+        max_resource_subscriptions: 10,
+        confirm_import_threshold: 100,
+        oauth_storage: Default::default(),
+        issue_proxy_tokens: false,
+        registration_policy: Default::default(),
+        consent_policy: Default::default(),
+        log_pii: false,
+        snapshot_dir: "snapshots".into(),
     });
 
     // Create app with bearer middleware
@@ -244,7 +316,13 @@ async fn test_bearer_auth_returns_401_with_www_authenticate() {
         let cookie_manager = Arc::new(CookieManager::new(&config.encryption_key));
         let code_storage = CodeStorage::new();
 
-        let app = create_app_adr002(token_validator, config, oauth_provider, cookie_manager, code_storage);
+        let app = create_app_adr002(
+            token_validator,
+            config,
+            oauth_provider,
+            cookie_manager,
+            code_storage,
+        );
 
         // Make request without auth token
         let response = app
@@ -0,0 +1,428 @@
+#![cfg(feature = "oauth-proxy")]
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use miro_mcp_server::config::RegistrationPolicy;
+use miro_mcp_server::oauth::code_storage::CodeStorage;
+use miro_mcp_server::oauth::cookie_manager::CookieManager;
+use miro_mcp_server::oauth::proxy_provider::MiroOAuthProvider;
+use miro_mcp_server::{http_server::create_app_adr002, Config, TokenValidator};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+const REDIRECT_URI: &str = "https://claude.ai/api/mcp/auth_callback";
+
+fn create_app(registration_policy: RegistrationPolicy) -> Router {
+    let config = Arc::new(Config {
+        client_id: "test_client_id".to_string(),
+        client_secret: "test_client_secret".to_string(),
+        redirect_uri: "http://localhost:3010/oauth/callback".to_string(),
+        encryption_key: [0u8; 32],
+        port: 3010,
+        base_url: Some("http://localhost:3010".to_string()),
+        resource_poll_interval_secs: 30,
+        max_resource_subscriptions: 10,
+        confirm_import_threshold: 100,
+        oauth_storage: Default::default(),
+        issue_proxy_tokens: false,
+        registration_policy,
+        consent_policy: Default::default(),
+        log_pii: false,
+        snapshot_dir: "snapshots".into(),
+    });
+    let oauth_provider = Arc::new(MiroOAuthProvider::new(
+        config.client_id.clone(),
+        config.client_secret.clone(),
+        config.redirect_uri.clone(),
+    ));
+    let cookie_manager = Arc::new(CookieManager::new(&config.encryption_key));
+
+    create_app_adr002(
+        Arc::new(TokenValidator::new()),
+        config,
+        oauth_provider,
+        cookie_manager,
+        CodeStorage::new(),
+    )
+}
+
+/// Send a JSON request and return (status, body)
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    bearer: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let mut request = Request::builder().method(method).uri(uri);
+    if let Some(token) = bearer {
+        request = request.header("authorization", format!("Bearer {}", token));
+    }
+    let body = match body {
+        Some(body) => {
+            request = request.header("content-type", "application/json");
+            Body::from(body.to_string())
+        }
+        None => Body::empty(),
+    };
+    let response = app
+        .clone()
+        .oneshot(request.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, body)
+}
+
+fn registration(redirect_uri: &str) -> Value {
+    json!({
+        "client_name": "Test Client",
+        "redirect_uris": [redirect_uri],
+    })
+}
+
+/// Token request that authenticates the client but carries no grant: a
+/// registered secret gets 400 invalid_request, a wrong one 401
+async fn token_status(app: &Router, client_id: &str, client_secret: &str) -> StatusCode {
+    let form = format!(
+        "grant_type=refresh_token&client_id={}&client_secret={}",
+        client_id, client_secret
+    );
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/oauth/token")
+                .header("content-type", "application/x-www-form-urlencoded")
+                .body(Body::from(form))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+/// Registration returns the credentials needed to manage it later
+#[tokio::test]
+async fn test_read_registration_with_access_token() {
+    let app = create_app(RegistrationPolicy::default());
+    let (status, registered) = send(
+        &app,
+        "POST",
+        "/register",
+        None,
+        Some(registration(REDIRECT_URI)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let client_id = registered["client_id"].as_str().unwrap();
+    let token = registered["registration_access_token"].as_str().unwrap();
+    let uri = registered["registration_client_uri"].as_str().unwrap();
+    assert_eq!(uri, format!("http://localhost:3010/register/{}", client_id));
+    assert_eq!(registered["client_secret_expires_at"], 0);
+
+    let path = format!("/register/{}", client_id);
+    let (status, read) = send(&app, "GET", &path, Some(token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(read["client_id"], client_id);
+    assert_eq!(read["redirect_uris"], json!([REDIRECT_URI]));
+
+    let (status, _) = send(&app, "GET", &path, None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(&app, "GET", &path, Some("wrong"), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Another client's token can't manage this registration
+    let (_, other) = send(
+        &app,
+        "POST",
+        "/register",
+        None,
+        Some(registration(REDIRECT_URI)),
+    )
+    .await;
+    let other_token = other["registration_access_token"].as_str().unwrap();
+    let (status, _) = send(&app, "GET", &path, Some(other_token), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+/// Updates replace the metadata but keep the credentials
+#[tokio::test]
+async fn test_update_registration() {
+    let app = create_app(RegistrationPolicy::default());
+    let (_, registered) = send(
+        &app,
+        "POST",
+        "/register",
+        None,
+        Some(registration(REDIRECT_URI)),
+    )
+    .await;
+    let client_id = registered["client_id"].as_str().unwrap();
+    let token = registered["registration_access_token"].as_str().unwrap();
+    let path = format!("/register/{}", client_id);
+
+    let mut update = registration("https://claude.ai/api/mcp/other_callback");
+    update["client_id"] = json!(client_id);
+    let (status, updated) = send(&app, "PUT", &path, Some(token), Some(update.clone())).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        updated["redirect_uris"],
+        json!(["https://claude.ai/api/mcp/other_callback"])
+    );
+    // Secrets are stored hashed, so only the registration response carries one
+    assert!(updated.get("client_secret").is_none());
+    let secret = registered["client_secret"].as_str().unwrap();
+    assert_eq!(
+        token_status(&app, client_id, secret).await,
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        token_status(&app, client_id, "wrong").await,
+        StatusCode::UNAUTHORIZED
+    );
+
+    update["client_id"] = json!("another-client");
+    let (status, _) = send(&app, "PUT", &path, Some(token), Some(update)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+/// A deleted client can no longer be managed or authenticate
+#[tokio::test]
+async fn test_delete_registration() {
+    let app = create_app(RegistrationPolicy::default());
+    let (_, registered) = send(
+        &app,
+        "POST",
+        "/register",
+        None,
+        Some(registration(REDIRECT_URI)),
+    )
+    .await;
+    let client_id = registered["client_id"].as_str().unwrap();
+    let token = registered["registration_access_token"].as_str().unwrap();
+    let path = format!("/register/{}", client_id);
+
+    let (status, _) = send(&app, "DELETE", &path, Some(token), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send(&app, "GET", &path, Some(token), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!(
+                    "/oauth/authorize?response_type=code&client_id={}&redirect_uri={}",
+                    client_id, REDIRECT_URI
+                ))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+/// An initial access token, when configured, is required to register
+#[tokio::test]
+async fn test_initial_access_token_policy() {
+    let app = create_app(RegistrationPolicy {
+        initial_access_token: Some("let-me-in".to_string()),
+        ..Default::default()
+    });
+    let body = registration(REDIRECT_URI);
+
+    let (status, error) = send(&app, "POST", "/register", None, Some(body.clone())).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(error["error"], "invalid_token");
+    let (status, _) = send(&app, "POST", "/register", Some("wrong"), Some(body.clone())).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(&app, "POST", "/register", Some("let-me-in"), Some(body)).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+/// Redirect URIs must match an allowed host pattern
+#[tokio::test]
+async fn test_redirect_host_policy() {
+    let app = create_app(RegistrationPolicy {
+        allowed_redirect_hosts: vec!["claude.ai".to_string()],
+        ..Default::default()
+    });
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/register",
+        None,
+        Some(registration(REDIRECT_URI)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, error) = send(
+        &app,
+        "POST",
+        "/register",
+        None,
+        Some(registration("https://evil.example/callback")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["error"], "invalid_redirect_uri");
+}
+
+/// Plain http is only accepted for redirects to this machine
+#[tokio::test]
+async fn test_http_redirect_uri_must_be_loopback() {
+    let app = create_app(RegistrationPolicy::default());
+
+    for uri in [
+        "http://localhost:8080/cb",
+        "http://127.0.0.1:8080/cb",
+        "http://[::1]:8080/cb",
+    ] {
+        let (status, _) = send(&app, "POST", "/register", None, Some(registration(uri))).await;
+        assert_eq!(status, StatusCode::OK, "{}", uri);
+    }
+
+    for uri in [
+        "http://localhost.evil.example/cb",
+        "http://localhost@evil.example/cb",
+        "http://evil.example/cb",
+        "not a uri",
+    ] {
+        let (status, error) = send(&app, "POST", "/register", None, Some(registration(uri))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+        assert_eq!(error["error"], "invalid_redirect_uri");
+    }
+}
+
+/// Only token endpoint auth methods the server implements can be registered
+#[tokio::test]
+async fn test_unsupported_token_endpoint_auth_method_is_rejected() {
+    let app = create_app(RegistrationPolicy::default());
+
+    for method in ["none", "client_secret_basic", "client_secret_post"] {
+        let mut body = registration(REDIRECT_URI);
+        body["token_endpoint_auth_method"] = json!(method);
+        let (status, _) = send(&app, "POST", "/register", None, Some(body)).await;
+        assert_eq!(status, StatusCode::OK, "{}", method);
+    }
+
+    let mut body = registration(REDIRECT_URI);
+    body["token_endpoint_auth_method"] = json!("private_key_jwt");
+    let (status, error) = send(&app, "POST", "/register", None, Some(body)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["error"], "invalid_client_metadata");
+}
+
+/// Registrations stop once the client limit is reached
+#[tokio::test]
+async fn test_max_clients_policy() {
+    let app = create_app(RegistrationPolicy {
+        max_clients: Some(1),
+        ..Default::default()
+    });
+    let body = registration(REDIRECT_URI);
+
+    let (status, _) = send(&app, "POST", "/register", None, Some(body.clone())).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, error) = send(&app, "POST", "/register", None, Some(body)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(error["error"], "access_denied");
+}
+
+/// Registrations whose secret has expired no longer count toward the limit
+#[tokio::test]
+async fn test_expired_registration_frees_a_client_slot() {
+    let app = create_app(RegistrationPolicy {
+        max_clients: Some(1),
+        client_secret_ttl_secs: Some(0),
+        ..Default::default()
+    });
+    let body = registration(REDIRECT_URI);
+
+    let (status, _) = send(&app, "POST", "/register", None, Some(body.clone())).await;
+    assert_eq!(status, StatusCode::OK);
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    let (status, _) = send(&app, "POST", "/register", None, Some(body)).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+/// Public clients authenticate with PKCE alone and are issued no secret
+#[tokio::test]
+async fn test_public_client_gets_no_secret() {
+    let app = create_app(RegistrationPolicy {
+        client_secret_ttl_secs: Some(3600),
+        ..Default::default()
+    });
+    let mut body = registration(REDIRECT_URI);
+    body["token_endpoint_auth_method"] = json!("none");
+
+    let (status, registered) = send(&app, "POST", "/register", None, Some(body)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(registered.get("client_secret").is_none());
+    assert!(registered.get("client_secret_expires_at").is_none());
+    assert!(registered["registration_access_token"].is_string());
+}
+
+/// Client secrets expire after the configured lifetime
+#[tokio::test]
+async fn test_client_secret_expiry_policy() {
+    let app = create_app(RegistrationPolicy {
+        client_secret_ttl_secs: Some(3600),
+        ..Default::default()
+    });
+    let (_, registered) = send(
+        &app,
+        "POST",
+        "/register",
+        None,
+        Some(registration(REDIRECT_URI)),
+    )
+    .await;
+    let expires_at = registered["client_secret_expires_at"].as_i64().unwrap();
+    let issued_at = registered["client_id_issued_at"].as_i64().unwrap();
+    assert_eq!(expires_at - issued_at, 3600);
+
+    // An expired secret no longer authenticates
+    let app = create_app(RegistrationPolicy {
+        client_secret_ttl_secs: Some(0),
+        ..Default::default()
+    });
+    let (_, registered) = send(
+        &app,
+        "POST",
+        "/register",
+        None,
+        Some(registration(REDIRECT_URI)),
+    )
+    .await;
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/oauth/token")
+                .header("content-type", "application/x-www-form-urlencoded")
+                .body(Body::from(format!(
+                    "grant_type=refresh_token&refresh_token=abc&client_id={}&client_secret={}",
+                    registered["client_id"].as_str().unwrap(),
+                    registered["client_secret"].as_str().unwrap()
+                )))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,756 @@
+#![cfg(feature = "oauth-proxy")]
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use miro_mcp_server::oauth::code_storage::CodeStorage;
+use miro_mcp_server::oauth::cookie_manager::CookieManager;
+use miro_mcp_server::oauth::proxy_provider::MiroOAuthProvider;
+use miro_mcp_server::{http_server::create_app_adr002, Config, TokenValidator};
+use serde_json::Value;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+fn test_config() -> Config {
+    Config {
+        client_id: "test_client_id".to_string(),
+        client_secret: "test_client_secret".to_string(),
+        redirect_uri: "http://localhost:3010/oauth/callback".to_string(),
+        encryption_key: [0u8; 32],
+        port: 3010,
+        base_url: Some("http://localhost:3010".to_string()),
+        resource_poll_interval_secs: 30,
+        max_resource_subscriptions: 10,
+        confirm_import_threshold: 100,
+        oauth_storage: Default::default(),
+        issue_proxy_tokens: false,
+        registration_policy: Default::default(),
+        consent_policy: Default::default(),
+        log_pii: false,
+        snapshot_dir: "snapshots".into(),
+    }
+}
+
+fn create_test_app() -> Router {
+    create_app(test_config())
+}
+
+fn create_app(config: Config) -> Router {
+    let config = Arc::new(config);
+    let oauth_provider = Arc::new(MiroOAuthProvider::new(
+        config.client_id.clone(),
+        config.client_secret.clone(),
+        config.redirect_uri.clone(),
+    ));
+    let cookie_manager = Arc::new(CookieManager::new(&config.encryption_key));
+
+    create_app_adr002(
+        Arc::new(TokenValidator::new()),
+        config,
+        oauth_provider,
+        cookie_manager,
+        CodeStorage::new(),
+    )
+}
+
+const REDIRECT_URI: &str = "https://claude.ai/api/mcp/auth_callback";
+
+/// Register a client through DCR and return (client_id, client_secret)
+///
+/// Public clients get no secret; theirs is returned empty.
+async fn register_client(app: &Router, metadata: Value) -> (String, String) {
+    let mut body = serde_json::json!({
+        "client_name": "Test Client",
+        "redirect_uris": [REDIRECT_URI],
+    });
+    for (key, value) in metadata.as_object().unwrap() {
+        body[key] = value.clone();
+    }
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/register")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let registration: Value = serde_json::from_slice(&body).unwrap();
+    (
+        registration["client_id"].as_str().unwrap().to_string(),
+        registration["client_secret"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+    )
+}
+
+async fn post_form(app: &Router, uri: &str, form: &str) -> axum::response::Response {
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", "application/x-www-form-urlencoded")
+                .body(Body::from(form.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+async fn post_token(app: &Router, form: &str) -> StatusCode {
+    post_form(app, "/oauth/token", form).await.status()
+}
+
+/// Status and RFC 6749 `error` code of a rejected request
+async fn error_response(response: axum::response::Response) -> (StatusCode, String) {
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let error: Value = serde_json::from_slice(&body).unwrap();
+    assert!(error["error_description"].is_string());
+    (status, error["error"].as_str().unwrap().to_string())
+}
+
+async fn post_token_error(app: &Router, form: &str) -> (StatusCode, String) {
+    error_response(post_form(app, "/oauth/token", form).await).await
+}
+
+/// Refresh requests are authenticated like code exchanges
+#[tokio::test]
+async fn test_refresh_token_grant_requires_client_authentication() {
+    let app = create_test_app();
+    let (client_id, _) = register_client(&app, serde_json::json!({})).await;
+
+    let (status, error) = post_token_error(
+        &app,
+        &format!(
+            "grant_type=refresh_token&refresh_token=abc&client_id={}&client_secret=wrong",
+            client_id
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(error, "invalid_client");
+}
+
+/// A refresh request without a refresh_token is rejected before calling Miro
+#[tokio::test]
+async fn test_refresh_token_grant_requires_refresh_token() {
+    let app = create_test_app();
+    let (client_id, client_secret) = register_client(&app, serde_json::json!({})).await;
+
+    let (status, error) = post_token_error(
+        &app,
+        &format!(
+            "grant_type=refresh_token&client_id={}&client_secret={}",
+            client_id, client_secret
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error, "invalid_request");
+}
+
+/// Clients that registered only for authorization_code can't refresh
+#[tokio::test]
+async fn test_refresh_token_grant_requires_registered_grant_type() {
+    let app = create_test_app();
+    let (client_id, client_secret) = register_client(
+        &app,
+        serde_json::json!({"grant_types": ["authorization_code"]}),
+    )
+    .await;
+
+    let (status, error) = post_token_error(
+        &app,
+        &format!(
+            "grant_type=refresh_token&refresh_token=abc&client_id={}&client_secret={}",
+            client_id, client_secret
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error, "unauthorized_client");
+}
+
+/// Miro refresh tokens this server didn't issue to the client are never forwarded
+#[tokio::test]
+async fn test_refresh_token_grant_rejects_unbound_token() {
+    let app = create_test_app();
+    let (client_id, client_secret) = register_client(&app, serde_json::json!({})).await;
+    let (public_client_id, _) = register_client(
+        &app,
+        serde_json::json!({"token_endpoint_auth_method": "none"}),
+    )
+    .await;
+
+    for form in [
+        format!(
+            "grant_type=refresh_token&refresh_token=someone-elses-miro-token&client_id={}&client_secret={}",
+            client_id, client_secret
+        ),
+        // Public clients need no secret
+        format!(
+            "grant_type=refresh_token&refresh_token=someone-elses-miro-token&client_id={}",
+            public_client_id
+        ),
+    ] {
+        let (status, error) = post_token_error(&app, &form).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(error, "invalid_grant");
+    }
+}
+
+/// The configured Miro client_id is not an MCP client and can't use the token endpoint
+#[tokio::test]
+async fn test_configured_miro_client_id_is_not_a_public_client() {
+    let app = create_test_app();
+    let (status, error) = post_token_error(
+        &app,
+        "grant_type=refresh_token&refresh_token=some-miro-token&client_id=test_client_id",
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(error, "invalid_client");
+}
+
+/// With proxy tokens, refresh tokens are checked locally and never reach Miro
+#[tokio::test]
+async fn test_proxy_refresh_token_grant_rejects_unknown_token() {
+    let app = create_app(Config {
+        issue_proxy_tokens: true,
+        ..test_config()
+    });
+    let (client_id, client_secret) = register_client(&app, serde_json::json!({})).await;
+
+    let (status, error) = post_token_error(
+        &app,
+        &format!(
+            "grant_type=refresh_token&refresh_token=mcp_rt_unknown&client_id={}&client_secret={}",
+            client_id, client_secret
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error, "invalid_grant");
+}
+
+/// Grant types other than authorization_code and refresh_token are rejected
+#[tokio::test]
+async fn test_unsupported_grant_type_is_rejected() {
+    let app = create_test_app();
+    let (status, error) = post_token_error(
+        &app,
+        "grant_type=client_credentials&client_id=test_client_id",
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error, "unsupported_grant_type");
+}
+
+/// Start an authorization at /oauth/authorize and return the response
+async fn authorize(app: &Router, query: &str) -> axum::response::Response {
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/oauth/authorize?{}", query))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+/// Query parameters of a redirect response's Location
+fn location_params(response: &axum::response::Response) -> Vec<(String, String)> {
+    let location = url::Url::parse(response.headers()["location"].to_str().unwrap()).unwrap();
+    location.query_pairs().into_owned().collect()
+}
+
+fn param<'a>(params: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    params
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Register a public client, run /oauth/authorize and Miro's redirect to
+/// /oauth/callback, and return (client_id, code, redirect to the client)
+async fn authorize_with_challenge(
+    app: &Router,
+    challenge: &str,
+) -> (String, String, axum::response::Response) {
+    let (client_id, _) = register_client(
+        app,
+        serde_json::json!({"token_endpoint_auth_method": "none"}),
+    )
+    .await;
+    let response = authorize(
+        app,
+        &format!(
+            "response_type=code&client_id={}&redirect_uri={}&state=client-state&code_challenge={}&code_challenge_method=S256",
+            client_id, REDIRECT_URI, challenge
+        ),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FOUND);
+
+    let cookie = response.headers()["set-cookie"]
+        .to_str()
+        .unwrap()
+        .split(';')
+        .next()
+        .unwrap()
+        .to_string();
+    let params = location_params(&response);
+    let state = param(&params, "state").unwrap();
+
+    let code = format!("miro-code-{}", client_id);
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/oauth/callback?code={}&state={}", code, state))
+                .header("cookie", cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FOUND);
+    (client_id, code, response)
+}
+
+/// Unknown clients and unregistered redirect URIs are never redirected to
+#[tokio::test]
+async fn test_authorize_rejects_unknown_client_and_redirect_uri() {
+    let app = create_test_app();
+
+    let response = authorize(
+        &app,
+        &format!(
+            "response_type=code&client_id=unknown&redirect_uri={}",
+            REDIRECT_URI
+        ),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(response.headers().get("location").is_none());
+
+    let (client_id, _) = register_client(&app, serde_json::json!({})).await;
+    let response = authorize(
+        &app,
+        &format!(
+            "response_type=code&client_id={}&redirect_uri=https://evil.example/callback",
+            client_id
+        ),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(response.headers().get("location").is_none());
+}
+
+/// Other authorization errors are sent to the client's redirect_uri
+#[tokio::test]
+async fn test_authorize_redirects_errors_to_client() {
+    let app = create_test_app();
+    let (client_id, _) = register_client(&app, serde_json::json!({})).await;
+
+    let response = authorize(
+        &app,
+        &format!(
+            "response_type=token&client_id={}&redirect_uri={}&state=xyz",
+            client_id, REDIRECT_URI
+        ),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FOUND);
+    let location = response.headers()["location"].to_str().unwrap();
+    assert!(location.starts_with(REDIRECT_URI));
+    let params = location_params(&response);
+    assert_eq!(param(&params, "error"), Some("unsupported_response_type"));
+    assert_eq!(param(&params, "state"), Some("xyz"));
+}
+
+/// Public clients must send an S256 challenge
+#[tokio::test]
+async fn test_authorize_requires_s256_pkce_for_public_clients() {
+    let app = create_test_app();
+    let (client_id, _) = register_client(
+        &app,
+        serde_json::json!({"token_endpoint_auth_method": "none"}),
+    )
+    .await;
+    let pkce = miro_mcp_server::oauth::pkce::generate_pkce_pair();
+    let base = format!(
+        "response_type=code&client_id={}&redirect_uri={}",
+        client_id, REDIRECT_URI
+    );
+
+    let response = authorize(&app, &base).await;
+    assert_eq!(
+        param(&location_params(&response), "error"),
+        Some("invalid_request")
+    );
+
+    let response = authorize(
+        &app,
+        &format!(
+            "{}&code_challenge={}&code_challenge_method=plain",
+            base, pkce.verifier
+        ),
+    )
+    .await;
+    assert_eq!(
+        param(&location_params(&response), "error"),
+        Some("invalid_request")
+    );
+
+    let response = authorize(
+        &app,
+        &format!(
+            "{}&code_challenge={}&code_challenge_method=S256",
+            base, pkce.challenge
+        ),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FOUND);
+    let location = response.headers()["location"].to_str().unwrap();
+    assert!(location.starts_with("https://miro.com/oauth/authorize"));
+}
+
+/// Only supported scopes are requested from Miro; with none left the client gets invalid_scope
+#[tokio::test]
+async fn test_authorize_maps_requested_scope() {
+    let app = create_test_app();
+    let (client_id, _) = register_client(&app, serde_json::json!({})).await;
+    let base = format!(
+        "response_type=code&client_id={}&redirect_uri={}",
+        client_id, REDIRECT_URI
+    );
+
+    let response = authorize(&app, &base).await;
+    assert_eq!(
+        param(&location_params(&response), "scope"),
+        Some("boards:read boards:write")
+    );
+
+    let response = authorize(
+        &app,
+        &format!("{}&scope=boards:read%20auditlogs:read", base),
+    )
+    .await;
+    assert_eq!(
+        param(&location_params(&response), "scope"),
+        Some("boards:read")
+    );
+
+    let response = authorize(&app, &format!("{}&scope=auditlogs:read&state=xyz", base)).await;
+    let location = response.headers()["location"].to_str().unwrap();
+    assert!(location.starts_with(REDIRECT_URI));
+    assert_eq!(
+        param(&location_params(&response), "error"),
+        Some("invalid_scope")
+    );
+}
+
+/// Show the consent page for a new confidential client and return
+/// (state cookie, CSRF token, page)
+async fn consent_page(app: &Router) -> (String, String, String) {
+    let (client_id, _) = register_client(app, serde_json::json!({})).await;
+    let response = authorize(
+        app,
+        &format!(
+            "response_type=code&client_id={}&redirect_uri={}&state=xyz&scope=boards:read",
+            client_id, REDIRECT_URI
+        ),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-frame-options"], "DENY");
+
+    let cookie = response.headers()["set-cookie"]
+        .to_str()
+        .unwrap()
+        .split(';')
+        .next()
+        .unwrap()
+        .to_string();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let page = String::from_utf8(body.to_vec()).unwrap();
+    let csrf_token = page
+        .split(r#"name="csrf_token" value=""#)
+        .nth(1)
+        .and_then(|rest| rest.split('"').next())
+        .unwrap()
+        .to_string();
+    (cookie, csrf_token, page)
+}
+
+async fn post_consent(app: &Router, cookie: &str, form: &str) -> axum::response::Response {
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/oauth/consent")
+                .header("content-type", "application/x-www-form-urlencoded")
+                .header("cookie", cookie)
+                .body(Body::from(form.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+fn consent_app() -> Router {
+    let mut config = test_config();
+    config.consent_policy.required = true;
+    create_app(config)
+}
+
+/// With consent required, the user sees the client and its scopes before Miro
+#[tokio::test]
+async fn test_consent_page_approve_continues_to_miro() {
+    let app = consent_app();
+    let (cookie, csrf_token, page) = consent_page(&app).await;
+    assert!(page.contains("Test Client"));
+    assert!(page.contains("claude.ai"));
+    assert!(page.contains("boards:read"));
+    assert!(!page.contains("boards:write"));
+
+    let response = post_consent(&app, &cookie, "csrf_token=wrong-token&decision=approve").await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = post_consent(
+        &app,
+        "",
+        &format!("csrf_token={}&decision=approve", csrf_token),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = post_consent(
+        &app,
+        &cookie,
+        &format!("csrf_token={}&decision=approve", csrf_token),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FOUND);
+    let location = response.headers()["location"].to_str().unwrap();
+    assert!(location.starts_with("https://miro.com/oauth/authorize"));
+    assert_eq!(
+        param(&location_params(&response), "scope"),
+        Some("boards:read")
+    );
+    assert!(response.headers().get("set-cookie").is_some());
+}
+
+/// Denying consent sends access_denied to the client
+#[tokio::test]
+async fn test_consent_page_deny_returns_access_denied() {
+    let app = consent_app();
+    let (cookie, csrf_token, _) = consent_page(&app).await;
+
+    let response = post_consent(
+        &app,
+        &cookie,
+        &format!("csrf_token={}&decision=deny", csrf_token),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FOUND);
+    let location = response.headers()["location"].to_str().unwrap();
+    assert!(location.starts_with(REDIRECT_URI));
+    let params = location_params(&response);
+    assert_eq!(param(&params, "error"), Some("access_denied"));
+    assert_eq!(param(&params, "state"), Some("xyz"));
+}
+
+/// The callback returns the code with the client's own state
+#[tokio::test]
+async fn test_callback_returns_client_state() {
+    let app = create_test_app();
+    let pkce = miro_mcp_server::oauth::pkce::generate_pkce_pair();
+
+    let (_, code, response) = authorize_with_challenge(&app, &pkce.challenge).await;
+    let location = response.headers()["location"].to_str().unwrap();
+    assert!(location.starts_with(REDIRECT_URI));
+    let params = location_params(&response);
+    assert_eq!(param(&params, "code"), Some(code.as_str()));
+    assert_eq!(param(&params, "state"), Some("client-state"));
+}
+
+/// A proxy code can't be redeemed without the client's code_verifier
+#[tokio::test]
+async fn test_token_rejects_missing_or_wrong_code_verifier() {
+    let app = create_test_app();
+    let pkce = miro_mcp_server::oauth::pkce::generate_pkce_pair();
+
+    let (client_id, code, _) = authorize_with_challenge(&app, &pkce.challenge).await;
+    let status = post_token(
+        &app,
+        &format!(
+            "grant_type=authorization_code&code={}&redirect_uri={}&client_id={}",
+            code, REDIRECT_URI, client_id
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let other = miro_mcp_server::oauth::pkce::generate_pkce_pair();
+    let (client_id, code, _) = authorize_with_challenge(&app, &pkce.challenge).await;
+    let status = post_token(
+        &app,
+        &format!(
+            "grant_type=authorization_code&code={}&redirect_uri={}&client_id={}&code_verifier={}",
+            code, REDIRECT_URI, client_id, other.verifier
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+/// The token request must repeat the authorization request's redirect_uri
+#[tokio::test]
+async fn test_token_rejects_different_redirect_uri() {
+    let app = create_test_app();
+    let pkce = miro_mcp_server::oauth::pkce::generate_pkce_pair();
+
+    let (client_id, code, _) = authorize_with_challenge(&app, &pkce.challenge).await;
+    let (status, error) = post_token_error(
+        &app,
+        &format!(
+            "grant_type=authorization_code&code={}&redirect_uri=https://evil.example/callback&client_id={}&code_verifier={}",
+            code, client_id, pkce.verifier
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error, "invalid_grant");
+
+    // The code was consumed by the failed attempt
+    let (status, error) = post_token_error(
+        &app,
+        &format!(
+            "grant_type=authorization_code&code={}&redirect_uri={}&client_id={}&code_verifier={}",
+            code, REDIRECT_URI, client_id, pkce.verifier
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error, "invalid_grant");
+}
+
+/// Revocation and introspection require an authenticated client
+#[tokio::test]
+async fn test_revoke_and_introspect_require_client_authentication() {
+    let app = create_test_app();
+    let (client_id, _) = register_client(&app, serde_json::json!({})).await;
+
+    for uri in ["/oauth/revoke", "/oauth/introspect"] {
+        let response = post_form(&app, uri, "token=abc").await;
+        assert!(response.headers().contains_key("www-authenticate"));
+        let (status, error) = error_response(response).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(error, "invalid_client");
+
+        let response = post_form(
+            &app,
+            uri,
+            &format!("token=abc&client_id={}&client_secret=wrong", client_id),
+        )
+        .await;
+        let (status, error) = error_response(response).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(error, "invalid_client");
+    }
+}
+
+/// Public clients may revoke their tokens but not introspect
+#[tokio::test]
+async fn test_introspect_is_for_confidential_clients() {
+    let app = create_app(Config {
+        issue_proxy_tokens: true,
+        ..test_config()
+    });
+    let (client_id, _) = register_client(
+        &app,
+        serde_json::json!({"token_endpoint_auth_method": "none"}),
+    )
+    .await;
+
+    let response = post_form(
+        &app,
+        "/oauth/introspect",
+        &format!("token=mcp_at_unknown&client_id={}", client_id),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Unknown tokens count as revoked
+    let response = post_form(
+        &app,
+        "/oauth/revoke",
+        &format!("token=mcp_rt_unknown&client_id={}", client_id),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+/// Without proxy tokens, Miro tokens not issued to the client are never sent to Miro
+#[tokio::test]
+async fn test_revoke_ignores_tokens_issued_to_other_clients() {
+    let app = create_test_app();
+    let (client_id, _) = register_client(
+        &app,
+        serde_json::json!({"token_endpoint_auth_method": "none"}),
+    )
+    .await;
+
+    let response = post_form(
+        &app,
+        "/oauth/revoke",
+        &format!("token=someone-elses-miro-token&client_id={}", client_id),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+/// Tokens that don't resolve are reported inactive, without detail
+#[tokio::test]
+async fn test_introspect_unknown_token_is_inactive() {
+    let app = create_app(Config {
+        issue_proxy_tokens: true,
+        ..test_config()
+    });
+    let (client_id, client_secret) = register_client(&app, serde_json::json!({})).await;
+
+    let response = post_form(
+        &app,
+        "/oauth/introspect",
+        &format!(
+            "token=mcp_at_unknown&client_id={}&client_secret={}",
+            client_id, client_secret
+        ),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let introspection: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(introspection, serde_json::json!({"active": false}));
+}
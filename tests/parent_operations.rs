@@ -24,6 +24,15 @@ fn get_test_config() -> Config {
         encryption_key: [0u8; 32],
         port: 3000,
         base_url: Some("http://localhost:3000".to_string()),
+        resource_poll_interval_secs: 30,
+        max_resource_subscriptions: 10,
+        confirm_import_threshold: 100,
+        oauth_storage: Default::default(),
+        issue_proxy_tokens: false,
+        registration_policy: Default::default(),
+        consent_policy: Default::default(),
+        log_pii: false,
+        snapshot_dir: "snapshots".into(),
     }
 }
 
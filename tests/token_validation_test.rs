@@ -357,3 +357,39 @@ async fn test_scopes_parsing() {
         ]
     );
 }
+
+/// Test user, team and organization names are kept when Miro includes them
+#[tokio::test]
+async fn test_identity_parsing() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/oauth-token"))
+        .and(bearer_token("identity_token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "user_id": "user123",
+            "team_id": "team456",
+            "scopes": "boards:read team:read",
+            "user": {"id": "user123", "name": "Ada"},
+            "team": {"id": "team456", "name": "Design"},
+            "organization": {"id": "org789", "name": "Example Inc"}
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let validator =
+        TokenValidator::new_with_endpoint(format!("{}/v1/oauth-token", mock_server.uri()));
+
+    let user_info = validator.validate_token("identity_token").await.unwrap();
+    assert_eq!(user_info.identity.user_name.as_deref(), Some("Ada"));
+    assert_eq!(user_info.identity.team_name.as_deref(), Some("Design"));
+    assert_eq!(
+        user_info.identity.organization_id.as_deref(),
+        Some("org789")
+    );
+    assert_eq!(
+        user_info.identity.organization_name.as_deref(),
+        Some("Example Inc")
+    );
+    assert_eq!(user_info.identity.email, None);
+}